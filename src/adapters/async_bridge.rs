@@ -0,0 +1,256 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Adapters bridging synchronous and asynchronous configuration sources.
+//!
+//! `SyncSourceAdapter` exposes any `ConfigSource` as an `AsyncConfigSource`, and
+//! `BlockingSourceAdapter` exposes any `AsyncConfigSource` as a `ConfigSource`.
+
+use crate::adapters::runtime::block_on;
+use crate::domain::{ConfigKey, ConfigValue, Result};
+use crate::ports::{AsyncConfigSource, BoxFuture, ConfigSource};
+
+/// Exposes a synchronous configuration source through the async interface.
+///
+/// Every call runs the wrapped source inline and returns a ready future, so
+/// this is intended for sources whose operations are cheap (files, environment,
+/// command-line arguments).
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::{EnvVarAdapter, SyncSourceAdapter};
+/// use hexcfg::domain::ConfigKey;
+/// use hexcfg::ports::AsyncConfigSource;
+/// use std::collections::HashMap;
+///
+/// let mut values = HashMap::new();
+/// values.insert("app.name".to_string(), "demo".to_string());
+///
+/// let source = SyncSourceAdapter::new(EnvVarAdapter::with_values(values));
+///
+/// # tokio_test::block_on(async {
+/// let value = source.get(&ConfigKey::from("app.name")).await.unwrap();
+/// assert_eq!(value.unwrap().as_str(), "demo");
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct SyncSourceAdapter<S> {
+    /// The wrapped synchronous source
+    inner: S,
+}
+
+impl<S: ConfigSource> SyncSourceAdapter<S> {
+    /// Wraps a synchronous source.
+    pub fn new(source: S) -> Self {
+        Self { inner: source }
+    }
+
+    /// Returns a reference to the wrapped source.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Consumes the adapter and returns the wrapped source.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: ConfigSource> AsyncConfigSource for SyncSourceAdapter<S> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+        let result = self.inner.get(key);
+        Box::pin(async move { result })
+    }
+
+    fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> {
+        let result = self.inner.all_keys();
+        Box::pin(async move { result })
+    }
+
+    fn reload(&mut self) -> BoxFuture<'_, Result<()>> {
+        let result = self.inner.reload();
+        Box::pin(async move { result })
+    }
+}
+
+/// Exposes an asynchronous configuration source through the synchronous interface.
+///
+/// Each call blocks on the source's future using a shared runtime. When called
+/// from inside a tokio runtime the future runs on a separate thread, so this
+/// never panics with a nested-runtime error, but it does block the calling
+/// thread. Prefer [`AsyncConfigService`](crate::service::AsyncConfigService) in
+/// async applications.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::{BlockingSourceAdapter, EnvVarAdapter, SyncSourceAdapter};
+/// use hexcfg::ports::ConfigSource;
+/// use hexcfg::service::DefaultConfigService;
+/// use hexcfg::domain::ConfigurationService;
+/// use std::collections::HashMap;
+///
+/// let mut values = HashMap::new();
+/// values.insert("app.name".to_string(), "demo".to_string());
+/// let async_source = SyncSourceAdapter::new(EnvVarAdapter::with_values(values));
+///
+/// let mut service = DefaultConfigService::new();
+/// service.add_source(Box::new(BlockingSourceAdapter::new(async_source)));
+/// assert_eq!(service.get_str("app.name").unwrap().as_str(), "demo");
+/// ```
+#[derive(Debug)]
+pub struct BlockingSourceAdapter<S> {
+    /// The wrapped asynchronous source
+    inner: S,
+}
+
+impl<S: AsyncConfigSource> BlockingSourceAdapter<S> {
+    /// Wraps an asynchronous source.
+    pub fn new(source: S) -> Self {
+        Self { inner: source }
+    }
+
+    /// Returns a reference to the wrapped source.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Consumes the adapter and returns the wrapped source.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: AsyncConfigSource> ConfigSource for BlockingSourceAdapter<S> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        block_on(self.inner.get(key))
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        block_on(self.inner.all_keys())
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        block_on(self.inner.reload())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct CountingSource {
+        reloads: Arc<AtomicUsize>,
+    }
+
+    impl AsyncConfigSource for CountingSource {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn priority(&self) -> u8 {
+            4
+        }
+
+        fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                Ok((key.as_str() == "reloads")
+                    .then(|| ConfigValue::from(self.reloads.load(Ordering::SeqCst).to_string())))
+            })
+        }
+
+        fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> {
+            Box::pin(async { Ok(vec![ConfigKey::from("reloads")]) })
+        }
+
+        fn reload(&mut self) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move {
+                self.reloads.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        }
+    }
+
+    struct StaticSource;
+
+    impl ConfigSource for StaticSource {
+        fn name(&self) -> &str {
+            "static"
+        }
+
+        fn priority(&self) -> u8 {
+            2
+        }
+
+        fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+            Ok((key.as_str() == "app.name").then(|| ConfigValue::from("demo")))
+        }
+
+        fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+            Ok(vec![ConfigKey::from("app.name")])
+        }
+
+        fn reload(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sync_source_adapter() {
+        let mut source = SyncSourceAdapter::new(StaticSource);
+        assert_eq!(AsyncConfigSource::name(&source), "static");
+        assert_eq!(AsyncConfigSource::priority(&source), 2);
+
+        tokio_test::block_on(async {
+            let value = AsyncConfigSource::get(&source, &ConfigKey::from("app.name"))
+                .await
+                .unwrap();
+            assert_eq!(value.unwrap().as_str(), "demo");
+            assert!(AsyncConfigSource::reload(&mut source).await.is_ok());
+        });
+    }
+
+    #[test]
+    fn test_blocking_source_adapter() {
+        let reloads = Arc::new(AtomicUsize::new(0));
+        let mut source = BlockingSourceAdapter::new(CountingSource {
+            reloads: Arc::clone(&reloads),
+        });
+
+        source.reload().unwrap();
+        let value = source.get(&ConfigKey::from("reloads")).unwrap();
+        assert_eq!(value.unwrap().as_str(), "1");
+        assert_eq!(source.all_keys().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_blocking_source_adapter_inside_runtime() {
+        let reloads = Arc::new(AtomicUsize::new(0));
+        let mut source = BlockingSourceAdapter::new(CountingSource {
+            reloads: Arc::clone(&reloads),
+        });
+
+        // Must not panic with a nested runtime error
+        source.reload().unwrap();
+        assert_eq!(reloads.load(Ordering::SeqCst), 1);
+    }
+}
@@ -3,10 +3,14 @@
 //! Command-line argument configuration source adapter.
 //!
 //! This module provides an adapter that reads configuration values from
-//! command-line arguments.
+//! command-line arguments, and a schema that derives `clap` flags from typed
+//! configuration keys.
 
-use crate::domain::{ConfigKey, ConfigValue, Result};
+use crate::domain::{ConfigKey, ConfigValue, KeyInfo, Result};
 use crate::ports::ConfigSource;
+use crate::service::DefaultConfigService;
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use std::collections::HashMap;
 
 /// Maximum length for command-line argument keys (prevents DoS)
@@ -21,12 +25,26 @@ const MAX_ARG_VALUE_LEN: usize = 65536; // 64KB
 /// multiple argument formats:
 /// - `--key=value`: Long form with equals sign
 /// - `--key value`: Long form with space-separated value
-/// - `-k value`: Short form with space-separated value
+/// - `--key`: Boolean flag, sets `key` to `true` when no value follows
+/// - `--no-key`: Negated boolean flag, sets `key` to `false`
+/// - `-k value`, `-k=value`, `-k`: Short forms
+/// - `-abc`: Combined short boolean flags, sets `a`, `b` and `c` to `true`
+/// - `-p8080`: Short form with the value attached
+/// - `-Dkey=value`: Property definition, as for the JVM
+/// - `--`: Ends the options; all later arguments are positional
+///
+/// A value may start with a dash only if it is a number, as in `--offset -5`,
+/// or a lone `-`. When a flag is repeated, the last value is kept under its key
+/// and all values are also kept as a list under indexed keys, so
+/// `--tag a --tag b` sets `tag` to `b`, `tag.0` to `a` and `tag.1` to `b`, the
+/// same keys a YAML sequence produces. Arguments that are not consumed as flags
+/// or values are available from [`positional`](Self::positional).
 ///
 /// # Priority
 ///
-/// Command-line arguments have the highest priority (3), which means they override
-/// both environment variables (priority 2) and configuration files (priority 1).
+/// Command-line arguments have the highest default priority (3), which means they
+/// override both environment variables (priority 2) and configuration files
+/// (priority 1). Use [`with_priority`](Self::with_priority) to change this.
 ///
 /// # Examples
 ///
@@ -37,10 +55,17 @@ const MAX_ARG_VALUE_LEN: usize = 65536; // 64KB
 /// let args = vec!["--database.host=localhost", "--port", "5432"];
 /// let adapter = CommandLineAdapter::from_args(args);
 /// ```
+///
+/// To validate arguments and generate `--help`, derive the flags from a
+/// [`CliSchema`] and use [`from_matches`](Self::from_matches) instead.
 #[derive(Debug, Clone)]
 pub struct CommandLineAdapter {
     /// Parsed configuration values
     values: HashMap<String, String>,
+    /// Arguments that were not consumed as flags or values
+    positional: Vec<String>,
+    /// Priority for this source
+    priority: u8,
 }
 
 impl CommandLineAdapter {
@@ -56,6 +81,8 @@ impl CommandLineAdapter {
     pub fn new() -> Self {
         Self {
             values: HashMap::new(),
+            positional: Vec::new(),
+            priority: 3,
         }
     }
 
@@ -97,85 +124,336 @@ impl CommandLineAdapter {
         Self::from_args(args)
     }
 
+    /// Creates a new command-line adapter from arguments parsed by `clap`.
+    ///
+    /// Only the keys of `schema` that were given on the command line are
+    /// provided, so values from other sources and defaults are not
+    /// overridden.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::{CliSchema, CommandLineAdapter};
+    /// use hexcfg::domain::TypedKey;
+    /// use hexcfg::ports::ConfigSource;
+    ///
+    /// const DB_HOST: TypedKey<String> = TypedKey::new("db.host");
+    /// const DB_PORT: TypedKey<u16> = TypedKey::new("db.port").default(5432);
+    ///
+    /// let schema = CliSchema::new([DB_HOST.info(), DB_PORT.info()]);
+    /// let matches = schema
+    ///     .command("myapp")
+    ///     .get_matches_from(["myapp", "--db.host", "localhost"]);
+    ///
+    /// let adapter = CommandLineAdapter::from_matches(&matches, &schema);
+    /// assert_eq!(adapter.all_keys().unwrap().len(), 1);
+    /// ```
+    pub fn from_matches(matches: &ArgMatches, schema: &CliSchema) -> Self {
+        let mut adapter = Self::new();
+        for info in schema.keys() {
+            // Keys missing from the command the matches came from are skipped
+            let Ok(Some(value)) = matches.try_get_one::<String>(info.key) else {
+                continue;
+            };
+            if matches.value_source(info.key) == Some(ValueSource::CommandLine) {
+                adapter.values.insert(info.key.to_string(), value.clone());
+            }
+        }
+        adapter
+    }
+
+    /// Sets the priority of this source (default 3).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::CommandLineAdapter;
+    /// use hexcfg::ports::ConfigSource;
+    ///
+    /// let adapter = CommandLineAdapter::from_args(vec!["--port=8080"]).with_priority(20);
+    /// assert_eq!(adapter.priority(), 20);
+    /// ```
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns the arguments that were not consumed as flags or flag values,
+    /// in order, including every argument after `--`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::CommandLineAdapter;
+    ///
+    /// let adapter = CommandLineAdapter::from_args(vec!["input.txt", "--level=3", "--", "--raw"]);
+    /// assert_eq!(adapter.positional(), ["input.txt", "--raw"]);
+    /// ```
+    pub fn positional(&self) -> &[String] {
+        &self.positional
+    }
+
     /// Parses command-line arguments and populates the values map.
     fn parse_args<S: AsRef<str>>(&mut self, args: Vec<S>) {
-        let mut i = 0;
-        while i < args.len() {
-            let arg = args[i].as_ref();
-
-            // Handle --key=value format
-            if arg.starts_with("--") && arg.contains('=') {
-                if let Some((key, value)) = arg.strip_prefix("--").and_then(|s| s.split_once('=')) {
-                    // Validate input sizes to prevent DoS
-                    if key.len() <= MAX_ARG_KEY_LEN && value.len() <= MAX_ARG_VALUE_LEN {
-                        self.values.insert(key.to_string(), value.to_string());
-                    }
-                }
-                i += 1;
+        let mut occurrences: Vec<(String, String)> = Vec::new();
+        let mut push = |key: &str, value: &str| {
+            // Validate input sizes to prevent DoS
+            if !key.is_empty() && key.len() <= MAX_ARG_KEY_LEN && value.len() <= MAX_ARG_VALUE_LEN {
+                occurrences.push((key.to_string(), value.to_string()));
             }
-            // Handle --key value format
-            else if arg.starts_with("--") {
-                if let Some(key) = arg.strip_prefix("--") {
-                    if i + 1 < args.len() {
-                        let next_arg = args[i + 1].as_ref();
-                        // Make sure the next argument is not another flag
-                        if !next_arg.starts_with('-') {
-                            // Validate input sizes to prevent DoS
-                            if key.len() <= MAX_ARG_KEY_LEN && next_arg.len() <= MAX_ARG_VALUE_LEN {
-                                self.values.insert(key.to_string(), next_arg.to_string());
-                            }
-                            i += 2;
-                        } else {
-                            i += 1;
-                        }
-                    } else {
-                        i += 1;
-                    }
+        };
+
+        let mut args = args.iter().map(|arg| arg.as_ref()).peekable();
+        while let Some(arg) = args.next() {
+            // Everything after `--` is positional
+            if arg == "--" {
+                self.positional.extend(args.map(String::from));
+                break;
+            }
+
+            if let Some(long) = arg.strip_prefix("--") {
+                if let Some((key, value)) = long.split_once('=') {
+                    // --key=value
+                    push(key, value);
+                } else if let Some(key) = long.strip_prefix("no-") {
+                    // --no-key
+                    push(key, "false");
                 } else {
-                    i += 1;
+                    // --key value, or a boolean --key
+                    let value = args.next_if(|next| is_value(next)).unwrap_or("true");
+                    push(long, value);
                 }
-            }
-            // Handle -k value format (single character short form)
-            else if arg.starts_with('-') && arg.len() == 2 {
-                if let Some(key) = arg.strip_prefix('-') {
-                    if i + 1 < args.len() {
-                        let next_arg = args[i + 1].as_ref();
-                        // Make sure the next argument is not another flag
-                        if !next_arg.starts_with('-') {
-                            // Validate input sizes to prevent DoS
-                            if key.len() <= MAX_ARG_KEY_LEN && next_arg.len() <= MAX_ARG_VALUE_LEN {
-                                self.values.insert(key.to_string(), next_arg.to_string());
-                            }
-                            i += 2;
-                        } else {
-                            i += 1;
-                        }
-                    } else {
-                        i += 1;
+            } else if let Some(short) = arg.strip_prefix('-').filter(|_| is_flag(arg)) {
+                if let Some(property) = short.strip_prefix('D').filter(|p| !p.is_empty()) {
+                    // -Dkey=value, or a boolean -Dkey
+                    let (key, value) = property.split_once('=').unwrap_or((property, "true"));
+                    push(key, value);
+                } else if let Some((key, value)) = short.split_once('=') {
+                    // -k=value
+                    push(key, value);
+                } else if short.chars().count() == 1 {
+                    // -k value, or a boolean -k
+                    let value = args.next_if(|next| is_value(next)).unwrap_or("true");
+                    push(short, value);
+                } else if short.chars().all(char::is_alphabetic) {
+                    // -abc sets a, b and c
+                    for flag in short.chars() {
+                        push(flag.encode_utf8(&mut [0; 4]), "true");
                     }
                 } else {
-                    i += 1;
+                    // -p8080
+                    let split = short.chars().next().map_or(0, char::len_utf8);
+                    push(&short[..split], &short[split..]);
                 }
             } else {
-                i += 1;
+                self.positional.push(arg.to_string());
+            }
+        }
+
+        // The last occurrence of a flag wins, and repeated flags also become
+        // a list under indexed keys
+        let mut repeated: HashMap<String, Vec<String>> = HashMap::new();
+        for (key, value) in occurrences {
+            repeated.entry(key.clone()).or_default().push(value.clone());
+            self.values.insert(key, value);
+        }
+        for (key, values) in repeated.into_iter().filter(|(_, v)| v.len() > 1) {
+            for (index, value) in values.into_iter().enumerate() {
+                self.values.insert(format!("{}.{}", key, index), value);
             }
         }
     }
 }
 
+/// Returns whether an argument is a flag rather than a value.
+fn is_flag(arg: &str) -> bool {
+    !is_value(arg)
+}
+
+/// Returns whether an argument can be the value of the flag before it.
+///
+/// Negative numbers such as `-5` and a lone `-` (often standard input) are
+/// values even though they start with a dash.
+fn is_value(arg: &str) -> bool {
+    if !arg.starts_with('-') || arg == "-" {
+        return true;
+    }
+    arg[1..].starts_with(|c: char| c.is_ascii_digit() || c == '.') && arg.parse::<f64>().is_ok()
+}
+
 impl Default for CommandLineAdapter {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// The value and source shown as a key's default in `--help`.
+#[derive(Debug, Clone)]
+struct EffectiveValue {
+    /// The value, or `<redacted>` for secrets
+    value: String,
+    /// Name of the source supplying the value
+    source: String,
+}
+
+/// Command-line flags derived from typed configuration keys.
+///
+/// Each key becomes a long flag named after its path, such as
+/// `--database.port <PORT>`, with the key's description as help text and its
+/// default shown as `[default: ...]`. Flags for `bool` keys may be given
+/// without a value, as `--app.debug`, or as `--app.debug=false`.
+///
+/// Defaults are only shown, not passed to `clap`, so flags that were not given
+/// never override other sources. With
+/// [`with_effective_values`](Self::with_effective_values), the help shows the
+/// value the application would currently use and which source supplies it.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::{CliSchema, CommandLineAdapter};
+/// use hexcfg::domain::TypedKey;
+/// use hexcfg::prelude::*;
+///
+/// const DB_HOST: TypedKey<String> = TypedKey::new("database.host").describe("Database host name");
+/// const DB_PORT: TypedKey<u16> = TypedKey::new("database.port")
+///     .default(5432)
+///     .describe("Database port");
+///
+/// # fn main() -> Result<()> {
+/// // Read the other sources first, so `--help` can show where values come from
+/// let mut service = DefaultConfigService::builder().with_env_vars().build()?;
+/// let schema = CliSchema::new([DB_HOST.info(), DB_PORT.info()]).with_effective_values(&service);
+///
+/// let command = schema.augment(clap::Command::new("myapp").version("1.0"));
+/// let matches = command.get_matches_from(["myapp", "--database.port", "6543"]);
+///
+/// service.add_source(Box::new(CommandLineAdapter::from_matches(&matches, &schema)));
+/// assert_eq!(service.get_str("database.port")?.as_str(), "6543");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CliSchema {
+    /// Keys that become flags, in the order they are listed in `--help`
+    keys: Vec<KeyInfo>,
+    /// Current values of keys supplied by a source, keyed by key path
+    effective: HashMap<&'static str, EffectiveValue>,
+}
+
+impl CliSchema {
+    /// Creates a schema with a flag for each key.
+    pub fn new(keys: impl IntoIterator<Item = KeyInfo>) -> Self {
+        Self {
+            keys: keys.into_iter().collect(),
+            effective: HashMap::new(),
+        }
+    }
+
+    /// Adds a flag for another key.
+    pub fn with_key(mut self, key: KeyInfo) -> Self {
+        self.keys.push(key);
+        self
+    }
+
+    /// Shows the value `service` currently supplies for each key, and the
+    /// source it comes from, in place of the key's declared default.
+    ///
+    /// Secret values are shown as `<redacted>`.
+    pub fn with_effective_values(mut self, service: &DefaultConfigService) -> Self {
+        use crate::domain::ConfigurationService;
+
+        for info in &self.keys {
+            let Some(source) = service.source_of(info.key) else {
+                continue;
+            };
+            let Ok(value) = service.get(&ConfigKey::from(info.key)) else {
+                continue;
+            };
+            let value = if value.is_secret() {
+                "<redacted>".to_string()
+            } else {
+                value.as_str().to_string()
+            };
+            self.effective.insert(
+                info.key,
+                EffectiveValue {
+                    value,
+                    source: source.to_string(),
+                },
+            );
+        }
+        self
+    }
+
+    /// Returns the keys of the schema.
+    pub fn keys(&self) -> &[KeyInfo] {
+        &self.keys
+    }
+
+    /// Creates a command with a flag for each key.
+    pub fn command(&self, name: impl Into<clap::builder::Str>) -> Command {
+        self.augment(Command::new(name))
+    }
+
+    /// Adds a flag for each key to the application's own command.
+    pub fn augment(&self, command: Command) -> Command {
+        self.keys
+            .iter()
+            .fold(command, |command, info| command.arg(self.arg(info)))
+    }
+
+    /// Creates the flag for a key.
+    fn arg(&self, info: &KeyInfo) -> Arg {
+        // The last path segment names the value, as in `--database.port <PORT>`
+        let value_name = info
+            .key
+            .rsplit('.')
+            .next()
+            .unwrap_or(info.key)
+            .replace('-', "_")
+            .to_uppercase();
+
+        let arg = Arg::new(info.key)
+            .long(info.key)
+            .value_name(value_name)
+            .action(ArgAction::Set)
+            .help(self.help(info));
+
+        if info.value_type == "bool" {
+            arg.num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("true")
+        } else {
+            arg
+        }
+    }
+
+    /// Returns the help text of a key, ending with its effective default.
+    fn help(&self, info: &KeyInfo) -> String {
+        let default = match self.effective.get(info.key) {
+            Some(effective) => Some(format!("{} (from {})", effective.value, effective.source)),
+            None => info.default.clone(),
+        };
+
+        let mut help = info.description.unwrap_or_default().to_string();
+        if let Some(default) = default {
+            if !help.is_empty() {
+                help.push(' ');
+            }
+            help.push_str(&format!("[default: {}]", default));
+        }
+        help
+    }
+}
+
 impl ConfigSource for CommandLineAdapter {
     fn name(&self) -> &str {
         "cli"
     }
 
     fn priority(&self) -> u8 {
-        3
+        self.priority
     }
 
     fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
@@ -291,23 +569,24 @@ mod tests {
 
     #[test]
     fn test_cli_adapter_missing_value() {
-        let args = vec!["--host"];
+        // A flag without a value is a boolean flag
+        let args = vec!["--verbose"];
         let adapter = CommandLineAdapter::from_args(args);
 
-        let key = ConfigKey::from("host");
+        let key = ConfigKey::from("verbose");
         let value = adapter.get(&key).unwrap();
-        assert!(value.is_none());
+        assert_eq!(value.unwrap().as_str(), "true");
     }
 
     #[test]
     fn test_cli_adapter_flag_as_value() {
-        // --host followed by another flag should not treat the flag as a value
-        let args = vec!["--host", "--port", "8080"];
+        // --verbose followed by another flag should not treat the flag as a value
+        let args = vec!["--verbose", "--port", "8080"];
         let adapter = CommandLineAdapter::from_args(args);
 
-        let key = ConfigKey::from("host");
+        let key = ConfigKey::from("verbose");
         let value = adapter.get(&key).unwrap();
-        assert!(value.is_none());
+        assert_eq!(value.unwrap().as_str(), "true");
 
         let key = ConfigKey::from("port");
         let value = adapter.get(&key).unwrap();
@@ -375,6 +654,171 @@ mod tests {
         assert!(keys.contains(&ConfigKey::from("key")));
     }
 
+    #[test]
+    fn test_cli_adapter_negated_flag() {
+        let adapter = CommandLineAdapter::from_args(vec!["--no-color", "--no-reply=x"]);
+        assert_eq!(adapter.get_str("color").unwrap().unwrap().as_str(), "false");
+        // With a value, the key keeps its prefix
+        assert_eq!(adapter.get_str("no-reply").unwrap().unwrap().as_str(), "x");
+    }
+
+    #[test]
+    fn test_cli_adapter_repeated_flags() {
+        let args = vec!["--tag", "a", "--tag=b", "--tag", "c", "--name", "app"];
+        let adapter = CommandLineAdapter::from_args(args);
+
+        assert_eq!(adapter.get_str("tag").unwrap().unwrap().as_str(), "c");
+        assert_eq!(adapter.get_str("tag.0").unwrap().unwrap().as_str(), "a");
+        assert_eq!(adapter.get_str("tag.1").unwrap().unwrap().as_str(), "b");
+        assert_eq!(adapter.get_str("tag.2").unwrap().unwrap().as_str(), "c");
+        // A flag given once is not a list
+        assert!(adapter.get_str("name.0").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cli_adapter_negative_number_values() {
+        let args = vec!["--offset", "-5", "-t", "-0.25", "--level", "-x"];
+        let adapter = CommandLineAdapter::from_args(args);
+
+        assert_eq!(adapter.get_str("offset").unwrap().unwrap().as_str(), "-5");
+        assert_eq!(adapter.get_str("t").unwrap().unwrap().as_str(), "-0.25");
+        assert_eq!(adapter.get_str("level").unwrap().unwrap().as_str(), "true");
+        assert_eq!(adapter.get_str("x").unwrap().unwrap().as_str(), "true");
+    }
+
+    #[test]
+    fn test_cli_adapter_end_of_options() {
+        let args = vec!["in.txt", "--key", "value", "--", "--not-a-flag", "-v"];
+        let adapter = CommandLineAdapter::from_args(args);
+
+        assert_eq!(adapter.all_keys().unwrap().len(), 1);
+        assert_eq!(adapter.positional(), ["in.txt", "--not-a-flag", "-v"]);
+    }
+
+    #[test]
+    fn test_cli_adapter_combined_short_flags() {
+        let adapter = CommandLineAdapter::from_args(vec!["-xvf", "-p8080", "-k=v"]);
+
+        for flag in ["x", "v", "f"] {
+            assert_eq!(adapter.get_str(flag).unwrap().unwrap().as_str(), "true");
+        }
+        assert_eq!(adapter.get_str("p").unwrap().unwrap().as_str(), "8080");
+        assert_eq!(adapter.get_str("k").unwrap().unwrap().as_str(), "v");
+    }
+
+    #[test]
+    fn test_cli_adapter_properties() {
+        let args = vec!["-Ddatabase.host=localhost", "-Dfeature.beta", "-Durl=a=b"];
+        let adapter = CommandLineAdapter::from_args(args);
+
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "localhost"
+        );
+        assert_eq!(
+            adapter.get_str("feature.beta").unwrap().unwrap().as_str(),
+            "true"
+        );
+        assert_eq!(adapter.get_str("url").unwrap().unwrap().as_str(), "a=b");
+        assert!(adapter.get_str("D").unwrap().is_none());
+    }
+
+    fn schema() -> CliSchema {
+        CliSchema::new([
+            KeyInfo {
+                key: "database.host",
+                value_type: "alloc::string::String",
+                default: None,
+                description: Some("Database host name"),
+            },
+            KeyInfo {
+                key: "database.port",
+                value_type: "u16",
+                default: Some("5432".to_string()),
+                description: Some("Database port"),
+            },
+            KeyInfo {
+                key: "app.debug",
+                value_type: "bool",
+                default: Some("false".to_string()),
+                description: None,
+            },
+        ])
+    }
+
+    #[test]
+    fn test_cli_schema_from_matches() {
+        let schema = schema();
+        let matches = schema
+            .command("myapp")
+            .try_get_matches_from(["myapp", "--database.host", "db.internal", "--app.debug"])
+            .unwrap();
+        let adapter = CommandLineAdapter::from_matches(&matches, &schema);
+
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "db.internal"
+        );
+        assert_eq!(
+            adapter.get_str("app.debug").unwrap().unwrap().as_str(),
+            "true"
+        );
+        // Flags not given do not provide their default
+        assert!(adapter.get_str("database.port").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cli_schema_bool_with_value() {
+        let schema = schema();
+        let matches = schema
+            .command("myapp")
+            .try_get_matches_from(["myapp", "--app.debug=false"])
+            .unwrap();
+        let adapter = CommandLineAdapter::from_matches(&matches, &schema);
+        assert_eq!(
+            adapter.get_str("app.debug").unwrap().unwrap().as_str(),
+            "false"
+        );
+    }
+
+    #[test]
+    fn test_cli_schema_rejects_unknown_flags() {
+        let result = schema().command("myapp").try_get_matches_from([
+            "myapp",
+            "--database.hots",
+            "localhost",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_schema_help() {
+        let help = schema().command("myapp").render_help().to_string();
+        assert!(help.contains("--database.port <PORT>"));
+        assert!(help.contains("Database port [default: 5432]"));
+        assert!(help.contains("Database host name"));
+    }
+
+    #[test]
+    fn test_cli_schema_help_shows_supplying_source() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(crate::adapters::InMemoryAdapter::with_values(
+            HashMap::from([
+                ("database.port".to_string(), "6543".to_string()),
+                ("database.host".to_string(), "db.internal".to_string()),
+            ]),
+        )));
+
+        let help = schema()
+            .with_effective_values(&service)
+            .command("myapp")
+            .render_help()
+            .to_string();
+        assert!(help.contains("Database port [default: 6543 (from memory)]"));
+        assert!(help.contains("Database host name [default: db.internal (from memory)]"));
+        assert!(help.contains("[default: false]"));
+    }
+
     #[test]
     fn test_cli_adapter_override_value() {
         // If the same key appears multiple times, the last value should win
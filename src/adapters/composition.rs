@@ -0,0 +1,517 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Source composition combinators.
+//!
+//! This module provides source wrappers that change which keys another source
+//! exposes: [`Mounted`] moves keys under or out of a prefix, [`Filtered`]
+//! keeps or hides keys matching glob patterns, and [`Mapped`] renames keys
+//! with a function. The wrappers keep the name, priority and reload behavior
+//! of the wrapped source and can be nested in any order.
+
+use crate::domain::{ConfigKey, ConfigValue, KeyPattern, Result};
+use crate::ports::{ConfigSource, SourceHealth};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Removes `prefix` and the following dot from `key`, matching segment by
+/// segment. An empty prefix leaves the key unchanged.
+fn strip_prefix(prefix: &ConfigKey, key: &ConfigKey) -> Option<ConfigKey> {
+    if prefix.as_str().is_empty() {
+        return Some(key.clone());
+    }
+    if !prefix.is_prefix_of(key) {
+        return None;
+    }
+    let rest = key
+        .as_str()
+        .strip_prefix(prefix.as_str())?
+        .strip_prefix('.')?;
+    Some(ConfigKey::from(rest))
+}
+
+/// Prepends `prefix` and a dot to `key`. An empty prefix leaves the key
+/// unchanged.
+fn add_prefix(prefix: &ConfigKey, key: &ConfigKey) -> ConfigKey {
+    if prefix.as_str().is_empty() {
+        key.clone()
+    } else {
+        ConfigKey::from(format!("{}.{}", prefix, key))
+    }
+}
+
+/// Normalizes a prefix given as `shared` or `shared.` to `shared`.
+fn prefix_key(prefix: &str) -> ConfigKey {
+    ConfigKey::from(prefix.trim_end_matches('.'))
+}
+
+/// Source wrapper that moves the keys of another source under or out of a
+/// prefix.
+///
+/// [`new`](Self::new) mounts every key under a prefix, so that `db.host` in
+/// the wrapped source is read as `shared.db.host`. [`strip`](Self::strip) does
+/// the reverse: only keys under the prefix are exposed, without it. Prefixes
+/// are matched segment by segment, so stripping `app` hides `apps.name`.
+///
+/// Mounted sources are read-only, since writes would land on the wrong keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::{InMemoryAdapter, Mounted};
+/// use hexcfg::ports::ConfigSource;
+/// use std::collections::HashMap;
+///
+/// let mut values = HashMap::new();
+/// values.insert("db.host".to_string(), "db.internal".to_string());
+///
+/// let source = Mounted::new(Box::new(InMemoryAdapter::with_values(values)), "shared");
+/// assert!(source.get_str("shared.db.host").unwrap().is_some());
+/// assert!(source.get_str("db.host").unwrap().is_none());
+/// ```
+pub struct Mounted {
+    /// The wrapped source
+    inner: Box<dyn ConfigSource>,
+    /// Prefix added to the keys of the wrapped source
+    mount: ConfigKey,
+    /// Prefix removed from the keys of the wrapped source
+    strip: ConfigKey,
+}
+
+impl Mounted {
+    /// Exposes every key of `inner` under `prefix`.
+    pub fn new(inner: Box<dyn ConfigSource>, prefix: &str) -> Self {
+        Self {
+            inner,
+            mount: prefix_key(prefix),
+            strip: ConfigKey::from(""),
+        }
+    }
+
+    /// Exposes the keys of `inner` under `prefix`, with the prefix removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::{InMemoryAdapter, Mounted};
+    /// use hexcfg::ports::ConfigSource;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut values = HashMap::new();
+    /// values.insert("myapp.port".to_string(), "8080".to_string());
+    /// values.insert("other.port".to_string(), "9090".to_string());
+    ///
+    /// let source = Mounted::strip(Box::new(InMemoryAdapter::with_values(values)), "myapp");
+    /// assert_eq!(source.get_str("port").unwrap().unwrap().as_str(), "8080");
+    /// assert_eq!(source.all_keys().unwrap().len(), 1);
+    /// ```
+    pub fn strip(inner: Box<dyn ConfigSource>, prefix: &str) -> Self {
+        Self {
+            inner,
+            mount: ConfigKey::from(""),
+            strip: prefix_key(prefix),
+        }
+    }
+
+    /// Translates a key of this source to the key of the wrapped source.
+    fn inner_key(&self, key: &ConfigKey) -> Option<ConfigKey> {
+        let rest = strip_prefix(&self.mount, key)?;
+        Some(add_prefix(&self.strip, &rest))
+    }
+}
+
+impl fmt::Debug for Mounted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mounted")
+            .field("inner", &self.inner.name())
+            .field("mount", &self.mount)
+            .field("strip", &self.strip)
+            .finish()
+    }
+}
+
+impl ConfigSource for Mounted {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        match self.inner_key(key) {
+            Some(inner_key) => self.inner.get(&inner_key),
+            None => Ok(None),
+        }
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        Ok(self
+            .inner
+            .all_keys()?
+            .iter()
+            .filter_map(|key| strip_prefix(&self.strip, key))
+            .map(|key| add_prefix(&self.mount, &key))
+            .collect())
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.inner.reload()
+    }
+
+    fn health(&self) -> SourceHealth {
+        self.inner.health()
+    }
+}
+
+/// Source wrapper that keeps or hides keys of another source by glob pattern.
+///
+/// [`allow`](Self::allow) exposes only keys matching at least one pattern and
+/// [`deny`](Self::deny) hides them. Patterns use the [`KeyPattern`] syntax, so
+/// a pattern without wildcards names a single key.
+///
+/// Filtered sources are read-only.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::{EnvVarAdapter, Filtered};
+/// use hexcfg::ports::ConfigSource;
+///
+/// // Only database settings and the log level come from the environment
+/// let source = Filtered::allow(
+///     Box::new(EnvVarAdapter::new().lowercase_keys(true)),
+///     ["database.**", "log.level"],
+/// );
+/// assert!(source.get_str("home").unwrap().is_none());
+/// ```
+pub struct Filtered {
+    /// The wrapped source
+    inner: Box<dyn ConfigSource>,
+    /// Patterns of the keys to keep or hide
+    patterns: Vec<KeyPattern>,
+    /// Whether matching keys are kept rather than hidden
+    allow: bool,
+}
+
+impl Filtered {
+    /// Exposes only the keys of `inner` that match one of `patterns`.
+    pub fn allow<P: Into<KeyPattern>>(
+        inner: Box<dyn ConfigSource>,
+        patterns: impl IntoIterator<Item = P>,
+    ) -> Self {
+        Self {
+            inner,
+            patterns: patterns.into_iter().map(Into::into).collect(),
+            allow: true,
+        }
+    }
+
+    /// Hides the keys of `inner` that match one of `patterns`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::{Filtered, InMemoryAdapter};
+    /// use hexcfg::ports::ConfigSource;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut values = HashMap::new();
+    /// values.insert("db.host".to_string(), "db.internal".to_string());
+    /// values.insert("db.password".to_string(), "hunter2".to_string());
+    ///
+    /// let source = Filtered::deny(Box::new(InMemoryAdapter::with_values(values)), ["**.password"]);
+    /// assert!(source.get_str("db.password").unwrap().is_none());
+    /// assert!(source.get_str("db.host").unwrap().is_some());
+    /// ```
+    pub fn deny<P: Into<KeyPattern>>(
+        inner: Box<dyn ConfigSource>,
+        patterns: impl IntoIterator<Item = P>,
+    ) -> Self {
+        Self {
+            inner,
+            patterns: patterns.into_iter().map(Into::into).collect(),
+            allow: false,
+        }
+    }
+
+    /// Returns `true` if `key` is exposed by this source.
+    fn includes(&self, key: &ConfigKey) -> bool {
+        self.patterns.iter().any(|p| p.matches(key)) == self.allow
+    }
+}
+
+impl fmt::Debug for Filtered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filtered")
+            .field("inner", &self.inner.name())
+            .field("patterns", &self.patterns)
+            .field("allow", &self.allow)
+            .finish()
+    }
+}
+
+impl ConfigSource for Filtered {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        if self.includes(key) {
+            self.inner.get(key)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        let mut keys = self.inner.all_keys()?;
+        keys.retain(|key| self.includes(key));
+        Ok(keys)
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.inner.reload()
+    }
+
+    fn health(&self) -> SourceHealth {
+        self.inner.health()
+    }
+}
+
+/// Function that renames a key of the wrapped source, or drops it with `None`.
+type KeyMapper = Box<dyn Fn(&ConfigKey) -> Option<ConfigKey> + Send + Sync>;
+
+/// Source wrapper that renames the keys of another source with a function.
+///
+/// The function is applied to every key the wrapped source lists, when the
+/// wrapper is created and whenever it is reloaded; keys the function maps to
+/// `None` are hidden. Values are always read from the wrapped source, so
+/// changes to known keys are visible immediately, but keys the wrapped source
+/// adds later appear only after a reload. If two keys map to the same name,
+/// the first one listed wins.
+///
+/// Mapped sources are read-only.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::{InMemoryAdapter, Mapped};
+/// use hexcfg::domain::ConfigKey;
+/// use hexcfg::ports::ConfigSource;
+/// use std::collections::HashMap;
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// let mut values = HashMap::new();
+/// values.insert("pg_host".to_string(), "db.internal".to_string());
+///
+/// let source = Mapped::new(Box::new(InMemoryAdapter::with_values(values)), |key| {
+///     let name = key.as_str().strip_prefix("pg_")?;
+///     Some(ConfigKey::from(format!("database.{}", name)))
+/// })?;
+/// assert!(source.get_str("database.host")?.is_some());
+/// # Ok(())
+/// # }
+/// ```
+pub struct Mapped {
+    /// The wrapped source
+    inner: Box<dyn ConfigSource>,
+    /// Renames keys of the wrapped source
+    mapper: KeyMapper,
+    /// Keys of the wrapped source by their new names
+    index: HashMap<String, ConfigKey>,
+}
+
+impl Mapped {
+    /// Renames the keys of `inner` with `mapper`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the keys of `inner` cannot be listed.
+    pub fn new(
+        inner: Box<dyn ConfigSource>,
+        mapper: impl Fn(&ConfigKey) -> Option<ConfigKey> + Send + Sync + 'static,
+    ) -> Result<Self> {
+        let mut mapped = Self {
+            inner,
+            mapper: Box::new(mapper),
+            index: HashMap::new(),
+        };
+        mapped.index = mapped.build_index()?;
+        Ok(mapped)
+    }
+
+    /// Maps every key of the wrapped source.
+    fn build_index(&self) -> Result<HashMap<String, ConfigKey>> {
+        let mut index = HashMap::new();
+        for key in self.inner.all_keys()? {
+            if let Some(name) = (self.mapper)(&key) {
+                if index.contains_key(name.as_str()) {
+                    tracing::debug!(
+                        "Key '{}' of source '{}' maps to '{}', which is already taken",
+                        key,
+                        self.inner.name(),
+                        name
+                    );
+                    continue;
+                }
+                index.insert(name.to_string(), key);
+            }
+        }
+        Ok(index)
+    }
+}
+
+impl fmt::Debug for Mapped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mapped")
+            .field("inner", &self.inner.name())
+            .field("keys", &self.index.len())
+            .finish()
+    }
+}
+
+impl ConfigSource for Mapped {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        match self.index.get(key.as_str()) {
+            Some(inner_key) => self.inner.get(inner_key),
+            None => Ok(None),
+        }
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        Ok(self
+            .index
+            .keys()
+            .map(|k| ConfigKey::from(k.as_str()))
+            .collect())
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.inner.reload()?;
+        self.index = self.build_index()?;
+        Ok(())
+    }
+
+    fn health(&self) -> SourceHealth {
+        self.inner.health()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemoryAdapter;
+
+    fn source(values: &[(&str, &str)]) -> Box<dyn ConfigSource> {
+        Box::new(
+            InMemoryAdapter::with_values(
+                values
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            )
+            .with_priority(7),
+        )
+    }
+
+    fn sorted_keys(source: &dyn ConfigSource) -> Vec<String> {
+        let mut keys: Vec<String> = source
+            .all_keys()
+            .unwrap()
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn test_mounted_adds_and_strips_prefixes() {
+        let mounted = Mounted::new(source(&[("db.host", "a")]), "shared.");
+        assert_eq!(mounted.name(), "memory");
+        assert_eq!(mounted.priority(), 7);
+        assert_eq!(
+            mounted.get_str("shared.db.host").unwrap().unwrap().as_str(),
+            "a"
+        );
+        assert!(mounted.get_str("db.host").unwrap().is_none());
+        assert!(mounted.get_str("shared").unwrap().is_none());
+        assert_eq!(sorted_keys(&mounted), ["shared.db.host"]);
+
+        let stripped = Mounted::strip(
+            source(&[("app.port", "1"), ("apps.port", "2"), ("app", "3")]),
+            "app",
+        );
+        assert_eq!(stripped.get_str("port").unwrap().unwrap().as_str(), "1");
+        assert_eq!(sorted_keys(&stripped), ["port"]);
+    }
+
+    #[test]
+    fn test_filtered_allow_and_deny() {
+        let values = [("db.host", "a"), ("db.password", "b"), ("log.level", "c")];
+
+        let allowed = Filtered::allow(source(&values), ["db.*", "log.level"]);
+        assert_eq!(
+            sorted_keys(&allowed),
+            ["db.host", "db.password", "log.level"]
+        );
+        let allowed = Filtered::allow(source(&values), ["db.host"]);
+        assert_eq!(sorted_keys(&allowed), ["db.host"]);
+        assert!(allowed.get_str("log.level").unwrap().is_none());
+
+        let denied = Filtered::deny(source(&values), ["**.password"]);
+        assert_eq!(sorted_keys(&denied), ["db.host", "log.level"]);
+        assert!(denied.get_str("db.password").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_mapped_renames_and_reloads() {
+        let mut mapped = Mapped::new(source(&[("DB_HOST", "a"), ("HOME", "b")]), |key| {
+            let rest = key.as_str().strip_prefix("DB_")?;
+            Some(ConfigKey::from(format!("database.{}", rest.to_lowercase())))
+        })
+        .unwrap();
+        assert_eq!(mapped.priority(), 7);
+        assert_eq!(
+            mapped.get_str("database.host").unwrap().unwrap().as_str(),
+            "a"
+        );
+        assert!(mapped.get_str("HOME").unwrap().is_none());
+        assert_eq!(sorted_keys(&mapped), ["database.host"]);
+
+        mapped.reload().unwrap();
+        assert_eq!(sorted_keys(&mapped), ["database.host"]);
+    }
+
+    #[test]
+    fn test_combinators_compose() {
+        // Strip the application prefix, hide secrets, then mount under `shared`
+        let stripped = Mounted::strip(
+            source(&[("myapp.db.host", "a"), ("myapp.db.password", "b")]),
+            "myapp",
+        );
+        let filtered = Filtered::deny(Box::new(stripped), ["**.password"]);
+        let mounted = Mounted::new(Box::new(filtered), "shared");
+
+        assert_eq!(sorted_keys(&mounted), ["shared.db.host"]);
+        assert_eq!(
+            mounted.get_str("shared.db.host").unwrap().unwrap().as_str(),
+            "a"
+        );
+        assert!(mounted.get_str("shared.db.password").unwrap().is_none());
+        assert_eq!(mounted.priority(), 7);
+    }
+}
@@ -0,0 +1,641 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Consul KV configuration source adapter.
+//!
+//! This module provides an adapter that reads configuration values from
+//! Consul's key/value store over its HTTP API, and the connection options and
+//! indexed cache it shares with `ConsulWatcher`.
+
+use crate::adapters::runtime::block_on;
+use crate::adapters::shared::Shared;
+use crate::adapters::{ConsulWatcher, KeySeparator};
+use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result};
+use crate::ports::{AsyncConfigSource, BoxFuture, ConfigSource};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+/// How long Consul holds a blocking query open when no wait time is set.
+const DEFAULT_WAIT: Duration = Duration::from_secs(300);
+
+/// Extra time allowed for a blocking query to arrive after the wait time.
+const DEFAULT_SLACK: Duration = Duration::from_secs(10);
+
+/// Connection options for Consul agents.
+///
+/// Covers the ACL token, datacenter selection and timeouts. The same options
+/// are used by [`ConsulAdapter`] and
+/// [`ConsulWatcher`](crate::adapters::ConsulWatcher).
+///
+/// Keys are mapped like etcd keys: the prefix is stripped and `/` becomes `.`,
+/// unless a different [`KeySeparator`] is set.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::{ConsulAdapter, ConsulConnectOptions};
+/// use std::time::Duration;
+///
+/// # #[tokio::main]
+/// # async fn main() -> hexcfg::domain::Result<()> {
+/// let options = ConsulConnectOptions::new()
+///     .with_token("b1gs33cr3t")
+///     .with_datacenter("eu-west")
+///     .with_request_timeout(Duration::from_secs(10));
+///
+/// let adapter =
+///     ConsulAdapter::with_options("http://consul.internal:8500", Some("myapp/"), options).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct ConsulConnectOptions {
+    /// ACL token sent with every request
+    token: Option<String>,
+    /// Datacenter to query instead of the agent's own
+    datacenter: Option<String>,
+    /// Timeout for each non-blocking request
+    request_timeout: Option<Duration>,
+    /// How long a blocking query may wait for a change
+    wait: Option<Duration>,
+    /// Key separator translation; `/` when not set
+    key_separator: Option<KeySeparator>,
+}
+
+impl fmt::Debug for ConsulConnectOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConsulConnectOptions")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("datacenter", &self.datacenter)
+            .field("request_timeout", &self.request_timeout)
+            .field("wait", &self.wait_time())
+            .field("key_separator", &self.key_separator())
+            .finish()
+    }
+}
+
+impl ConsulConnectOptions {
+    /// Creates options for an agent without ACLs, in its own datacenter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Authenticates with an ACL token (`X-Consul-Token`).
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Reads keys from another datacenter (`?dc=`).
+    pub fn with_datacenter(mut self, datacenter: impl Into<String>) -> Self {
+        self.datacenter = Some(datacenter.into());
+        self
+    }
+
+    /// Sets the timeout for each request other than blocking queries.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long the watcher's blocking queries wait for a change before
+    /// Consul answers anyway. Consul caps this at ten minutes.
+    pub fn with_wait_time(mut self, wait: Duration) -> Self {
+        self.wait = Some(wait);
+        self
+    }
+
+    /// Sets how Consul keys map to configuration keys.
+    pub fn with_key_separator(mut self, separator: KeySeparator) -> Self {
+        self.key_separator = Some(separator);
+        self
+    }
+
+    /// Returns the key separator translation, `/` unless overridden.
+    pub fn key_separator(&self) -> KeySeparator {
+        self.key_separator
+            .clone()
+            .unwrap_or_else(|| KeySeparator::translate("/"))
+    }
+
+    /// Returns the blocking query wait time, five minutes unless overridden.
+    pub fn wait_time(&self) -> Duration {
+        self.wait.unwrap_or(DEFAULT_WAIT)
+    }
+}
+
+/// Keys under a prefix together with the Consul index they reflect.
+///
+/// Shared between a [`ConsulAdapter`] and the watchers it creates, so blocking
+/// query results update the adapter's cache directly.
+#[derive(Debug, Default)]
+pub(crate) struct ConsulState {
+    /// Cached configuration values, keyed by configuration key
+    values: HashMap<String, String>,
+    /// `X-Consul-Index` of the response the values came from; 0 before loading
+    index: u64,
+}
+
+/// Handle to a [`ConsulState`] shared between an adapter and its watchers.
+pub(crate) type SharedConsulState = Shared<ConsulState>;
+
+impl ConsulState {
+    /// Returns the index this state is consistent with.
+    pub(crate) fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Replaces the values with a newer response, returning the keys whose
+    /// values changed.
+    ///
+    /// Every response holds the whole prefix, and Consul's index may go
+    /// backwards after a snapshot restore, so responses are never ignored.
+    pub(crate) fn replace(&mut self, snapshot: ConsulState) -> Vec<String> {
+        let mut changed: Vec<String> = snapshot
+            .values
+            .iter()
+            .filter(|(key, value)| self.values.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .collect();
+        changed.extend(
+            self.values
+                .keys()
+                .filter(|key| !snapshot.values.contains_key(*key))
+                .cloned(),
+        );
+
+        *self = snapshot;
+        changed
+    }
+}
+
+/// One entry of a `GET /v1/kv/<prefix>?recurse` response.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct KvEntry {
+    key: String,
+    /// Base64-encoded value; `null` for folder entries
+    value: Option<String>,
+}
+
+/// HTTP access to a Consul agent's KV endpoint.
+#[derive(Debug, Clone)]
+pub(crate) struct ConsulClient {
+    /// HTTP client
+    http: reqwest::Client,
+    /// Agent address, including the scheme and without a trailing slash
+    address: String,
+    /// Connection options
+    options: ConsulConnectOptions,
+}
+
+impl ConsulClient {
+    /// Creates a client for the agent at `address`.
+    ///
+    /// Addresses without a scheme use `http://`.
+    pub(crate) fn new(address: &str, options: ConsulConnectOptions) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .build()
+            .map_err(|e| ConfigError::SourceError {
+                source_name: "consul".to_string(),
+                message: format!("Failed to create HTTP client: {}", e),
+                source: Some(Box::new(e)),
+            })?;
+
+        let address = address.trim_end_matches('/');
+        let address = if address.contains("://") {
+            address.to_string()
+        } else {
+            format!("http://{}", address)
+        };
+
+        Ok(Self {
+            http,
+            address,
+            options,
+        })
+    }
+
+    /// Returns the connection options.
+    pub(crate) fn options(&self) -> &ConsulConnectOptions {
+        &self.options
+    }
+
+    /// Reads every key under `prefix`.
+    ///
+    /// With `wait_index`, this is a blocking query that returns once the index
+    /// moves past it or the wait time runs out.
+    pub(crate) async fn fetch(&self, prefix: &str, wait_index: Option<u64>) -> Result<ConsulState> {
+        let fetch_error = |message: String, e: Option<reqwest::Error>| ConfigError::SourceError {
+            source_name: "consul".to_string(),
+            message,
+            source: e.map(|e| Box::new(e) as _),
+        };
+
+        let mut request = self
+            .http
+            .get(format!("{}/v1/kv/{}", self.address, prefix))
+            .query(&[("recurse", "true")]);
+        if let Some(datacenter) = &self.options.datacenter {
+            request = request.query(&[("dc", datacenter)]);
+        }
+        if let Some(token) = &self.options.token {
+            request = request.header("X-Consul-Token", token);
+        }
+        match wait_index {
+            Some(index) => {
+                let wait = self.options.wait_time();
+                // Consul adds up to wait/16 of jitter before answering
+                let timeout =
+                    wait + wait / 16 + self.options.request_timeout.unwrap_or(DEFAULT_SLACK);
+                request = request
+                    .query(&[
+                        ("index", index.to_string()),
+                        ("wait", format!("{}ms", wait.as_millis())),
+                    ])
+                    .timeout(timeout);
+            }
+            None => {
+                if let Some(timeout) = self.options.request_timeout {
+                    request = request.timeout(timeout);
+                }
+            }
+        }
+
+        let response = request.send().await.map_err(|e| {
+            fetch_error(format!("Failed to fetch keys from Consul: {}", e), Some(e))
+        })?;
+
+        let index = response
+            .headers()
+            .get("X-Consul-Index")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0);
+
+        // A missing prefix is an empty configuration, not an error
+        let entries: Vec<KvEntry> = match response.status() {
+            StatusCode::NOT_FOUND => Vec::new(),
+            status if status.is_success() => response.json().await.map_err(|e| {
+                fetch_error(format!("Invalid response from Consul: {}", e), Some(e))
+            })?,
+            status => {
+                let body = response.text().await.unwrap_or_default();
+                return Err(fetch_error(
+                    format!("Consul returned {}: {}", status, body.trim()),
+                    None,
+                ));
+            }
+        };
+
+        let separator = self.options.key_separator();
+        let mut values = HashMap::new();
+        for entry in entries {
+            let Some(encoded) = entry.value else { continue };
+            let decoded = STANDARD
+                .decode(encoded)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok());
+            match decoded {
+                Some(value) => {
+                    values.insert(config_key(prefix, &separator, &entry.key), value);
+                }
+                None => tracing::warn!("Skipping non-UTF-8 Consul value: {}", entry.key),
+            }
+        }
+
+        Ok(ConsulState { values, index })
+    }
+}
+
+/// Converts a Consul key under `prefix` to a configuration key.
+fn config_key(prefix: &str, separator: &KeySeparator, key: &str) -> String {
+    separator.to_config_key(key.strip_prefix(prefix).unwrap_or(key))
+}
+
+/// Configuration source adapter for Consul's KV store.
+///
+/// This adapter reads every key under a prefix with a single recursive request
+/// and decodes the base64 values Consul returns. Folder entries are skipped.
+///
+/// # Consistency
+///
+/// The adapter records the `X-Consul-Index` of the response it loaded (see
+/// [`index`](Self::index)). A watcher created with [`watcher`](Self::watcher)
+/// issues blocking queries from that index, so changes made after loading are
+/// not missed.
+///
+/// # Priority
+///
+/// Consul has a default priority of 1, but this can be customized.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::ConsulAdapter;
+/// use hexcfg::ports::ConfigSource;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // `myapp/database/host` is read as `database.host`
+/// let adapter = ConsulAdapter::new("localhost:8500", Some("myapp/")).await?;
+/// let host = adapter.get_str("database.host")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConsulAdapter {
+    /// Consul HTTP client
+    client: ConsulClient,
+    /// Key prefix for namespacing
+    prefix: Option<String>,
+    /// Priority for this source
+    priority: u8,
+    /// Cached configuration values and their index
+    state: SharedConsulState,
+}
+
+impl ConsulAdapter {
+    /// Validates prefix so it stays within the KV path of the request
+    fn validate_prefix(prefix: &str) -> Result<()> {
+        if prefix.contains(['?', '#', '\0', '\n', '\r']) {
+            return Err(ConfigError::SourceError {
+                source_name: "consul".to_string(),
+                message: "Prefix contains invalid characters".to_string(),
+                source: None,
+            });
+        }
+        Ok(())
+    }
+
+    /// Creates a new Consul adapter for the agent at `address`.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Agent address (e.g., `"http://localhost:8500"`)
+    /// * `prefix` - Optional key prefix for namespacing (e.g., `"myapp/"`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::ConsulAdapter;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let adapter = ConsulAdapter::new("http://localhost:8500", Some("myapp/")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn new(address: &str, prefix: Option<&str>) -> Result<Self> {
+        Self::with_options(address, prefix, ConsulConnectOptions::new()).await
+    }
+
+    /// Creates a new Consul adapter with an ACL token, datacenter or timeouts.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Agent address
+    /// * `prefix` - Optional key prefix for namespacing
+    /// * `options` - Connection options, also used by watchers
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{ConsulAdapter, ConsulConnectOptions};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let options = ConsulConnectOptions::new().with_token("b1gs33cr3t");
+    /// let adapter =
+    ///     ConsulAdapter::with_options("http://localhost:8500", Some("myapp/"), options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_options(
+        address: &str,
+        prefix: Option<&str>,
+        options: ConsulConnectOptions,
+    ) -> Result<Self> {
+        if let Some(p) = prefix {
+            Self::validate_prefix(p)?;
+        }
+
+        let mut adapter = Self {
+            client: ConsulClient::new(address, options)?,
+            prefix: prefix.map(|s| s.to_string()),
+            priority: 1,
+            state: SharedConsulState::default(),
+        };
+
+        // Initial load of all keys
+        adapter.load_all_keys().await?;
+
+        Ok(adapter)
+    }
+
+    /// Creates a new Consul adapter with a custom priority.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Agent address
+    /// * `prefix` - Optional key prefix for namespacing
+    /// * `priority` - Priority for this source (higher values override lower values)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::ConsulAdapter;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let adapter = ConsulAdapter::with_priority("http://localhost:8500", Some("myapp/"), 2)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_priority(address: &str, prefix: Option<&str>, priority: u8) -> Result<Self> {
+        let mut adapter = Self::new(address, prefix).await?;
+        adapter.priority = priority;
+        Ok(adapter)
+    }
+
+    /// Returns the Consul index the cached values are consistent with.
+    pub fn index(&self) -> u64 {
+        self.state.read().index()
+    }
+
+    /// Creates a watcher that keeps this adapter's cache up to date.
+    ///
+    /// The watcher's first blocking query starts at the adapter's index, and
+    /// each result is applied to the cache before the callback runs for the
+    /// keys that changed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::ConsulAdapter;
+    /// use hexcfg::ports::ConfigWatcher;
+    /// use std::sync::Arc;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let adapter = ConsulAdapter::new("http://localhost:8500", Some("myapp/")).await?;
+    ///
+    /// let mut watcher = adapter.watcher();
+    /// watcher.watch(Arc::new(|key| {
+    ///     println!("Configuration changed: {}", key);
+    /// }))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watcher(&self) -> ConsulWatcher {
+        ConsulWatcher::with_state(
+            self.client.address.clone(),
+            self.client.options().clone(),
+            self.prefix.clone(),
+            self.state.clone(),
+        )
+    }
+
+    /// Loads all keys from Consul into the cache.
+    async fn load_all_keys(&mut self) -> Result<()> {
+        let snapshot = self
+            .client
+            .fetch(self.prefix.as_deref().unwrap_or(""), None)
+            .await?;
+        self.state.write().replace(snapshot);
+        Ok(())
+    }
+
+    /// Reloads all keys from Consul synchronously.
+    ///
+    /// Pooled connections belong to the runtime that opened them, so a fresh
+    /// client is used on the shared blocking runtime.
+    fn reload_sync(&mut self) -> Result<()> {
+        let client = ConsulClient::new(&self.client.address, self.client.options().clone())?;
+        let prefix = self.prefix.as_deref().unwrap_or("");
+
+        let snapshot = block_on(async move { client.fetch(prefix, None).await })?;
+        self.state.write().replace(snapshot);
+
+        Ok(())
+    }
+}
+
+impl ConfigSource for ConsulAdapter {
+    fn name(&self) -> &str {
+        "consul"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        Ok(self
+            .state
+            .read()
+            .values
+            .get(key.as_str())
+            .map(|v| ConfigValue::from(v.as_str())))
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        Ok(self
+            .state
+            .read()
+            .values
+            .keys()
+            .map(|k| ConfigKey::from(k.as_str()))
+            .collect())
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.reload_sync()
+    }
+}
+
+impl AsyncConfigSource for ConsulAdapter {
+    fn name(&self) -> &str {
+        "consul"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+        let result = ConfigSource::get(self, key);
+        Box::pin(async move { result })
+    }
+
+    fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> {
+        let result = ConfigSource::all_keys(self);
+        Box::pin(async move { result })
+    }
+
+    fn reload(&mut self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(self.load_all_keys())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_key_mapping() {
+        let slash = KeySeparator::translate("/");
+        assert_eq!(
+            config_key("myapp/", &slash, "myapp/database/host"),
+            "database.host"
+        );
+        assert_eq!(
+            config_key("myapp/", &KeySeparator::None, "myapp/database/host"),
+            "database/host"
+        );
+    }
+
+    #[test]
+    fn test_client_address_scheme() {
+        let client = ConsulClient::new("localhost:8500/", ConsulConnectOptions::new()).unwrap();
+        assert_eq!(client.address, "http://localhost:8500");
+
+        let client =
+            ConsulClient::new("https://consul.internal:8501", ConsulConnectOptions::new()).unwrap();
+        assert_eq!(client.address, "https://consul.internal:8501");
+    }
+
+    #[test]
+    fn test_options_debug_redacts_token() {
+        let options = ConsulConnectOptions::new().with_token("b1gs33cr3t");
+        let debug = format!("{:?}", options);
+        assert!(!debug.contains("b1gs33cr3t"));
+        assert!(debug.contains("<redacted>"));
+    }
+
+    #[test]
+    fn test_state_replace_reports_changes() {
+        let mut state = ConsulState::default();
+        let snapshot = |pairs: &[(&str, &str)], index| ConsulState {
+            values: pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            index,
+        };
+
+        let mut changed = state.replace(snapshot(&[("a", "1"), ("b", "2")], 5));
+        changed.sort();
+        assert_eq!(changed, vec!["a", "b"]);
+
+        let mut changed = state.replace(snapshot(&[("a", "1"), ("c", "3")], 7));
+        changed.sort();
+        assert_eq!(changed, vec!["b", "c"]);
+        assert_eq!(state.index(), 7);
+    }
+}
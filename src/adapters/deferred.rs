@@ -0,0 +1,462 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Sources with a startup failure policy.
+//!
+//! This module provides a source wrapper that creates its source through a
+//! factory, decides by policy what happens when that fails, and keeps the last
+//! values it loaded when reloading fails later.
+
+use crate::adapters::shared::Shared;
+use crate::domain::{ConfigKey, ConfigValue, Result};
+use crate::ports::{ConfigSource, SourceHealth, SourceStatus};
+use std::fmt;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+/// What happens when a configuration source fails to load.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::SourcePolicy;
+/// use std::time::Duration;
+///
+/// let policy = SourcePolicy::BestEffort {
+///     retry_interval: Duration::from_secs(30),
+/// };
+/// assert_ne!(policy, SourcePolicy::default());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SourcePolicy {
+    /// Loading errors abort building the service
+    #[default]
+    Required,
+    /// A source that fails to load is left out
+    Optional,
+    /// The service starts without the source's values and keeps trying to
+    /// load the source in the background
+    BestEffort {
+        /// How long to wait between attempts
+        retry_interval: Duration,
+    },
+}
+
+/// Creates the wrapped source of a [`DeferredSource`].
+type SourceFactory = Arc<dyn Fn() -> Result<Box<dyn ConfigSource>> + Send + Sync>;
+
+/// Loaded source and health shared with the retry thread.
+struct DeferredState {
+    /// The source, once it has loaded
+    source: Option<Box<dyn ConfigSource>>,
+    /// Health of the source
+    health: SourceHealth,
+}
+
+impl DeferredState {
+    /// Records a successful load or reload.
+    fn succeeded(&mut self) {
+        self.health = SourceHealth::loaded();
+    }
+
+    /// Records a failed load or reload, keeping the time of the first failure.
+    fn failed(&mut self, message: String) {
+        let since = match self.health.status {
+            SourceStatus::Loaded => SystemTime::now(),
+            SourceStatus::Stale { since } | SourceStatus::Unavailable { since } => since,
+        };
+        self.health.status = match self.source {
+            Some(_) => SourceStatus::Stale { since },
+            None => SourceStatus::Unavailable { since },
+        };
+        self.health.last_error = Some(message);
+    }
+}
+
+/// Shared state of a deferred source.
+type SharedDeferredState = Shared<DeferredState>;
+
+impl SharedDeferredState {
+    /// Creates a fresh source and swaps it in, or records why that failed.
+    ///
+    /// The source is created outside the lock, so reads keep being served by
+    /// the previous source meanwhile; a failed attempt leaves it in place.
+    fn attempt(&self, name: &str, factory: &SourceFactory) -> Result<()> {
+        let created = factory();
+
+        let mut state = self.write();
+        match created {
+            Ok(source) => {
+                if state.source.replace(source).is_none() {
+                    tracing::info!("Configuration source '{}' loaded", name);
+                }
+                state.succeeded();
+                Ok(())
+            }
+            Err(e) => {
+                state.failed(e.to_string());
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Source wrapper that applies a [`SourcePolicy`] to a source that may fail
+/// to load.
+///
+/// The wrapped source is created by a factory. A required source that fails
+/// to load is an error, while an optional or best-effort one starts out
+/// unavailable and serves no values. A best-effort source calls the factory
+/// again at its retry interval until it succeeds, after which its values
+/// become visible.
+///
+/// Reloading calls the factory again and swaps in the new source, so reads
+/// are served by the previous source until it is ready. When that fails, the
+/// previous source keeps serving the values it last loaded and reports itself
+/// as stale. A best-effort source then retries the reload in the background.
+/// [`health`](ConfigSource::health) reports the current state for readiness
+/// probes.
+///
+/// Since the source has to be placed among the others before it loads, its
+/// name and priority are given up front. Values cannot be written through a
+/// deferred source.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::{DeferredSource, SourcePolicy, YamlFileAdapter};
+/// use hexcfg::ports::{ConfigSource, SourceStatus};
+/// use std::time::Duration;
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// let policy = SourcePolicy::BestEffort {
+///     retry_interval: Duration::from_secs(30),
+/// };
+/// let source = DeferredSource::new("yaml-file", 1, policy, || {
+///     Ok(Box::new(YamlFileAdapter::from_file("/etc/myapp/missing.yaml")?))
+/// })?;
+///
+/// assert!(matches!(source.health().status, SourceStatus::Unavailable { .. }));
+/// assert!(source.get_str("app.name")?.is_none());
+/// # Ok(())
+/// # }
+/// ```
+pub struct DeferredSource {
+    /// Name reported for the source
+    name: String,
+    /// Priority reported for the source
+    priority: u8,
+    /// Policy applied when loading fails
+    policy: SourcePolicy,
+    /// Creates the wrapped source
+    factory: SourceFactory,
+    /// Loaded source and health
+    state: SharedDeferredState,
+    /// Stop signal sender for the retry thread
+    stop_tx: Option<Sender<()>>,
+    /// Retry thread handle
+    retry_thread: Option<JoinHandle<()>>,
+}
+
+impl DeferredSource {
+    /// Creates the source with `factory` and applies `policy` if that fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns the factory's error if the policy is
+    /// [`Required`](SourcePolicy::Required).
+    pub fn new(
+        name: impl Into<String>,
+        priority: u8,
+        policy: SourcePolicy,
+        factory: impl Fn() -> Result<Box<dyn ConfigSource>> + Send + Sync + 'static,
+    ) -> Result<Self> {
+        let factory: SourceFactory = Arc::new(factory);
+        let first = factory();
+        Self::with_first_attempt(name, priority, policy, factory, first)
+    }
+
+    /// Creates the source from the outcome of a first attempt made by the
+    /// caller, such as an awaited async constructor.
+    pub(crate) fn with_first_attempt(
+        name: impl Into<String>,
+        priority: u8,
+        policy: SourcePolicy,
+        factory: SourceFactory,
+        first: Result<Box<dyn ConfigSource>>,
+    ) -> Result<Self> {
+        let name = name.into();
+        let mut state = DeferredState {
+            source: None,
+            health: SourceHealth::loaded(),
+        };
+        let failed = match first {
+            Ok(source) => {
+                state.source = Some(source);
+                false
+            }
+            Err(e) if policy == SourcePolicy::Required => return Err(e),
+            Err(e) => {
+                tracing::warn!("Configuration source '{}' failed to load: {}", name, e);
+                state.failed(e.to_string());
+                true
+            }
+        };
+
+        let mut source = Self {
+            name,
+            priority,
+            policy,
+            factory,
+            state: SharedDeferredState::new(state),
+            stop_tx: None,
+            retry_thread: None,
+        };
+        if failed {
+            source.retry_in_background();
+        }
+        Ok(source)
+    }
+
+    /// Returns `true` once the wrapped source has loaded.
+    pub fn is_loaded(&self) -> bool {
+        self.state.read().source.is_some()
+    }
+
+    /// Starts retrying in the background, for best-effort sources that are not
+    /// already retrying.
+    fn retry_in_background(&mut self) {
+        let SourcePolicy::BestEffort { retry_interval } = self.policy else {
+            return;
+        };
+        if let Some(handle) = &self.retry_thread {
+            if !handle.is_finished() {
+                return;
+            }
+        }
+        self.stop();
+
+        let (stop_tx, stop_rx) = channel::<()>();
+        self.stop_tx = Some(stop_tx);
+
+        let name = self.name.clone();
+        let factory = Arc::clone(&self.factory);
+        let state = self.state.clone();
+
+        self.retry_thread = Some(thread::spawn(move || {
+            // Retry at every interval until the source loads or we are stopped
+            while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(retry_interval) {
+                match state.attempt(&name, &factory) {
+                    Ok(()) => break,
+                    Err(e) => tracing::debug!("Retrying configuration source '{}': {}", name, e),
+                }
+            }
+        }));
+    }
+
+    /// Stops the retry thread, if any.
+    fn stop(&mut self) {
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+        if let Some(handle) = self.retry_thread.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl fmt::Debug for DeferredSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeferredSource")
+            .field("name", &self.name)
+            .field("priority", &self.priority)
+            .field("policy", &self.policy)
+            .field("health", &self.state.read().health)
+            .finish()
+    }
+}
+
+impl ConfigSource for DeferredSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        match &self.state.read().source {
+            Some(source) => source.get(key),
+            None => Ok(None),
+        }
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        match &self.state.read().source {
+            Some(source) => source.all_keys(),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        let result = self.state.attempt(&self.name, &self.factory);
+        if result.is_err() {
+            self.retry_in_background();
+        }
+        result
+    }
+
+    fn health(&self) -> SourceHealth {
+        let state = self.state.read();
+        match &state.source {
+            // Report the wrapped source's own state once it has loaded
+            Some(source) if state.health.status == SourceStatus::Loaded => source.health(),
+            _ => state.health.clone(),
+        }
+    }
+}
+
+impl Drop for DeferredSource {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemoryAdapter;
+    use crate::domain::ConfigError;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// A factory that fails while `available` is false and counts its calls.
+    fn factory(
+        available: Arc<AtomicBool>,
+        calls: Arc<AtomicUsize>,
+    ) -> impl Fn() -> Result<Box<dyn ConfigSource>> + Send + Sync + 'static {
+        move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            if !available.load(Ordering::SeqCst) {
+                return Err(ConfigError::SourceError {
+                    source_name: "test".to_string(),
+                    message: "Connection refused".to_string(),
+                    source: None,
+                });
+            }
+            Ok(Box::new(InMemoryAdapter::with_values(HashMap::from([(
+                "app.name".to_string(),
+                "demo".to_string(),
+            )]))))
+        }
+    }
+
+    #[test]
+    fn test_required_and_optional_policies() {
+        let available = Arc::new(AtomicBool::new(false));
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let result = DeferredSource::new(
+            "test",
+            1,
+            SourcePolicy::Required,
+            factory(available.clone(), calls.clone()),
+        );
+        assert!(result.is_err());
+
+        let mut source = DeferredSource::new(
+            "test",
+            1,
+            SourcePolicy::Optional,
+            factory(available.clone(), calls.clone()),
+        )
+        .unwrap();
+        assert!(!source.is_loaded());
+        assert!(source.all_keys().unwrap().is_empty());
+        let health = source.health();
+        assert!(matches!(health.status, SourceStatus::Unavailable { .. }));
+        assert!(health.last_error.unwrap().contains("Connection refused"));
+
+        // Optional sources are not retried in the background, only on reload
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        available.store(true, Ordering::SeqCst);
+        source.reload().unwrap();
+        assert!(source.is_loaded());
+        assert_eq!(source.health(), SourceHealth::loaded());
+        assert_eq!(
+            source.get_str("app.name").unwrap().unwrap().as_str(),
+            "demo"
+        );
+    }
+
+    #[test]
+    fn test_best_effort_retries_in_background() {
+        let available = Arc::new(AtomicBool::new(false));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let policy = SourcePolicy::BestEffort {
+            retry_interval: Duration::from_millis(20),
+        };
+
+        let source =
+            DeferredSource::new("test", 5, policy, factory(available.clone(), calls.clone()))
+                .unwrap();
+        assert_eq!(source.name(), "test");
+        assert_eq!(source.priority(), 5);
+        assert!(source.get_str("app.name").unwrap().is_none());
+
+        let since = match source.health().status {
+            SourceStatus::Unavailable { since } => since,
+            status => panic!("unexpected status {:?}", status),
+        };
+        thread::sleep(Duration::from_millis(100));
+        assert!(calls.load(Ordering::SeqCst) > 2);
+        // Failed retries keep the time of the first failure
+        assert_eq!(source.health().status, SourceStatus::Unavailable { since });
+
+        available.store(true, Ordering::SeqCst);
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while !source.is_loaded() && std::time::Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(
+            source.get_str("app.name").unwrap().unwrap().as_str(),
+            "demo"
+        );
+        assert_eq!(source.health(), SourceHealth::loaded());
+    }
+
+    #[test]
+    fn test_failed_reload_keeps_last_known_good() {
+        let available = Arc::new(AtomicBool::new(true));
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let mut source = DeferredSource::new(
+            "test",
+            1,
+            SourcePolicy::Required,
+            factory(available.clone(), calls.clone()),
+        )
+        .unwrap();
+
+        // Reloading creates a fresh source
+        source.reload().unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        available.store(false, Ordering::SeqCst);
+        assert!(source.reload().is_err());
+        let health = source.health();
+        assert!(matches!(health.status, SourceStatus::Stale { .. }));
+        assert_eq!(
+            health.last_error.as_deref(),
+            Some("Configuration source 'test' error: Connection refused")
+        );
+        assert_eq!(
+            source.get_str("app.name").unwrap().unwrap().as_str(),
+            "demo"
+        );
+    }
+}
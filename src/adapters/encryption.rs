@@ -0,0 +1,545 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Decryption of encrypted configuration values.
+//!
+//! This module provides a source wrapper that decrypts `ENC[<scheme>,<payload>]`
+//! values when its source is loaded, along with decryptors for a local
+//! AES-256-GCM key file and for age X25519 identities.
+
+use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result};
+use crate::ports::{ConfigSource, SecretDecryptor, SourceHealth};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Length of an AES-GCM nonce in bytes
+const NONCE_LEN: usize = 12;
+
+/// Length of an AES-GCM authentication tag in bytes
+const TAG_LEN: usize = 16;
+
+/// Splits an `ENC[<scheme>,<payload>]` value into its scheme and payload.
+fn parse_envelope(value: &str) -> Option<(&str, &str)> {
+    let inner = value.trim().strip_prefix("ENC[")?.strip_suffix(']')?;
+    let (scheme, payload) = inner.split_once(',')?;
+    (!scheme.is_empty()).then_some((scheme, payload))
+}
+
+/// Creates a decryption error for `key` with a message that holds no values.
+fn decryption_error(key: &ConfigKey, message: impl Into<String>) -> ConfigError {
+    ConfigError::DecryptionError {
+        key: key.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Decodes a base64 payload.
+fn decode_payload(key: &ConfigKey, payload: &str) -> Result<Vec<u8>> {
+    STANDARD
+        .decode(payload.trim())
+        .map_err(|_| decryption_error(key, "payload is not valid base64"))
+}
+
+/// Reads a key or identity file, naming only the path in errors.
+fn read_key_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| ConfigError::SourceError {
+        source_name: "encryption".to_string(),
+        message: format!("Failed to read key file: {}", path.display()),
+        source: Some(Box::new(e)),
+    })
+}
+
+/// Configuration source wrapper that decrypts encrypted values at load time.
+///
+/// Every value of the wrapped source of the form `ENC[<scheme>,<payload>]` is
+/// decrypted by the decryptor handling `<scheme>` when the wrapper is created
+/// and on every reload, so a missing key or corrupted value is reported up
+/// front. YAML values tagged `!encrypted <scheme>,<payload>` are read in the
+/// same form. Decrypted values are marked as secret, so their `Debug` output
+/// is redacted, and errors name the key but never include the encrypted or
+/// decrypted value.
+///
+/// The wrapper is read-only: write changes to the inner source before wrapping
+/// it.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::{AesGcmDecryptor, DecryptingSource, YamlFileAdapter};
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// let decryptor = AesGcmDecryptor::from_key_file("/etc/myapp/config.key")?;
+/// let yaml = YamlFileAdapter::from_file("/etc/myapp/config.yaml")?;
+///
+/// let source = DecryptingSource::new(Box::new(yaml), decryptor)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DecryptingSource {
+    /// The source holding encrypted values
+    inner: Box<dyn ConfigSource>,
+    /// Decryptors, consulted in order for a matching scheme
+    decryptors: Vec<Box<dyn SecretDecryptor>>,
+    /// Decrypted values of the inner source, keyed by configuration key
+    decrypted: HashMap<String, ConfigValue>,
+}
+
+impl DecryptingSource {
+    /// Wraps `inner`, decrypting its values with `decryptor`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError::DecryptionError`] if any value cannot be
+    /// decrypted.
+    pub fn new(
+        inner: Box<dyn ConfigSource>,
+        decryptor: impl SecretDecryptor + 'static,
+    ) -> Result<Self> {
+        Self::with_decryptors(inner, vec![Box::new(decryptor)])
+    }
+
+    /// Wraps `inner`, decrypting each value with the first decryptor that
+    /// handles its scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{AesGcmDecryptor, AgeDecryptor, DecryptingSource, YamlFileAdapter};
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let source = DecryptingSource::with_decryptors(
+    ///     Box::new(YamlFileAdapter::from_file("/etc/myapp/config.yaml")?),
+    ///     vec![
+    ///         Box::new(AesGcmDecryptor::from_key_file("/etc/myapp/config.key")?),
+    ///         Box::new(AgeDecryptor::from_identity_file("/etc/myapp/age.key")?),
+    ///     ],
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_decryptors(
+        inner: Box<dyn ConfigSource>,
+        decryptors: Vec<Box<dyn SecretDecryptor>>,
+    ) -> Result<Self> {
+        let mut source = Self {
+            inner,
+            decryptors,
+            decrypted: HashMap::new(),
+        };
+        source.decrypted = source.decrypt_all()?;
+        Ok(source)
+    }
+
+    /// Decrypts `value` if it is encrypted.
+    fn decrypt_value(&self, key: &ConfigKey, value: &ConfigValue) -> Result<Option<ConfigValue>> {
+        let Some((scheme, payload)) = parse_envelope(value.as_str()) else {
+            return Ok(None);
+        };
+
+        let decryptor = self
+            .decryptors
+            .iter()
+            .find(|d| d.schemes().contains(&scheme))
+            .ok_or_else(|| {
+                decryption_error(key, format!("no decryptor for scheme '{}'", scheme))
+            })?;
+
+        let plaintext = decryptor.decrypt(key, scheme, payload)?;
+        Ok(Some(ConfigValue::secret(plaintext)))
+    }
+
+    /// Decrypts every encrypted value of the inner source.
+    fn decrypt_all(&self) -> Result<HashMap<String, ConfigValue>> {
+        let mut decrypted = HashMap::new();
+        for key in self.inner.all_keys()? {
+            if let Some(value) = self.inner.get(&key)? {
+                if let Some(plaintext) = self.decrypt_value(&key, &value)? {
+                    decrypted.insert(key.as_str().to_string(), plaintext);
+                }
+            }
+        }
+        tracing::debug!(
+            "Decrypted {} values from source '{}'",
+            decrypted.len(),
+            self.inner.name()
+        );
+        Ok(decrypted)
+    }
+}
+
+impl fmt::Debug for DecryptingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecryptingSource")
+            .field("inner", &self.inner.name())
+            .field("decryptors", &self.decryptors.len())
+            .field("decrypted", &self.decrypted.len())
+            .finish()
+    }
+}
+
+impl ConfigSource for DecryptingSource {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        if let Some(value) = self.decrypted.get(key.as_str()) {
+            return Ok(Some(value.clone()));
+        }
+
+        // Keys the inner source does not list are decrypted on demand
+        match self.inner.get(key)? {
+            Some(value) => Ok(Some(self.decrypt_value(key, &value)?.unwrap_or(value))),
+            None => Ok(None),
+        }
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        self.inner.all_keys()
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.inner.reload()?;
+        self.decrypted = self.decrypt_all()?;
+        Ok(())
+    }
+
+    fn health(&self) -> SourceHealth {
+        self.inner.health()
+    }
+}
+
+/// Decryptor for values encrypted with a local AES-256-GCM key.
+///
+/// Handles the `AES256_GCM` scheme. The payload is the base64 encoding of a
+/// 12-byte nonce followed by the ciphertext and authentication tag. The key
+/// file holds a base64-encoded 32-byte key, such as the output of
+/// `openssl rand -base64 32`, and should be kept out of version control.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::AesGcmDecryptor;
+/// use hexcfg::domain::ConfigKey;
+/// use hexcfg::ports::SecretDecryptor;
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// let decryptor = AesGcmDecryptor::new([7u8; 32]);
+///
+/// // ENC[AES256_GCM,...] can be placed in a configuration file
+/// let encrypted = decryptor.encrypt("hunter2")?;
+/// assert!(encrypted.starts_with("ENC[AES256_GCM,"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AesGcmDecryptor {
+    /// The AES-256-GCM cipher holding the key
+    cipher: Aes256Gcm,
+}
+
+impl AesGcmDecryptor {
+    /// Creates a decryptor from a raw 32-byte key.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(&key.into()),
+        }
+    }
+
+    /// Creates a decryptor from a file holding a base64-encoded 32-byte key.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the key file
+    pub fn from_key_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let invalid_key = || ConfigError::SourceError {
+            source_name: "encryption".to_string(),
+            message: format!(
+                "Key file must hold a base64-encoded 32-byte key: {}",
+                path.display()
+            ),
+            source: None,
+        };
+
+        let key = STANDARD
+            .decode(read_key_file(path)?.trim())
+            .map_err(|_| invalid_key())?;
+        let key: [u8; 32] = key.try_into().map_err(|_| invalid_key())?;
+        Ok(Self::new(key))
+    }
+
+    /// Encrypts `plaintext` into an `ENC[AES256_GCM,...]` value.
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| ConfigError::SourceError {
+                source_name: "encryption".to_string(),
+                message: "Failed to encrypt value".to_string(),
+                source: None,
+            })?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&ciphertext);
+        Ok(format!("ENC[AES256_GCM,{}]", STANDARD.encode(payload)))
+    }
+}
+
+impl fmt::Debug for AesGcmDecryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AesGcmDecryptor")
+            .field("key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl SecretDecryptor for AesGcmDecryptor {
+    fn schemes(&self) -> &[&str] {
+        &["AES256_GCM"]
+    }
+
+    fn decrypt(&self, key: &ConfigKey, _scheme: &str, payload: &str) -> Result<String> {
+        let bytes = decode_payload(key, payload)?;
+        if bytes.len() < NONCE_LEN + TAG_LEN {
+            return Err(decryption_error(key, "payload is too short"));
+        }
+
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                decryption_error(key, "authentication failed (wrong key or corrupted value)")
+            })?;
+
+        String::from_utf8(plaintext).map_err(|_| decryption_error(key, "plaintext is not UTF-8"))
+    }
+}
+
+/// Decryptor for values encrypted to age X25519 recipients.
+///
+/// Handles the `AGE` scheme. The payload is the base64 encoding of a binary
+/// age file, such as the output of `age -r age1... | base64 -w0`, so values
+/// can be encrypted to the public keys of everyone allowed to read them. The
+/// identity file uses the format of `age-keygen`: one `AGE-SECRET-KEY-1...`
+/// per line, with `#` comments.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::{AgeDecryptor, DecryptingSource, YamlFileAdapter};
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// let decryptor = AgeDecryptor::from_identity_file("/etc/myapp/age.key")?;
+/// let yaml = YamlFileAdapter::from_file("/etc/myapp/config.yaml")?;
+///
+/// let source = DecryptingSource::new(Box::new(yaml), decryptor)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AgeDecryptor {
+    /// Identities tried in order when decrypting
+    identities: Vec<age::x25519::Identity>,
+}
+
+impl AgeDecryptor {
+    /// Creates a decryptor from an age identity file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the identity file
+    pub fn from_identity_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_identities(&read_key_file(path.as_ref())?)
+    }
+
+    /// Creates a decryptor from identities in the format of an identity file.
+    pub fn from_identities(identities: &str) -> Result<Self> {
+        let identities = identities
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(index, line)| {
+                age::x25519::Identity::from_str(line).map_err(|_| ConfigError::SourceError {
+                    source_name: "encryption".to_string(),
+                    message: format!("Invalid age identity on line {}", index + 1),
+                    source: None,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if identities.is_empty() {
+            return Err(ConfigError::SourceError {
+                source_name: "encryption".to_string(),
+                message: "No age identities found".to_string(),
+                source: None,
+            });
+        }
+
+        Ok(Self { identities })
+    }
+
+    /// Encrypts `plaintext` to an age recipient (`age1...`) as an
+    /// `ENC[AGE,...]` value.
+    pub fn encrypt_to(recipient: &str, plaintext: &str) -> Result<String> {
+        let recipient = age::x25519::Recipient::from_str(recipient.trim()).map_err(|_| {
+            ConfigError::SourceError {
+                source_name: "encryption".to_string(),
+                message: "Invalid age recipient".to_string(),
+                source: None,
+            }
+        })?;
+
+        let ciphertext = age::encrypt(&recipient, plaintext.as_bytes()).map_err(|e| {
+            ConfigError::SourceError {
+                source_name: "encryption".to_string(),
+                message: "Failed to encrypt value".to_string(),
+                source: Some(Box::new(e)),
+            }
+        })?;
+        Ok(format!("ENC[AGE,{}]", STANDARD.encode(ciphertext)))
+    }
+}
+
+impl fmt::Debug for AgeDecryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AgeDecryptor")
+            .field("identities", &self.identities.len())
+            .finish()
+    }
+}
+
+impl SecretDecryptor for AgeDecryptor {
+    fn schemes(&self) -> &[&str] {
+        &["AGE"]
+    }
+
+    fn decrypt(&self, key: &ConfigKey, _scheme: &str, payload: &str) -> Result<String> {
+        let bytes = decode_payload(key, payload)?;
+
+        let decryptor = age::Decryptor::new_buffered(&bytes[..])
+            .map_err(|_| decryption_error(key, "payload is not an age file"))?;
+        let mut reader = decryptor
+            .decrypt(self.identities.iter().map(|i| i as &dyn age::Identity))
+            .map_err(|_| decryption_error(key, "no matching age identity"))?;
+
+        let mut plaintext = Vec::new();
+        reader
+            .read_to_end(&mut plaintext)
+            .map_err(|_| decryption_error(key, "authentication failed (corrupted value)"))?;
+
+        String::from_utf8(plaintext).map_err(|_| decryption_error(key, "plaintext is not UTF-8"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemoryAdapter;
+
+    fn source(values: &[(&str, &str)]) -> Box<dyn ConfigSource> {
+        let values = values
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Box::new(InMemoryAdapter::with_values(values))
+    }
+
+    #[test]
+    fn test_parse_envelope() {
+        assert_eq!(
+            parse_envelope("ENC[AES256_GCM,abc=]"),
+            Some(("AES256_GCM", "abc="))
+        );
+        assert_eq!(
+            parse_envelope("ENC[SOPS,data:a,iv:b]"),
+            Some(("SOPS", "data:a,iv:b"))
+        );
+        assert_eq!(parse_envelope("ENC[,abc]"), None);
+        assert_eq!(parse_envelope("ENC[abc]"), None);
+        assert_eq!(parse_envelope("plain"), None);
+    }
+
+    #[test]
+    fn test_aes_gcm_round_trip() {
+        let decryptor = AesGcmDecryptor::new([1u8; 32]);
+        let encrypted = decryptor.encrypt("hunter2").unwrap();
+        let (scheme, payload) = parse_envelope(&encrypted).unwrap();
+
+        let key = ConfigKey::from("database.password");
+        assert_eq!(decryptor.decrypt(&key, scheme, payload).unwrap(), "hunter2");
+
+        let other = AesGcmDecryptor::new([2u8; 32]);
+        assert!(other.decrypt(&key, scheme, payload).is_err());
+    }
+
+    #[test]
+    fn test_age_round_trip() {
+        let identity = age::x25519::Identity::generate();
+        let recipient = identity.to_public().to_string();
+        let encrypted = AgeDecryptor::encrypt_to(&recipient, "hunter2").unwrap();
+        let (scheme, payload) = parse_envelope(&encrypted).unwrap();
+
+        use age::secrecy::ExposeSecret;
+        let identities = format!(
+            "# created: today\n{}\n",
+            identity.to_string().expose_secret()
+        );
+        let decryptor = AgeDecryptor::from_identities(&identities).unwrap();
+
+        let key = ConfigKey::from("database.password");
+        assert_eq!(decryptor.decrypt(&key, scheme, payload).unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn test_decrypting_source_marks_values_secret() {
+        let decryptor = AesGcmDecryptor::new([1u8; 32]);
+        let encrypted = decryptor.encrypt("hunter2").unwrap();
+        let inner = source(&[("database.password", &encrypted), ("database.host", "db")]);
+
+        let source = DecryptingSource::new(inner, decryptor).unwrap();
+        let password = source.get_str("database.password").unwrap().unwrap();
+        assert_eq!(password.as_str(), "hunter2");
+        assert!(password.is_secret());
+
+        let host = source.get_str("database.host").unwrap().unwrap();
+        assert!(!host.is_secret());
+    }
+
+    #[test]
+    fn test_decryption_errors_hide_values() {
+        let decryptor = AesGcmDecryptor::new([1u8; 32]);
+        let encrypted = AesGcmDecryptor::new([2u8; 32]).encrypt("hunter2").unwrap();
+        let inner = source(&[("database.password", &encrypted)]);
+
+        let err = DecryptingSource::new(inner, decryptor.clone()).unwrap_err();
+        let message = err.to_string();
+        assert!(matches!(err, ConfigError::DecryptionError { .. }));
+        assert!(message.contains("database.password"));
+        assert!(!message.contains("hunter2"));
+        assert!(!message.contains(parse_envelope(&encrypted).unwrap().1));
+
+        let inner = source(&[("api.token", "ENC[VAULT,abc]")]);
+        let err = DecryptingSource::new(inner, decryptor).unwrap_err();
+        assert!(err.to_string().contains("no decryptor for scheme 'VAULT'"));
+    }
+
+    #[test]
+    fn test_decryptor_debug_redacts_keys() {
+        let debug = format!("{:?}", AesGcmDecryptor::new([42u8; 32]));
+        assert!(debug.contains("<redacted>"));
+    }
+}
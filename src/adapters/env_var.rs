@@ -5,10 +5,12 @@
 //! This module provides an adapter that reads configuration values from
 //! environment variables.
 
-use crate::domain::{ConfigKey, ConfigValue, Result};
+use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result};
 use crate::ports::ConfigSource;
 use std::collections::HashMap;
 use std::env;
+use std::fs;
+use std::io::{self, Read};
 use std::sync::RwLock;
 
 /// Maximum length for environment variable keys (prevents DoS)
@@ -17,6 +19,16 @@ const MAX_ENV_KEY_LEN: usize = 512;
 /// Maximum length for environment variable values (prevents DoS)
 const MAX_ENV_VALUE_LEN: usize = 1048576; // 1MB
 
+/// Default suffix of variables that name a file holding the value
+const DEFAULT_FILE_SUFFIX: &str = "_FILE";
+
+/// A loaded variable, or why the file it names could not be used.
+#[derive(Debug, Clone)]
+enum EnvEntry {
+    Value(ConfigValue),
+    Failed(String),
+}
+
 /// Configuration source adapter for environment variables.
 ///
 /// This adapter reads configuration values from environment variables. It supports
@@ -25,8 +37,10 @@ const MAX_ENV_VALUE_LEN: usize = 1048576; // 1MB
 ///
 /// # Priority
 ///
-/// Environment variables have a priority of 2, which means they override configuration
-/// files (priority 1) but are overridden by command-line arguments (priority 3).
+/// Environment variables have a default priority of 2, which means they override
+/// configuration files (priority 1) but are overridden by command-line arguments
+/// (priority 3). Use [`with_priority`](Self::with_priority) to change this, for
+/// example to let environment variables override a remote source.
 ///
 /// # Examples
 ///
@@ -40,6 +54,14 @@ const MAX_ENV_VALUE_LEN: usize = 1048576; // 1MB
 /// // Read only variables with a specific prefix
 /// let adapter = EnvVarAdapter::with_prefix("APP_");
 /// ```
+///
+/// # File Indirection
+///
+/// With [`file_indirection`](Self::file_indirection) enabled, a variable such
+/// as `DB_PASSWORD_FILE=/run/secrets/db` is resolved by reading the named file
+/// and exposing its contents under the key of `DB_PASSWORD`, so secrets do not
+/// have to be placed in the environment. The values are marked as secret with
+/// [`ConfigValue::secret`].
 #[derive(Debug)]
 pub struct EnvVarAdapter {
     /// Optional prefix to filter environment variables
@@ -48,8 +70,14 @@ pub struct EnvVarAdapter {
     lowercase_keys: bool,
     /// Whether to replace underscores with dots
     replace_underscores: bool,
+    /// Optional separator that marks nesting instead of single underscores
+    nesting_separator: Option<String>,
+    /// Suffix of variables that name a file holding the value, if enabled
+    file_suffix: Option<String>,
     /// Cached environment variables with interior mutability for thread-safe lazy loading
-    cache: RwLock<Option<HashMap<String, String>>>,
+    cache: RwLock<Option<HashMap<String, EnvEntry>>>,
+    /// Priority for this source
+    priority: u8,
 }
 
 impl EnvVarAdapter {
@@ -69,7 +97,10 @@ impl EnvVarAdapter {
             prefix: None,
             lowercase_keys: false,
             replace_underscores: true,
+            nesting_separator: None,
+            file_suffix: None,
             cache: RwLock::new(None),
+            priority: 2,
         }
     }
 
@@ -94,7 +125,10 @@ impl EnvVarAdapter {
             prefix: Some(prefix.into()),
             lowercase_keys: false,
             replace_underscores: true,
+            nesting_separator: None,
+            file_suffix: None,
             cache: RwLock::new(None),
+            priority: 2,
         }
     }
 
@@ -134,6 +168,96 @@ impl EnvVarAdapter {
         self
     }
 
+    /// Sets a separator that marks nesting in variable names (e.g. `"__"`).
+    ///
+    /// When set, only the separator is replaced with a dot and single
+    /// underscores are kept, so `DB__MAX_CONNECTIONS` becomes
+    /// `DB.MAX_CONNECTIONS` rather than `DB.MAX.CONNECTIONS`. This takes
+    /// precedence over [`replace_underscores`](Self::replace_underscores).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::EnvVarAdapter;
+    ///
+    /// let adapter = EnvVarAdapter::with_prefix("APP__")
+    ///     .nesting_separator("__");
+    /// ```
+    pub fn nesting_separator(mut self, separator: impl Into<String>) -> Self {
+        let separator = separator.into();
+        self.nesting_separator = if separator.is_empty() {
+            None
+        } else {
+            Some(separator)
+        };
+        self
+    }
+
+    /// Sets whether variables ending in `_FILE` are read from the file they name.
+    ///
+    /// When enabled, `DB_PASSWORD_FILE=/run/secrets/db` provides the contents
+    /// of `/run/secrets/db`, with trailing newlines trimmed, under the key
+    /// `DB_PASSWORD` would have. Files larger than the 1MB limit for
+    /// environment values, unreadable files, and setting both `DB_PASSWORD`
+    /// and `DB_PASSWORD_FILE` are errors for lookups of that key only; other
+    /// keys stay readable. Files are read once per load, not on every lookup.
+    /// The values are marked as secret.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::EnvVarAdapter;
+    ///
+    /// let adapter = EnvVarAdapter::with_prefix("MYAPP_")
+    ///     .file_indirection(true);
+    /// ```
+    pub fn file_indirection(mut self, enabled: bool) -> Self {
+        self.file_suffix = enabled.then(|| DEFAULT_FILE_SUFFIX.to_string());
+        self
+    }
+
+    /// Enables file indirection for variables ending in `suffix` instead of
+    /// `_FILE`.
+    ///
+    /// An empty suffix disables file indirection. See
+    /// [`file_indirection`](Self::file_indirection).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::EnvVarAdapter;
+    ///
+    /// // DB_PASSWORD_PATH=/run/secrets/db
+    /// let adapter = EnvVarAdapter::new()
+    ///     .file_indirection_suffix("_PATH");
+    /// ```
+    pub fn file_indirection_suffix(mut self, suffix: impl Into<String>) -> Self {
+        let suffix = suffix.into();
+        self.file_suffix = if suffix.is_empty() {
+            None
+        } else {
+            Some(suffix)
+        };
+        self
+    }
+
+    /// Sets the priority of this source (default 2).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::EnvVarAdapter;
+    /// use hexcfg::ports::ConfigSource;
+    ///
+    /// // Let environment variables override remote sources
+    /// let adapter = EnvVarAdapter::with_prefix("MYAPP_").with_priority(10);
+    /// assert_eq!(adapter.priority(), 10);
+    /// ```
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Creates an adapter with pre-populated values for testing.
     ///
     /// **Note**: This method is primarily intended for testing and should not
@@ -163,20 +287,31 @@ impl EnvVarAdapter {
             prefix: None,
             lowercase_keys: false,
             replace_underscores: false,
-            cache: RwLock::new(Some(values)),
+            nesting_separator: None,
+            file_suffix: None,
+            cache: RwLock::new(Some(
+                values
+                    .into_iter()
+                    .map(|(k, v)| (k, EnvEntry::Value(ConfigValue::from(v))))
+                    .collect(),
+            )),
+            priority: 2,
         }
     }
 
     /// Loads environment variables into a new HashMap.
-    fn load(&self) -> HashMap<String, String> {
+    ///
+    /// A `_FILE` variable that cannot be resolved is recorded as a failure
+    /// for its key instead of failing the whole load.
+    fn load(&self) -> HashMap<String, EnvEntry> {
         let mut cache = HashMap::new();
 
-        for (key, value) in env::vars() {
+        for (name, value) in env::vars() {
             // Validate input sizes to prevent DoS
-            if key.len() > MAX_ENV_KEY_LEN || value.len() > MAX_ENV_VALUE_LEN {
+            if name.len() > MAX_ENV_KEY_LEN || value.len() > MAX_ENV_VALUE_LEN {
                 tracing::debug!(
                     "Skipping oversized environment variable: key_len={}, value_len={} (max key={}, max value={})",
-                    key.len(),
+                    name.len(),
                     value.len(),
                     MAX_ENV_KEY_LEN,
                     MAX_ENV_VALUE_LEN
@@ -186,43 +321,110 @@ impl EnvVarAdapter {
 
             // Apply prefix filtering
             let key = if let Some(prefix) = &self.prefix {
-                if !key.starts_with(prefix) {
+                if !name.starts_with(prefix) {
                     continue;
                 }
                 // Strip prefix - this is safe because we just checked starts_with
-                match key.strip_prefix(prefix) {
+                match name.strip_prefix(prefix) {
                     Some(stripped) => stripped.to_string(),
                     None => continue, // Should never happen, but skip if it does
                 }
             } else {
-                key
+                name.clone()
             };
 
-            // Apply transformations
-            let mut transformed_key = key;
-            if self.lowercase_keys {
-                transformed_key = transformed_key.to_lowercase();
-            }
-            if self.replace_underscores {
-                transformed_key = transformed_key.replace('_', ".");
-            }
+            // Resolve `_FILE` variables to the contents of the named file
+            let indirect_key = self
+                .file_suffix
+                .as_deref()
+                .and_then(|suffix| Some((key.strip_suffix(suffix)?, suffix)))
+                .filter(|(stripped, _)| !stripped.is_empty());
+            let (key, entry) = match indirect_key {
+                Some((stripped, suffix)) => {
+                    // The variable the file stands in for, with any prefix
+                    let direct = &name[..name.len() - suffix.len()];
+                    let entry = if env::var_os(direct).is_some() {
+                        EnvEntry::Failed(format!("Both {} and {} are set", direct, name))
+                    } else {
+                        match Self::read_file_value(&name, &value) {
+                            Ok(value) => EnvEntry::Value(ConfigValue::secret(value)),
+                            Err(message) => EnvEntry::Failed(message),
+                        }
+                    };
+                    (stripped.to_string(), entry)
+                }
+                None => (key, EnvEntry::Value(ConfigValue::from(value))),
+            };
 
-            cache.insert(transformed_key, value);
+            let key = self.transform_key(key);
+            if let EnvEntry::Failed(message) = &entry {
+                tracing::warn!("Environment variable {} is unusable: {}", key, message);
+                cache.insert(key, entry);
+            } else {
+                // A failure for the key is kept over the direct variable
+                cache.entry(key).or_insert(entry);
+            }
         }
 
         tracing::debug!(
-            "Loaded {} environment variables (prefix={:?}, lowercase={}, replace_underscores={})",
+            "Loaded {} environment variables (prefix={:?}, lowercase={}, replace_underscores={}, nesting_separator={:?}, file_suffix={:?})",
             cache.len(),
             self.prefix,
             self.lowercase_keys,
-            self.replace_underscores
+            self.replace_underscores,
+            self.nesting_separator,
+            self.file_suffix
         );
 
         cache
     }
 
+    /// Applies the configured key transformations.
+    fn transform_key(&self, key: String) -> String {
+        let mut transformed_key = key;
+        if self.lowercase_keys {
+            transformed_key = transformed_key.to_lowercase();
+        }
+        if let Some(separator) = &self.nesting_separator {
+            transformed_key = transformed_key.replace(separator.as_str(), ".");
+        } else if self.replace_underscores {
+            transformed_key = transformed_key.replace('_', ".");
+        }
+        transformed_key
+    }
+
+    /// Reads the file named by the variable `name`, enforcing the value size
+    /// limit and trimming trailing newlines.
+    ///
+    /// The limit is applied to the bytes read rather than the reported file
+    /// size, which is 0 for pipes, devices and `/proc` files.
+    ///
+    /// Returns the reason as a message on failure, so it can be cached.
+    fn read_file_value(name: &str, path: &str) -> std::result::Result<String, String> {
+        let read_error =
+            |e: io::Error| format!("Failed to read file {} named by {}: {}", path, name, e);
+
+        let mut content = Vec::new();
+        fs::File::open(path)
+            .and_then(|file| {
+                file.take(MAX_ENV_VALUE_LEN as u64 + 1)
+                    .read_to_end(&mut content)
+            })
+            .map_err(read_error)?;
+        if content.len() > MAX_ENV_VALUE_LEN {
+            return Err(format!(
+                "File {} named by {} is too large (max {} bytes)",
+                path, name, MAX_ENV_VALUE_LEN
+            ));
+        }
+
+        let content = String::from_utf8(content)
+            .map_err(|e| read_error(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+        Ok(content.trim_end_matches(['\n', '\r']).to_string())
+    }
+
     /// Gets the cache, loading it if necessary. Uses interior mutability for thread-safe lazy loading.
-    fn get_cache(&self) -> HashMap<String, String> {
+    fn get_cache(&self) -> HashMap<String, EnvEntry> {
         // Try to read from cache first
         {
             let cache_guard = self.cache.read().unwrap();
@@ -256,15 +458,21 @@ impl ConfigSource for EnvVarAdapter {
     }
 
     fn priority(&self) -> u8 {
-        2
+        self.priority
     }
 
     fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
         let cache = self.get_cache();
 
-        Ok(cache
-            .get(key.as_str())
-            .map(|v| ConfigValue::from(v.as_str())))
+        match cache.get(key.as_str()) {
+            Some(EnvEntry::Value(value)) => Ok(Some(value.clone())),
+            Some(EnvEntry::Failed(message)) => Err(ConfigError::SourceError {
+                source_name: "env".to_string(),
+                message: message.clone(),
+                source: None,
+            }),
+            None => Ok(None),
+        }
     }
 
     fn all_keys(&self) -> Result<Vec<ConfigKey>> {
@@ -448,4 +656,144 @@ mod tests {
         assert!(value.is_some());
         assert_eq!(value.unwrap().as_str(), "localhost");
     }
+
+    #[test]
+    fn test_env_adapter_nesting_separator() {
+        let mut guard = EnvGuard::new();
+        guard.set("NESTAPP__DB__MAX_CONNECTIONS", "50");
+
+        let adapter = EnvVarAdapter::with_prefix("NESTAPP__")
+            .lowercase_keys(true)
+            .nesting_separator("__");
+
+        let key = ConfigKey::from("db.max_connections");
+        let value = adapter.get(&key).unwrap();
+
+        assert!(value.is_some());
+        assert_eq!(value.unwrap().as_str(), "50");
+    }
+
+    #[test]
+    fn test_env_adapter_file_indirection() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("db_password");
+        fs::write(&path, "hunter2\n").unwrap();
+
+        let mut guard = EnvGuard::new();
+        guard.set("FILEAPP_DB_PASSWORD_FILE", path.to_str().unwrap());
+        guard.set("FILEAPP_DB_USER", "admin");
+
+        let adapter = EnvVarAdapter::with_prefix("FILEAPP_")
+            .lowercase_keys(true)
+            .file_indirection(true);
+
+        let value = adapter
+            .get(&ConfigKey::from("db.password"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(value.as_str(), "hunter2");
+        assert!(value.is_secret());
+        assert!(!format!("{:?}", value).contains("hunter2"));
+
+        let value = adapter.get(&ConfigKey::from("db.user")).unwrap().unwrap();
+        assert!(!value.is_secret());
+        assert!(adapter
+            .get(&ConfigKey::from("db.password.file"))
+            .unwrap()
+            .is_none());
+
+        // Without indirection the variable is an ordinary value
+        let adapter = EnvVarAdapter::with_prefix("FILEAPP_").lowercase_keys(true);
+        assert!(adapter
+            .get(&ConfigKey::from("db.password.file"))
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_env_adapter_file_indirection_custom_suffix() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("token");
+        fs::write(&path, "abc123").unwrap();
+
+        let mut guard = EnvGuard::new();
+        guard.set("PATHAPP__API_TOKEN__PATH", path.to_str().unwrap());
+
+        let adapter = EnvVarAdapter::with_prefix("PATHAPP__")
+            .nesting_separator("__")
+            .file_indirection_suffix("__PATH");
+
+        let value = adapter.get(&ConfigKey::from("API_TOKEN")).unwrap().unwrap();
+        assert_eq!(value.as_str(), "abc123");
+    }
+
+    #[test]
+    fn test_env_adapter_file_indirection_errors() {
+        let mut guard = EnvGuard::new();
+        guard.set("MISSINGAPP_KEY_FILE", "/nonexistent/hexcfg/secret");
+
+        let adapter = EnvVarAdapter::with_prefix("MISSINGAPP_").file_indirection(true);
+        let err = adapter.get(&ConfigKey::from("KEY")).unwrap_err();
+        assert!(err.to_string().contains("MISSINGAPP_KEY_FILE"));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("big");
+        fs::write(&path, vec![b'x'; MAX_ENV_VALUE_LEN + 1]).unwrap();
+        guard.set("BIGAPP_KEY_FILE", path.to_str().unwrap());
+        let adapter = EnvVarAdapter::with_prefix("BIGAPP_").file_indirection(true);
+        assert!(adapter.get(&ConfigKey::from("KEY")).is_err());
+
+        // Devices report a size of 0, but are still cut off at the limit
+        #[cfg(unix)]
+        {
+            guard.set("ZEROAPP_KEY_FILE", "/dev/zero");
+            let adapter = EnvVarAdapter::with_prefix("ZEROAPP_").file_indirection(true);
+            let err = adapter.get(&ConfigKey::from("KEY")).unwrap_err();
+            assert!(err.to_string().contains("too large"));
+        }
+
+        fs::write(&path, "value").unwrap();
+        guard.set("BIGAPP_KEY", "direct");
+        let adapter = EnvVarAdapter::with_prefix("BIGAPP_").file_indirection(true);
+        let err = adapter.get(&ConfigKey::from("KEY")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Both BIGAPP_KEY and BIGAPP_KEY_FILE"));
+    }
+
+    #[test]
+    fn test_env_adapter_file_indirection_failure_is_per_key() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("token");
+
+        let mut guard = EnvGuard::new();
+        guard.set("PERKEYAPP_TOKEN_FILE", path.to_str().unwrap());
+        guard.set("PERKEYAPP_USER", "admin");
+
+        let adapter = EnvVarAdapter::with_prefix("PERKEYAPP_").file_indirection(true);
+        assert!(adapter.get(&ConfigKey::from("TOKEN")).is_err());
+        assert_eq!(
+            adapter
+                .get(&ConfigKey::from("USER"))
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "admin"
+        );
+        assert!(adapter.all_keys().is_ok());
+
+        // The failure is cached until the next reload
+        fs::write(&path, "abc123").unwrap();
+        assert!(adapter.get(&ConfigKey::from("TOKEN")).is_err());
+        let mut adapter = adapter;
+        adapter.reload().unwrap();
+        assert_eq!(
+            adapter
+                .get(&ConfigKey::from("TOKEN"))
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "abc123"
+        );
+    }
 }
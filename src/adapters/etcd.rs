@@ -2,26 +2,390 @@
 
 //! etcd configuration source adapter.
 //!
-//! This module provides an adapter that reads configuration values from etcd.
+//! This module provides an adapter that reads configuration values from etcd,
+//! and the connection options and revisioned cache it shares with `EtcdWatcher`.
 
+use crate::adapters::runtime::block_on;
+use crate::adapters::shared::Shared;
+use crate::adapters::{EtcdWatcher, KeySeparator};
 use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result};
-use crate::ports::ConfigSource;
-use etcd_client::{Client, GetOptions};
-use once_cell::sync::Lazy;
+use crate::ports::{AsyncConfigSource, BoxFuture, ConfigChange, ConfigSink, ConfigSource};
+use etcd_client::{
+    Certificate, Client, ConnectOptions, GetOptions, Identity, TlsOptions, Txn, TxnOp,
+};
 use std::collections::HashMap;
 use std::fmt;
+use std::fs;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
-/// Shared runtime for reload operations to avoid expensive runtime creation on every reload
-static RELOAD_RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
-    tokio::runtime::Runtime::new().expect("Failed to create reload runtime for etcd adapter")
-});
+/// Connection options for etcd clusters.
+///
+/// Covers authentication, TLS (including mutual TLS with a client certificate)
+/// and timeouts. The same options are used by [`EtcdAdapter`] and
+/// [`EtcdWatcher`](crate::adapters::EtcdWatcher), including when they reconnect.
+///
+/// TLS is enabled when a CA certificate, client identity or domain name is
+/// set. Endpoints without a scheme then connect over `https://`.
+///
+/// The options also carry the key separator translation, so an adapter and the
+/// watchers created from it map keys the same way. By default `/` in etcd keys
+/// becomes `.` in configuration keys.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::{EtcdAdapter, EtcdConnectOptions};
+/// use std::time::Duration;
+///
+/// # #[tokio::main]
+/// # async fn main() -> hexcfg::domain::Result<()> {
+/// let options = EtcdConnectOptions::new()
+///     .with_user("myapp", "secret")
+///     .with_ca_cert_file("/etc/etcd/ca.pem")?
+///     .with_client_identity_files("/etc/etcd/client.pem", "/etc/etcd/client-key.pem")?
+///     .with_connect_timeout(Duration::from_secs(5))
+///     .with_request_timeout(Duration::from_secs(10));
+///
+/// let adapter = EtcdAdapter::with_options(vec!["etcd.internal:2379"], Some("myapp/"), options)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct EtcdConnectOptions {
+    /// User name and password for etcd authentication
+    user: Option<(String, String)>,
+    /// PEM-encoded CA certificate used to verify the server
+    ca_cert: Option<Vec<u8>>,
+    /// PEM-encoded client certificate and private key
+    client_identity: Option<(Vec<u8>, Vec<u8>)>,
+    /// Domain name to verify the server certificate against
+    domain_name: Option<String>,
+    /// Timeout for establishing a connection
+    connect_timeout: Option<Duration>,
+    /// Timeout for each request
+    request_timeout: Option<Duration>,
+    /// HTTP/2 keep-alive interval and timeout
+    keep_alive: Option<(Duration, Duration)>,
+    /// Key separator translation; `/` when not set
+    key_separator: Option<KeySeparator>,
+}
+
+impl fmt::Debug for EtcdConnectOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EtcdConnectOptions")
+            .field("user", &self.user.as_ref().map(|(name, _)| name))
+            .field("ca_cert", &self.ca_cert.as_ref().map(|_| "<pem>"))
+            .field(
+                "client_identity",
+                &self.client_identity.as_ref().map(|_| "<redacted>"),
+            )
+            .field("domain_name", &self.domain_name)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("request_timeout", &self.request_timeout)
+            .field("keep_alive", &self.keep_alive)
+            .field("key_separator", &self.key_separator())
+            .finish()
+    }
+}
+
+impl EtcdConnectOptions {
+    /// Creates options for a plain, unauthenticated connection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Authenticates with etcd's role-based access control.
+    pub fn with_user(mut self, name: impl Into<String>, password: impl Into<String>) -> Self {
+        self.user = Some((name.into(), password.into()));
+        self
+    }
+
+    /// Verifies the server against a PEM-encoded CA certificate bundle.
+    pub fn with_ca_cert(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.ca_cert = Some(pem.into());
+        self
+    }
+
+    /// Reads the CA certificate bundle from a PEM file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::SourceError` if the file cannot be read.
+    pub fn with_ca_cert_file(self, path: impl AsRef<Path>) -> Result<Self> {
+        let pem = Self::read_pem(path.as_ref())?;
+        Ok(self.with_ca_cert(pem))
+    }
+
+    /// Presents a PEM-encoded client certificate and private key (mutual TLS).
+    pub fn with_client_identity(
+        mut self,
+        cert_pem: impl Into<Vec<u8>>,
+        key_pem: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.client_identity = Some((cert_pem.into(), key_pem.into()));
+        self
+    }
+
+    /// Reads the client certificate and private key from PEM files.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::SourceError` if either file cannot be read.
+    pub fn with_client_identity_files(
+        self,
+        cert_path: impl AsRef<Path>,
+        key_path: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let cert = Self::read_pem(cert_path.as_ref())?;
+        let key = Self::read_pem(key_path.as_ref())?;
+        Ok(self.with_client_identity(cert, key))
+    }
+
+    /// Overrides the domain name the server certificate is verified against.
+    ///
+    /// By default the host of each endpoint is used.
+    pub fn with_domain_name(mut self, domain_name: impl Into<String>) -> Self {
+        self.domain_name = Some(domain_name.into());
+        self
+    }
+
+    /// Sets the timeout for establishing a connection.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for each request.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Sends HTTP/2 keep-alive pings every `interval`, closing the connection
+    /// if no reply arrives within `timeout`.
+    pub fn with_keep_alive(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.keep_alive = Some((interval, timeout));
+        self
+    }
+
+    /// Sets how etcd keys map to configuration keys.
+    ///
+    /// The translation applies to loaded keys, watch events and writes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::{EtcdConnectOptions, KeySeparator};
+    ///
+    /// // Keep etcd keys as they are, e.g. `myapp/db.host` maps to `db.host`
+    /// let options = EtcdConnectOptions::new().with_key_separator(KeySeparator::None);
+    /// assert_eq!(options.key_separator(), KeySeparator::None);
+    /// ```
+    pub fn with_key_separator(mut self, separator: KeySeparator) -> Self {
+        self.key_separator = Some(separator);
+        self
+    }
+
+    /// Returns the key separator translation, `/` unless overridden.
+    pub fn key_separator(&self) -> KeySeparator {
+        self.key_separator
+            .clone()
+            .unwrap_or_else(|| KeySeparator::translate("/"))
+    }
+
+    /// Returns `true` if these options connect over TLS.
+    pub fn uses_tls(&self) -> bool {
+        self.ca_cert.is_some() || self.client_identity.is_some() || self.domain_name.is_some()
+    }
+
+    /// Connects to etcd with these options.
+    pub(crate) async fn connect(
+        &self,
+        endpoints: &[String],
+    ) -> std::result::Result<Client, etcd_client::Error> {
+        Client::connect(endpoints, Some(self.to_connect_options())).await
+    }
+
+    /// Converts these options to the etcd client's connect options.
+    fn to_connect_options(&self) -> ConnectOptions {
+        let mut options = ConnectOptions::new();
+
+        if let Some((name, password)) = &self.user {
+            options = options.with_user(name.as_str(), password.as_str());
+        }
+        if let Some(timeout) = self.connect_timeout {
+            options = options.with_connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.request_timeout {
+            options = options.with_timeout(timeout);
+        }
+        if let Some((interval, timeout)) = self.keep_alive {
+            options = options.with_keep_alive(interval, timeout);
+        }
+
+        if self.uses_tls() {
+            let mut tls = TlsOptions::new();
+            if let Some(pem) = &self.ca_cert {
+                tls = tls.ca_certificate(Certificate::from_pem(pem));
+            }
+            if let Some((cert, key)) = &self.client_identity {
+                tls = tls.identity(Identity::from_pem(cert, key));
+            }
+            if let Some(domain_name) = &self.domain_name {
+                tls = tls.domain_name(domain_name.as_str());
+            }
+            options = options.with_tls(tls);
+        }
+
+        options
+    }
+
+    fn read_pem(path: &Path) -> Result<Vec<u8>> {
+        fs::read(path).map_err(|e| ConfigError::SourceError {
+            source_name: "etcd".to_string(),
+            message: format!("Failed to read PEM file: {}", path.display()),
+            source: Some(Box::new(e)),
+        })
+    }
+}
+
+/// Keys under a prefix together with the etcd revision they reflect.
+///
+/// Shared between an [`EtcdAdapter`] and the watchers it creates, so watch events
+/// patch the adapter's cache directly.
+#[derive(Debug, Default)]
+pub(crate) struct EtcdState {
+    /// Cached configuration values, keyed by configuration key
+    values: HashMap<String, String>,
+    /// Revision of the snapshot the values were loaded from; 0 before loading
+    revision: i64,
+}
+
+/// Handle to an [`EtcdState`] shared between an adapter and its watchers.
+pub(crate) type SharedEtcdState = Shared<EtcdState>;
+
+impl EtcdState {
+    /// Returns the revision this state is consistent with.
+    pub(crate) fn revision(&self) -> i64 {
+        self.revision
+    }
+
+    /// Replaces the values with a newer snapshot.
+    ///
+    /// Snapshots older than the current state are ignored, since watch events
+    /// may already have moved it past them. Returns the keys whose values changed.
+    pub(crate) fn replace(&mut self, snapshot: EtcdState) -> Vec<String> {
+        if snapshot.revision <= self.revision {
+            return Vec::new();
+        }
+
+        let mut changed: Vec<String> = snapshot
+            .values
+            .iter()
+            .filter(|(key, value)| self.values.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .collect();
+        changed.extend(
+            self.values
+                .keys()
+                .filter(|key| !snapshot.values.contains_key(*key))
+                .cloned(),
+        );
+
+        *self = snapshot;
+        changed
+    }
+
+    /// Applies the events of one watch response.
+    ///
+    /// Events at or below the current revision are already reflected in the
+    /// state and are skipped. Returns the keys the events touched.
+    pub(crate) fn apply_events(
+        &mut self,
+        prefix: &str,
+        separator: &KeySeparator,
+        events: &[etcd_client::Event],
+    ) -> Vec<String> {
+        let base = self.revision;
+        let mut keys = Vec::new();
+
+        for event in events {
+            let Some(kv) = event.kv() else { continue };
+            let Ok(key) = kv.key_str() else { continue };
+            let key = config_key(prefix, separator, key);
+
+            if kv.mod_revision() > base {
+                match event.event_type() {
+                    etcd_client::EventType::Put => {
+                        let Ok(value) = kv.value_str() else { continue };
+                        self.values.insert(key.clone(), value.to_string());
+                    }
+                    etcd_client::EventType::Delete => {
+                        self.values.remove(&key);
+                    }
+                }
+                self.revision = self.revision.max(kv.mod_revision());
+            }
+            keys.push(key);
+        }
+
+        keys
+    }
+}
+
+/// Converts an etcd key under `prefix` to a configuration key.
+pub(crate) fn config_key(prefix: &str, separator: &KeySeparator, key: &str) -> String {
+    separator.to_config_key(key.strip_prefix(prefix).unwrap_or(key))
+}
+
+/// Fetches all keys under the prefix, along with the revision of the read.
+pub(crate) async fn fetch_snapshot(
+    client: &mut Client,
+    prefix: Option<&str>,
+    separator: &KeySeparator,
+) -> Result<EtcdState> {
+    let prefix = prefix.unwrap_or("");
+
+    let options = GetOptions::new().with_prefix();
+    let response =
+        client
+            .get(prefix, Some(options))
+            .await
+            .map_err(|e| ConfigError::SourceError {
+                source_name: "etcd".to_string(),
+                message: format!("Failed to fetch keys from etcd: {}", e),
+                source: Some(Box::new(e)),
+            })?;
+
+    let mut values = HashMap::new();
+    for kv in response.kvs() {
+        if let (Ok(key), Ok(value)) = (kv.key_str(), kv.value_str()) {
+            values.insert(config_key(prefix, separator, key), value.to_string());
+        }
+    }
+
+    Ok(EtcdState {
+        values,
+        revision: response.header().map_or(0, |h| h.revision()),
+    })
+}
 
 /// Configuration source adapter for etcd.
 ///
 /// This adapter reads configuration values from an etcd cluster. It supports
 /// key prefix filtering for namespacing and includes connection retry logic.
 ///
+/// # Consistency
+///
+/// The adapter records the etcd revision of the snapshot it loaded (see
+/// [`revision`](Self::revision)). A watcher created with
+/// [`watcher`](Self::watcher) starts watching right after that revision and
+/// applies each event to the adapter's cache, so no change is missed between
+/// loading and watching and no full re-read is needed per change.
+///
 /// # Priority
 ///
 /// etcd has a default priority of 1, but this can be customized.
@@ -45,12 +409,14 @@ pub struct EtcdAdapter {
     client: Arc<Client>,
     /// etcd endpoints for reconnection
     endpoints: Vec<String>,
+    /// Connection options for reconnection
+    options: EtcdConnectOptions,
     /// Key prefix for namespacing
     prefix: Option<String>,
     /// Priority for this source
     priority: u8,
-    /// Cached configuration values
-    cache: HashMap<String, String>,
+    /// Cached configuration values and their revision
+    state: SharedEtcdState,
 }
 
 impl fmt::Debug for EtcdAdapter {
@@ -58,9 +424,10 @@ impl fmt::Debug for EtcdAdapter {
         f.debug_struct("EtcdAdapter")
             .field("client", &"<etcd::Client>")
             .field("endpoints", &self.endpoints)
+            .field("options", &self.options)
             .field("prefix", &self.prefix)
             .field("priority", &self.priority)
-            .field("cache", &self.cache)
+            .field("state", &self.state)
             .finish()
     }
 }
@@ -99,6 +466,35 @@ impl EtcdAdapter {
     /// # }
     /// ```
     pub async fn new<S: AsRef<str>>(endpoints: Vec<S>, prefix: Option<&str>) -> Result<Self> {
+        Self::with_options(endpoints, prefix, EtcdConnectOptions::new()).await
+    }
+
+    /// Creates a new etcd adapter with authentication, TLS or timeout options.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - List of etcd endpoints
+    /// * `prefix` - Optional key prefix for namespacing
+    /// * `options` - Connection options, also used when reconnecting
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{EtcdAdapter, EtcdConnectOptions};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let options = EtcdConnectOptions::new().with_user("myapp", "secret");
+    /// let adapter = EtcdAdapter::with_options(vec!["localhost:2379"], Some("myapp/"), options)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_options<S: AsRef<str>>(
+        endpoints: Vec<S>,
+        prefix: Option<&str>,
+        options: EtcdConnectOptions,
+    ) -> Result<Self> {
         // Validate prefix if provided
         if let Some(p) = prefix {
             Self::validate_prefix(p)?;
@@ -106,21 +502,15 @@ impl EtcdAdapter {
 
         let endpoints: Vec<String> = endpoints.iter().map(|s| s.as_ref().to_string()).collect();
 
-        let client =
-            Client::connect(&endpoints, None)
-                .await
-                .map_err(|e| ConfigError::SourceError {
-                    source_name: "etcd".to_string(),
-                    message: format!("Failed to connect to etcd: {}", e),
-                    source: Some(Box::new(e)),
-                })?;
+        let client = Self::connect(&endpoints, &options).await?;
 
         let mut adapter = Self {
             client: Arc::new(client),
             endpoints: endpoints.clone(),
+            options,
             prefix: prefix.map(|s| s.to_string()),
             priority: 1,
-            cache: HashMap::new(),
+            state: SharedEtcdState::default(),
         };
 
         // Initial load of all keys
@@ -162,151 +552,107 @@ impl EtcdAdapter {
         Ok(adapter)
     }
 
+    /// Returns the etcd revision the cached values are consistent with.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::EtcdAdapter;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let adapter = EtcdAdapter::new(vec!["localhost:2379"], Some("myapp/")).await?;
+    /// println!("Loaded configuration at revision {}", adapter.revision());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn revision(&self) -> i64 {
+        self.state.read().revision()
+    }
+
+    /// Creates a watcher that keeps this adapter's cache up to date.
+    ///
+    /// The watcher starts at the revision after the adapter's snapshot and
+    /// resumes from the last revision it saw when it reconnects. Changes are
+    /// applied to the cache before the callback runs, so callbacks only need to
+    /// clear cached values in the service (see
+    /// [`DefaultConfigService::clear_cache`](crate::service::DefaultConfigService::clear_cache)).
+    /// If etcd has compacted the revisions the watcher needs, it reloads the
+    /// prefix and calls the callback for every key that changed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::EtcdAdapter;
+    /// use hexcfg::ports::ConfigWatcher;
+    /// use std::sync::Arc;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let adapter = EtcdAdapter::new(vec!["localhost:2379"], Some("myapp/")).await?;
+    ///
+    /// let mut watcher = adapter.watcher();
+    /// watcher.watch(Arc::new(|key| {
+    ///     println!("Configuration changed: {}", key);
+    /// }))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watcher(&self) -> EtcdWatcher {
+        EtcdWatcher::with_state(
+            self.endpoints.clone(),
+            self.options.clone(),
+            self.prefix.clone(),
+            self.state.clone(),
+        )
+    }
+
     /// Loads all keys from etcd into the cache.
     async fn load_all_keys(&mut self) -> Result<()> {
-        let prefix = self.prefix.as_deref().unwrap_or("");
-
         let mut client = (*self.client).clone();
+        let separator = self.options.key_separator();
+        let snapshot = fetch_snapshot(&mut client, self.prefix.as_deref(), &separator).await?;
+        self.state.write().replace(snapshot);
+        Ok(())
+    }
 
-        let options = GetOptions::new().with_prefix();
-        let response =
-            client
-                .get(prefix, Some(options))
-                .await
-                .map_err(|e| ConfigError::SourceError {
-                    source_name: "etcd".to_string(),
-                    message: format!("Failed to fetch keys from etcd: {}", e),
-                    source: Some(Box::new(e)),
-                })?;
-
-        self.cache.clear();
-
-        for kv in response.kvs() {
-            if let (Ok(key), Ok(value)) = (kv.key_str(), kv.value_str()) {
-                // Strip prefix from key
-                let key = if !prefix.is_empty() && key.starts_with(prefix) {
-                    &key[prefix.len()..]
-                } else {
-                    key
-                };
-
-                // Convert slashes to dots for consistency with other adapters
-                let key = key.replace('/', ".");
-
-                self.cache.insert(key, value.to_string());
-            }
-        }
+    /// Converts a configuration key to the etcd key it is stored under.
+    fn storage_key(&self, key: &ConfigKey) -> String {
+        format!(
+            "{}{}",
+            self.prefix.as_deref().unwrap_or(""),
+            self.options.key_separator().to_storage_key(key.as_str())
+        )
+    }
 
-        Ok(())
+    /// Connects to etcd, mapping failures to a source error.
+    async fn connect(endpoints: &[String], options: &EtcdConnectOptions) -> Result<Client> {
+        options
+            .connect(endpoints)
+            .await
+            .map_err(|e| ConfigError::SourceError {
+                source_name: "etcd".to_string(),
+                message: format!("Failed to connect to etcd: {}", e),
+                source: Some(Box::new(e)),
+            })
     }
 
     /// Reloads all keys from etcd synchronously.
     ///
-    /// Note: This method uses a shared runtime to perform async operations efficiently.
-    /// If called from an async context, it will spawn a separate thread to avoid blocking.
+    /// The client is bound to the runtime it was created on, so a fresh connection
+    /// is made on the shared blocking runtime.
     fn reload_sync(&mut self) -> Result<()> {
-        let endpoints = self.endpoints.clone();
-        let prefix = self.prefix.clone();
-
-        // Try to use the current runtime if available, otherwise use the shared runtime
-        let new_cache = if tokio::runtime::Handle::try_current().is_ok() {
-            // We're in an async context, need to spawn a separate thread with the shared runtime
-            // to avoid blocking the current runtime's executor
-            let handle = std::thread::spawn(move || {
-                RELOAD_RUNTIME.block_on(async move {
-                    let prefix_str = prefix.as_deref().unwrap_or("");
-
-                    // Connect fresh to etcd
-                    let mut client = Client::connect(&endpoints, None).await.map_err(|e| {
-                        ConfigError::SourceError {
-                            source_name: "etcd".to_string(),
-                            message: format!("Failed to connect to etcd: {}", e),
-                            source: Some(Box::new(e)),
-                        }
-                    })?;
-
-                    let options = GetOptions::new().with_prefix();
-                    let response = client.get(prefix_str, Some(options)).await.map_err(|e| {
-                        ConfigError::SourceError {
-                            source_name: "etcd".to_string(),
-                            message: format!("Failed to fetch keys from etcd: {}", e),
-                            source: Some(Box::new(e)),
-                        }
-                    })?;
-
-                    let mut new_cache = HashMap::new();
-
-                    for kv in response.kvs() {
-                        if let (Ok(key), Ok(value)) = (kv.key_str(), kv.value_str()) {
-                            // Strip prefix from key
-                            let key = if !prefix_str.is_empty() && key.starts_with(prefix_str) {
-                                &key[prefix_str.len()..]
-                            } else {
-                                key
-                            };
-
-                            // Convert slashes to dots for consistency
-                            let key = key.replace('/', ".");
-
-                            new_cache.insert(key, value.to_string());
-                        }
-                    }
+        let endpoints = &self.endpoints;
+        let options = &self.options;
+        let prefix = self.prefix.as_deref();
+        let separator = options.key_separator();
 
-                    Ok::<HashMap<String, String>, ConfigError>(new_cache)
-                })
-            });
+        let snapshot = block_on(async move {
+            let mut client = Self::connect(endpoints, options).await?;
+            fetch_snapshot(&mut client, prefix, &separator).await
+        })?;
+        self.state.write().replace(snapshot);
 
-            handle.join().map_err(|_| ConfigError::SourceError {
-                source_name: "etcd".to_string(),
-                message: "Failed to join reload thread".to_string(),
-                source: None,
-            })?
-        } else {
-            // No runtime available, use the shared runtime
-            RELOAD_RUNTIME.block_on(async move {
-                let prefix_str = prefix.as_deref().unwrap_or("");
-
-                // Connect fresh to etcd
-                let mut client = Client::connect(&endpoints, None).await.map_err(|e| {
-                    ConfigError::SourceError {
-                        source_name: "etcd".to_string(),
-                        message: format!("Failed to connect to etcd: {}", e),
-                        source: Some(Box::new(e)),
-                    }
-                })?;
-
-                let options = GetOptions::new().with_prefix();
-                let response = client.get(prefix_str, Some(options)).await.map_err(|e| {
-                    ConfigError::SourceError {
-                        source_name: "etcd".to_string(),
-                        message: format!("Failed to fetch keys from etcd: {}", e),
-                        source: Some(Box::new(e)),
-                    }
-                })?;
-
-                let mut new_cache = HashMap::new();
-
-                for kv in response.kvs() {
-                    if let (Ok(key), Ok(value)) = (kv.key_str(), kv.value_str()) {
-                        // Strip prefix from key
-                        let key = if !prefix_str.is_empty() && key.starts_with(prefix_str) {
-                            &key[prefix_str.len()..]
-                        } else {
-                            key
-                        };
-
-                        // Convert slashes to dots for consistency
-                        let key = key.replace('/', ".");
-
-                        new_cache.insert(key, value.to_string());
-                    }
-                }
-
-                Ok::<HashMap<String, String>, ConfigError>(new_cache)
-            })
-        }?;
-
-        self.cache = new_cache;
         Ok(())
     }
 }
@@ -322,14 +668,18 @@ impl ConfigSource for EtcdAdapter {
 
     fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
         Ok(self
-            .cache
+            .state
+            .read()
+            .values
             .get(key.as_str())
             .map(|v| ConfigValue::from(v.as_str())))
     }
 
     fn all_keys(&self) -> Result<Vec<ConfigKey>> {
         Ok(self
-            .cache
+            .state
+            .read()
+            .values
             .keys()
             .map(|k| ConfigKey::from(k.as_str()))
             .collect())
@@ -338,6 +688,92 @@ impl ConfigSource for EtcdAdapter {
     fn reload(&mut self) -> Result<()> {
         self.reload_sync()
     }
+
+    fn as_sink(&mut self) -> Option<&mut dyn ConfigSink> {
+        Some(self)
+    }
+}
+
+impl ConfigSink for EtcdAdapter {
+    fn set(&mut self, key: &ConfigKey, value: &ConfigValue) -> Result<()> {
+        self.apply(&[ConfigChange::Set {
+            key: key.clone(),
+            value: value.clone(),
+        }])
+    }
+
+    fn delete(&mut self, key: &ConfigKey) -> Result<()> {
+        self.apply(&[ConfigChange::Delete { key: key.clone() }])
+    }
+
+    /// Applies all changes in a single etcd transaction.
+    fn apply(&mut self, changes: &[ConfigChange]) -> Result<()> {
+        let operations: Vec<TxnOp> = changes
+            .iter()
+            .map(|change| match change {
+                ConfigChange::Set { key, value } => {
+                    TxnOp::put(self.storage_key(key), value.as_str(), None)
+                }
+                ConfigChange::Delete { key } => TxnOp::delete(self.storage_key(key), None),
+            })
+            .collect();
+
+        let endpoints = &self.endpoints;
+        let options = &self.options;
+        block_on(async move {
+            let mut client = Self::connect(endpoints, options).await?;
+            client
+                .txn(Txn::new().and_then(operations))
+                .await
+                .map_err(|e| ConfigError::SourceError {
+                    source_name: "etcd".to_string(),
+                    message: format!("Failed to write to etcd: {}", e),
+                    source: Some(Box::new(e)),
+                })
+        })?;
+
+        // The revision is left alone: a running watcher may still deliver
+        // earlier events, and the write's own events will be reapplied.
+        let mut state = self.state.write();
+        for change in changes {
+            match change {
+                ConfigChange::Set { key, value } => {
+                    state
+                        .values
+                        .insert(key.as_str().to_string(), value.as_str().to_string());
+                }
+                ConfigChange::Delete { key } => {
+                    state.values.remove(key.as_str());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl AsyncConfigSource for EtcdAdapter {
+    fn name(&self) -> &str {
+        "etcd"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+        let result = ConfigSource::get(self, key);
+        Box::pin(async move { result })
+    }
+
+    fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> {
+        let result = ConfigSource::all_keys(self);
+        Box::pin(async move { result })
+    }
+
+    fn reload(&mut self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(self.load_all_keys())
+    }
 }
 
 // Tests for etcd adapter are in tests/etcd_integration_tests.rs
@@ -0,0 +1,698 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! HTTP(S) configuration source adapter.
+//!
+//! This module provides an adapter that downloads a configuration document from
+//! a URL and parses it with a `ConfigParser` chosen by content type or file
+//! extension, along with the request options and cache it shares with
+//! `HttpPollingWatcher`.
+
+use crate::adapters::runtime::block_on;
+use crate::adapters::shared::Shared;
+use crate::adapters::HttpPollingWatcher;
+use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result};
+use crate::ports::{AsyncConfigSource, BoxFuture, ConfigParser, ConfigSource};
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+    LAST_MODIFIED,
+};
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Maximum size of a downloaded configuration document (10 MB).
+const MAX_DOCUMENT_SIZE: usize = 10 * 1024 * 1024;
+
+/// A parser and the file extensions it is chosen for.
+#[derive(Clone)]
+struct RegisteredParser {
+    /// Extensions, without the dot
+    extensions: Vec<String>,
+    /// The parser
+    parser: Arc<dyn ConfigParser + Send + Sync>,
+}
+
+/// Request options for HTTP configuration sources.
+///
+/// Covers extra headers, bearer authentication, the request timeout and the
+/// parsers documents are read with. The same options are used by
+/// [`HttpAdapter`] and [`HttpPollingWatcher`].
+///
+/// A parser is chosen by the response's `Content-Type` (for example
+/// `application/json` selects a parser for `json`), or else by the extension of
+/// the URL path. With the `yaml` feature, [`YamlParser`](crate::adapters::YamlParser)
+/// handles `yaml`, `yml` and `json`; parsers added with
+/// [`with_parser`](Self::with_parser) take precedence.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::{HttpAdapter, HttpOptions};
+/// use std::time::Duration;
+///
+/// # #[tokio::main]
+/// # async fn main() -> hexcfg::domain::Result<()> {
+/// let options = HttpOptions::new()
+///     .with_bearer_token("b1gs33cr3t")
+///     .with_header("X-Environment", "production")?
+///     .with_timeout(Duration::from_secs(10));
+///
+/// let adapter =
+///     HttpAdapter::with_options("https://config.internal/myapp.yaml", options).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct HttpOptions {
+    /// Extra request headers
+    headers: HeaderMap,
+    /// Token sent as `Authorization: Bearer <token>`
+    bearer_token: Option<String>,
+    /// Timeout for each request
+    timeout: Option<Duration>,
+    /// Parsers in order of precedence
+    parsers: Vec<RegisteredParser>,
+}
+
+impl fmt::Debug for HttpOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Header values may hold credentials, so only their names are shown
+        let headers: Vec<&str> = self.headers.keys().map(|name| name.as_str()).collect();
+        let extensions: Vec<&[String]> = self
+            .parsers
+            .iter()
+            .map(|p| p.extensions.as_slice())
+            .collect();
+        f.debug_struct("HttpOptions")
+            .field("headers", &headers)
+            .field(
+                "bearer_token",
+                &self.bearer_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("timeout", &self.timeout)
+            .field("parsers", &extensions)
+            .finish()
+    }
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpOptions {
+    /// Creates options with no extra headers and the default parsers.
+    pub fn new() -> Self {
+        Self {
+            headers: HeaderMap::new(),
+            bearer_token: None,
+            timeout: None,
+            parsers: Self::default_parsers(),
+        }
+    }
+
+    /// Returns the YAML parser, which also reads JSON documents.
+    #[cfg(feature = "yaml")]
+    fn default_parsers() -> Vec<RegisteredParser> {
+        vec![RegisteredParser {
+            extensions: ["yaml", "yml", "json"].map(String::from).to_vec(),
+            parser: Arc::new(crate::adapters::YamlParser::new()),
+        }]
+    }
+
+    /// Without the `yaml` feature, parsers must be registered explicitly.
+    #[cfg(not(feature = "yaml"))]
+    fn default_parsers() -> Vec<RegisteredParser> {
+        Vec::new()
+    }
+
+    /// Adds a header to every request.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::SourceError` if the name or value is not a valid
+    /// HTTP header.
+    pub fn with_header(mut self, name: &str, value: &str) -> Result<Self> {
+        let invalid = |what: &str| ConfigError::SourceError {
+            source_name: "http".to_string(),
+            message: format!("Invalid HTTP header {}: {}", what, name),
+            source: None,
+        };
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid("name"))?;
+        let value = HeaderValue::from_str(value).map_err(|_| invalid("value"))?;
+        self.headers.append(name, value);
+        Ok(self)
+    }
+
+    /// Authenticates with `Authorization: Bearer <token>`.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Sets the timeout for each request.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Registers a parser for the extensions it supports, ahead of the
+    /// parsers already registered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::HttpOptions;
+    /// use hexcfg::domain::Result;
+    /// use hexcfg::ports::ConfigParser;
+    /// use std::collections::HashMap;
+    ///
+    /// struct PropertiesParser;
+    ///
+    /// impl ConfigParser for PropertiesParser {
+    ///     fn parse(&self, content: &str) -> Result<HashMap<String, String>> {
+    ///         Ok(content
+    ///             .lines()
+    ///             .filter_map(|line| line.split_once('='))
+    ///             .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+    ///             .collect())
+    ///     }
+    ///
+    ///     fn supported_extensions(&self) -> &[&str] {
+    ///         &["properties"]
+    ///     }
+    /// }
+    ///
+    /// let options = HttpOptions::new().with_parser(PropertiesParser);
+    /// ```
+    pub fn with_parser(mut self, parser: impl ConfigParser + Send + Sync + 'static) -> Self {
+        let extensions = parser
+            .supported_extensions()
+            .iter()
+            .map(|ext| ext.to_ascii_lowercase())
+            .collect();
+        self.parsers.insert(
+            0,
+            RegisteredParser {
+                extensions,
+                parser: Arc::new(parser),
+            },
+        );
+        self
+    }
+
+    /// Returns the parser registered for `extension`.
+    fn parser_for(&self, extension: &str) -> Option<&(dyn ConfigParser + Send + Sync)> {
+        self.parsers
+            .iter()
+            .find(|p| p.extensions.iter().any(|ext| ext == extension))
+            .map(|p| p.parser.as_ref())
+    }
+}
+
+/// Maps a `Content-Type` to the file extension of its format.
+///
+/// Types such as `text/plain` or `application/octet-stream` that do not name a
+/// format give `None`, so the URL's extension is used instead.
+fn content_type_extension(content_type: &str) -> Option<String> {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    let subtype = mime.split_once('/')?.1;
+
+    if subtype == "json" || subtype.ends_with("+json") {
+        return Some("json".to_string());
+    }
+    if subtype.contains("yaml") {
+        return Some("yaml".to_string());
+    }
+    match subtype {
+        "plain" | "octet-stream" | "html" => None,
+        subtype => Some(subtype.strip_prefix("x-").unwrap_or(subtype).to_string()),
+    }
+}
+
+/// Returns the extension of the last segment of the URL path.
+fn url_extension(url: &reqwest::Url) -> Option<String> {
+    let segment = url.path_segments()?.next_back()?;
+    let (_, extension) = segment.rsplit_once('.')?;
+    Some(extension.to_ascii_lowercase())
+}
+
+/// The parsed document and the validators of the response it came from.
+///
+/// Shared between an [`HttpAdapter`] and the watchers it creates.
+#[derive(Debug, Default)]
+pub(crate) struct HttpState {
+    /// Parsed configuration values
+    values: HashMap<String, String>,
+    /// `ETag` of the last downloaded document
+    etag: Option<String>,
+    /// `Last-Modified` of the last downloaded document
+    last_modified: Option<String>,
+}
+
+/// Handle to an [`HttpState`] shared between an adapter and its watchers.
+pub(crate) type SharedHttpState = Shared<HttpState>;
+
+/// Downloads and parses the document at one URL.
+#[derive(Debug, Clone)]
+pub(crate) struct HttpClient {
+    /// HTTP client
+    http: reqwest::Client,
+    /// Document URL
+    url: reqwest::Url,
+    /// Request options
+    options: HttpOptions,
+}
+
+impl HttpClient {
+    /// Creates a client for the document at `url`.
+    pub(crate) fn new(url: &str, options: HttpOptions) -> Result<Self> {
+        let url = reqwest::Url::parse(url).map_err(|e| ConfigError::SourceError {
+            source_name: "http".to_string(),
+            message: format!("Invalid URL: {}", e),
+            source: Some(Box::new(e)),
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(ConfigError::SourceError {
+                source_name: "http".to_string(),
+                message: format!("Unsupported URL scheme: {}", url.scheme()),
+                source: None,
+            });
+        }
+
+        let http = reqwest::Client::builder()
+            .build()
+            .map_err(|e| ConfigError::SourceError {
+                source_name: "http".to_string(),
+                message: format!("Failed to create HTTP client: {}", e),
+                source: Some(Box::new(e)),
+            })?;
+
+        Ok(Self { http, url, options })
+    }
+
+    /// Returns the document URL.
+    pub(crate) fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    /// Returns the request options.
+    pub(crate) fn options(&self) -> &HttpOptions {
+        &self.options
+    }
+
+    /// Downloads the document unless it is unchanged, updating `state`.
+    ///
+    /// Sends the validators of the last download, so an unchanged document
+    /// costs a `304 Not Modified`. Returns the keys whose values changed, which
+    /// is empty when the document was not modified or parses to the same values.
+    pub(crate) async fn fetch(&self, state: &SharedHttpState) -> Result<Vec<String>> {
+        let fetch_error = |message: String| ConfigError::SourceError {
+            source_name: "http".to_string(),
+            message,
+            source: None,
+        };
+
+        let mut request = self
+            .http
+            .get(self.url.clone())
+            .headers(self.options.headers.clone());
+        if let Some(token) = &self.options.bearer_token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(timeout) = self.options.timeout {
+            request = request.timeout(timeout);
+        }
+        {
+            let state = state.read();
+            if let Some(etag) = &state.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &state.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = request.send().await.map_err(|e| ConfigError::SourceError {
+            source_name: "http".to_string(),
+            message: format!("Failed to fetch {}: {}", self.url, e),
+            source: Some(Box::new(e)),
+        })?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            return Ok(Vec::new());
+        }
+        if !status.is_success() {
+            return Err(fetch_error(format!("{} returned {}", self.url, status)));
+        }
+        if response
+            .content_length()
+            .is_some_and(|len| len > MAX_DOCUMENT_SIZE as u64)
+        {
+            return Err(fetch_error(format!(
+                "Configuration document too large (max {} bytes)",
+                MAX_DOCUMENT_SIZE
+            )));
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(|value| value.to_string())
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let content_type = header(CONTENT_TYPE);
+
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| ConfigError::SourceError {
+                source_name: "http".to_string(),
+                message: format!("Failed to read response from {}: {}", self.url, e),
+                source: Some(Box::new(e)),
+            })?;
+        if body.len() > MAX_DOCUMENT_SIZE {
+            return Err(fetch_error(format!(
+                "Configuration document too large: {} bytes (max {} bytes)",
+                body.len(),
+                MAX_DOCUMENT_SIZE
+            )));
+        }
+        let body = std::str::from_utf8(&body).map_err(|e| ConfigError::ParseError {
+            message: format!("Configuration document is not UTF-8: {}", e),
+            source: Some(Box::new(e)),
+        })?;
+
+        let values = self.parse(content_type.as_deref(), body)?;
+
+        let mut state = state.write();
+        let mut changed: Vec<String> = values
+            .iter()
+            .filter(|(key, value)| state.values.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .collect();
+        changed.extend(
+            state
+                .values
+                .keys()
+                .filter(|key| !values.contains_key(*key))
+                .cloned(),
+        );
+
+        *state = HttpState {
+            values,
+            etag,
+            last_modified,
+        };
+        Ok(changed)
+    }
+
+    /// Parses a document with the parser for its content type or URL extension.
+    fn parse(&self, content_type: Option<&str>, body: &str) -> Result<HashMap<String, String>> {
+        let candidates = [
+            content_type.and_then(content_type_extension),
+            url_extension(&self.url),
+        ];
+        let parser = candidates
+            .iter()
+            .flatten()
+            .find_map(|ext| self.options.parser_for(ext))
+            .ok_or_else(|| ConfigError::ParseError {
+                message: format!(
+                    "No parser for {} (content type {})",
+                    self.url,
+                    content_type.unwrap_or("unknown")
+                ),
+                source: None,
+            })?;
+
+        parser.parse(body)
+    }
+}
+
+/// Configuration source adapter for documents served over HTTP(S).
+///
+/// The document is downloaded when the adapter is created and on every reload,
+/// and parsed into flat keys like a configuration file.
+///
+/// # Caching
+///
+/// The adapter keeps the `ETag` and `Last-Modified` validators of the last
+/// download and sends them as `If-None-Match` and `If-Modified-Since`, so a
+/// reload of an unchanged document costs a `304 Not Modified` and no parsing.
+///
+/// # Priority
+///
+/// HTTP sources have a default priority of 1, but this can be customized.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::HttpAdapter;
+/// use hexcfg::ports::ConfigSource;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let adapter = HttpAdapter::new("https://config.internal/myapp.yaml").await?;
+/// let host = adapter.get_str("database.host")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct HttpAdapter {
+    /// HTTP client
+    client: HttpClient,
+    /// Priority for this source
+    priority: u8,
+    /// Parsed values and the validators of their download
+    state: SharedHttpState,
+}
+
+impl HttpAdapter {
+    /// Creates a new HTTP adapter for the document at `url`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::HttpAdapter;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let adapter = HttpAdapter::new("https://config.internal/myapp.json").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn new(url: &str) -> Result<Self> {
+        Self::with_options(url, HttpOptions::new()).await
+    }
+
+    /// Creates a new HTTP adapter with custom headers, authentication or parsers.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Document URL
+    /// * `options` - Request options, also used by watchers
+    pub async fn with_options(url: &str, options: HttpOptions) -> Result<Self> {
+        let mut adapter = Self {
+            client: HttpClient::new(url, options)?,
+            priority: 1,
+            state: SharedHttpState::default(),
+        };
+
+        // Initial download
+        adapter.load().await?;
+
+        Ok(adapter)
+    }
+
+    /// Creates a new HTTP adapter with a custom priority.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Document URL
+    /// * `priority` - Priority for this source (higher values override lower values)
+    pub async fn with_priority(url: &str, priority: u8) -> Result<Self> {
+        let mut adapter = Self::new(url).await?;
+        adapter.priority = priority;
+        Ok(adapter)
+    }
+
+    /// Creates a watcher that polls the document every `interval` and keeps
+    /// this adapter's values up to date.
+    ///
+    /// The watcher starts from the adapter's validators, so polls of an
+    /// unchanged document are answered with `304 Not Modified`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::HttpAdapter;
+    /// use hexcfg::ports::ConfigWatcher;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let adapter = HttpAdapter::new("https://config.internal/myapp.yaml").await?;
+    ///
+    /// let mut watcher = adapter.watcher(Duration::from_secs(30));
+    /// watcher.watch(Arc::new(|key| {
+    ///     println!("Configuration changed: {}", key);
+    /// }))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watcher(&self, interval: Duration) -> HttpPollingWatcher {
+        HttpPollingWatcher::with_state(self.client.clone(), interval, self.state.clone())
+    }
+
+    /// Downloads the document if it changed.
+    async fn load(&mut self) -> Result<()> {
+        self.client.fetch(&self.state).await?;
+        Ok(())
+    }
+
+    /// Downloads the document synchronously.
+    ///
+    /// Pooled connections belong to the runtime that opened them, so a fresh
+    /// client is used on the shared blocking runtime.
+    fn reload_sync(&mut self) -> Result<()> {
+        let client = HttpClient::new(self.client.url(), self.client.options().clone())?;
+        let state = &self.state;
+
+        block_on(async move { client.fetch(state).await })?;
+
+        Ok(())
+    }
+}
+
+impl ConfigSource for HttpAdapter {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        Ok(self
+            .state
+            .read()
+            .values
+            .get(key.as_str())
+            .map(|v| ConfigValue::from(v.as_str())))
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        Ok(self
+            .state
+            .read()
+            .values
+            .keys()
+            .map(|k| ConfigKey::from(k.as_str()))
+            .collect())
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.reload_sync()
+    }
+}
+
+impl AsyncConfigSource for HttpAdapter {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+        let result = ConfigSource::get(self, key);
+        Box::pin(async move { result })
+    }
+
+    fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> {
+        let result = ConfigSource::all_keys(self);
+        Box::pin(async move { result })
+    }
+
+    fn reload(&mut self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(self.load())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_type_extension() {
+        assert_eq!(
+            content_type_extension("application/json; charset=utf-8").as_deref(),
+            Some("json")
+        );
+        assert_eq!(
+            content_type_extension("application/vnd.myapp+json").as_deref(),
+            Some("json")
+        );
+        assert_eq!(
+            content_type_extension("application/x-yaml").as_deref(),
+            Some("yaml")
+        );
+        assert_eq!(
+            content_type_extension("application/toml").as_deref(),
+            Some("toml")
+        );
+        assert_eq!(content_type_extension("text/plain"), None);
+        assert_eq!(content_type_extension("garbage"), None);
+    }
+
+    #[test]
+    fn test_url_extension() {
+        let url = reqwest::Url::parse("https://config.internal/apps/myapp.YAML?v=2").unwrap();
+        assert_eq!(url_extension(&url).as_deref(), Some("yaml"));
+
+        let url = reqwest::Url::parse("https://config.internal/apps/myapp").unwrap();
+        assert_eq!(url_extension(&url), None);
+    }
+
+    #[test]
+    fn test_client_rejects_other_schemes() {
+        assert!(HttpClient::new("file:///etc/myapp.yaml", HttpOptions::new()).is_err());
+        assert!(HttpClient::new("not a url", HttpOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_options_debug_redacts_credentials() {
+        let options = HttpOptions::new()
+            .with_bearer_token("b1gs33cr3t")
+            .with_header("X-Api-Key", "k3y")
+            .unwrap();
+        let debug = format!("{:?}", options);
+        assert!(!debug.contains("b1gs33cr3t"));
+        assert!(!debug.contains("k3y"));
+        assert!(debug.contains("x-api-key"));
+    }
+
+    #[test]
+    fn test_options_invalid_header() {
+        assert!(HttpOptions::new().with_header("bad header", "v").is_err());
+        assert!(HttpOptions::new()
+            .with_header("X-Ok", "bad\nvalue")
+            .is_err());
+    }
+}
@@ -0,0 +1,459 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Key-per-file configuration source adapter.
+//!
+//! This module provides an adapter that reads a directory in which every file
+//! holds one configuration value, as produced by Kubernetes ConfigMap and
+//! Secret volumes, Docker secrets (`/run/secrets`) and systemd credentials
+//! (`$CREDENTIALS_DIRECTORY`).
+
+use crate::adapters::shared::Shared;
+use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result};
+use crate::ports::ConfigSource;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "reload")]
+use crate::adapters::watchers::KeyPerFileWatcher;
+#[cfg(feature = "reload")]
+use std::time::Duration;
+
+/// Maximum allowed size of a single value file (10MB)
+/// This prevents denial of service attacks via extremely large files
+const MAX_VALUE_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Values read from a key-per-file directory.
+#[derive(Debug, Default)]
+pub(crate) struct KeyPerFileState {
+    /// Configuration values, keyed by configuration key
+    values: HashMap<String, String>,
+}
+
+/// Handle to a [`KeyPerFileState`] shared between an adapter and its watchers.
+pub(crate) type SharedKeyPerFileState = Shared<KeyPerFileState>;
+
+impl KeyPerFileState {
+    /// Replaces the values with a new scan, returning the keys whose values
+    /// changed.
+    pub(crate) fn replace(&mut self, values: HashMap<String, String>) -> Vec<String> {
+        let mut changed: Vec<String> = values
+            .iter()
+            .filter(|(key, value)| self.values.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .collect();
+        changed.extend(
+            self.values
+                .keys()
+                .filter(|key| !values.contains_key(*key))
+                .cloned(),
+        );
+
+        self.values = values;
+        changed
+    }
+}
+
+/// Reads every value file in `dir`.
+///
+/// Entries whose names start with `..` are skipped, since they are the
+/// timestamped data directory and the `..data` symlink Kubernetes uses to swap
+/// all files at once. Symlinks are followed, so the visible files resolve
+/// through `..data` to the current version. Subdirectories become dotted key
+/// prefixes when `nested` is set and are skipped otherwise; a directory reached
+/// again through a symlink is read only once. Each file name is one key
+/// segment, so dots in it are escaped. Trailing newlines are trimmed from
+/// values, and files that are not UTF-8 are skipped.
+pub(crate) fn scan(dir: &Path, nested: bool) -> Result<HashMap<String, String>> {
+    let mut values = HashMap::new();
+    let mut visited = HashSet::new();
+    scan_into(dir, &ConfigKey::from(""), nested, &mut visited, &mut values)?;
+    Ok(values)
+}
+
+fn scan_into(
+    dir: &Path,
+    prefix: &ConfigKey,
+    nested: bool,
+    visited: &mut HashSet<PathBuf>,
+    values: &mut HashMap<String, String>,
+) -> Result<()> {
+    let read_error = |e: std::io::Error| ConfigError::SourceError {
+        source_name: "key-per-file".to_string(),
+        message: format!("Failed to read directory: {}", dir.display()),
+        source: Some(Box::new(e)),
+    };
+
+    if !visited.insert(fs::canonicalize(dir).map_err(read_error)?) {
+        tracing::debug!("Skipping directory already read: {}", dir.display());
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir).map_err(read_error)? {
+        let entry = entry.map_err(read_error)?;
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            tracing::debug!("Skipping non UTF-8 file name in {}", dir.display());
+            continue;
+        };
+        if name.starts_with("..") {
+            continue;
+        }
+
+        let path = entry.path();
+        // Follow symlinks; a dangling link is skipped rather than failing the scan
+        let Ok(metadata) = fs::metadata(&path) else {
+            tracing::debug!("Skipping unreadable entry: {}", path.display());
+            continue;
+        };
+        let key = prefix.child(name.as_str());
+
+        if metadata.is_dir() {
+            if nested {
+                scan_into(&path, &key, nested, visited, values)?;
+            }
+            continue;
+        }
+        let key = key.into_string();
+
+        if metadata.len() > MAX_VALUE_FILE_SIZE {
+            return Err(ConfigError::SourceError {
+                source_name: "key-per-file".to_string(),
+                message: format!(
+                    "Value file too large: {} bytes (max {} bytes)",
+                    metadata.len(),
+                    MAX_VALUE_FILE_SIZE
+                ),
+                source: None,
+            });
+        }
+
+        let content = fs::read(&path).map_err(|e| ConfigError::SourceError {
+            source_name: "key-per-file".to_string(),
+            message: format!("Failed to read value file for key '{}'", key),
+            source: Some(Box::new(e)),
+        })?;
+        match String::from_utf8(content) {
+            Ok(value) => {
+                values.insert(key, value.trim_end_matches(['\n', '\r']).to_string());
+            }
+            Err(_) => {
+                tracing::debug!("Skipping value file that is not UTF-8: {}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Configuration source adapter for directories with one file per key.
+///
+/// Every file in the directory is a configuration key named after the file,
+/// holding the file's contents with trailing newlines trimmed. This is the
+/// layout of:
+///
+/// - Kubernetes ConfigMaps and Secrets mounted as volumes
+/// - Docker secrets in `/run/secrets`
+/// - systemd credentials in `$CREDENTIALS_DIRECTORY`
+///
+/// Kubernetes updates a mounted volume by writing a new timestamped directory
+/// and atomically swapping the `..data` symlink to it. These `..`-prefixed
+/// entries are ignored, and the files are read through their symlinks.
+///
+/// With [`from_dir_nested`](Self::from_dir_nested), subdirectories become
+/// dotted prefixes, so `dir/database/host` is read as `database.host`.
+///
+/// # Priority
+///
+/// Like configuration files, the adapter has a default priority of 1. Use
+/// [`with_priority`](Self::with_priority) to let mounted secrets override
+/// other sources.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::KeyPerFileAdapter;
+/// use hexcfg::ports::ConfigSource;
+///
+/// // Kubernetes ConfigMap mounted at /etc/myapp
+/// let adapter = KeyPerFileAdapter::from_dir("/etc/myapp").unwrap();
+///
+/// // Docker secrets, overriding environment variables
+/// let secrets = KeyPerFileAdapter::from_dir("/run/secrets")
+///     .unwrap()
+///     .with_priority(3);
+/// ```
+#[derive(Debug)]
+pub struct KeyPerFileAdapter {
+    /// Directory holding the value files
+    dir: PathBuf,
+    /// Whether subdirectories are read as dotted prefixes
+    nested: bool,
+    /// Priority of this source
+    priority: u8,
+    /// Values read from the directory, shared with watchers
+    state: SharedKeyPerFileState,
+}
+
+impl KeyPerFileAdapter {
+    /// Creates a new key-per-file adapter reading the files directly in `dir`.
+    ///
+    /// Subdirectories are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory holding one file per key
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::KeyPerFileAdapter;
+    ///
+    /// let adapter = KeyPerFileAdapter::from_dir("/run/secrets").unwrap();
+    /// ```
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        Self::open(dir.as_ref(), false)
+    }
+
+    /// Creates a new key-per-file adapter that also reads subdirectories,
+    /// using their names as dotted key prefixes.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory holding one file per key
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::KeyPerFileAdapter;
+    ///
+    /// // /etc/myapp/database/host is read as database.host
+    /// let adapter = KeyPerFileAdapter::from_dir_nested("/etc/myapp").unwrap();
+    /// ```
+    pub fn from_dir_nested<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        Self::open(dir.as_ref(), true)
+    }
+
+    fn open(dir: &Path, nested: bool) -> Result<Self> {
+        if !dir.is_dir() {
+            return Err(ConfigError::SourceError {
+                source_name: "key-per-file".to_string(),
+                message: format!("Not a directory: {}", dir.display()),
+                source: None,
+            });
+        }
+
+        let state = SharedKeyPerFileState::default();
+        state.write().replace(scan(dir, nested)?);
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            nested,
+            priority: 1,
+            state,
+        })
+    }
+
+    /// Sets the priority of this source (default 1).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::KeyPerFileAdapter;
+    ///
+    /// let adapter = KeyPerFileAdapter::from_dir("/run/secrets")
+    ///     .unwrap()
+    ///     .with_priority(3);
+    /// ```
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns the directory this adapter reads.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Creates a watcher that keeps this adapter's values up to date.
+    ///
+    /// The watcher rescans the directory after changes settle for
+    /// `debounce_delay` (default 500ms) and calls the callback for every key
+    /// whose value changed. Unlike [`FileWatcher`](crate::adapters::FileWatcher),
+    /// it notices the `..data` symlink swap Kubernetes uses to update volumes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::KeyPerFileAdapter;
+    /// use hexcfg::ports::ConfigWatcher;
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let adapter = KeyPerFileAdapter::from_dir("/etc/myapp")?;
+    /// let mut watcher = adapter.watcher(None);
+    ///
+    /// watcher.watch(Arc::new(|key| {
+    ///     println!("Configuration changed: {}", key);
+    /// }))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "reload")]
+    pub fn watcher(&self, debounce_delay: Option<Duration>) -> KeyPerFileWatcher {
+        KeyPerFileWatcher::with_state(
+            self.dir.clone(),
+            self.nested,
+            debounce_delay.unwrap_or(Duration::from_millis(500)),
+            self.state.clone(),
+        )
+    }
+}
+
+impl ConfigSource for KeyPerFileAdapter {
+    fn name(&self) -> &str {
+        "key-per-file"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        Ok(self
+            .state
+            .read()
+            .values
+            .get(key.as_str())
+            .map(|v| ConfigValue::from(v.as_str())))
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        Ok(self
+            .state
+            .read()
+            .values
+            .keys()
+            .map(|k| ConfigKey::from(k.as_str()))
+            .collect())
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        let values = scan(&self.dir, self.nested)?;
+        self.state.write().replace(values);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::KeySegment;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_trims_trailing_newlines() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("host"), "localhost\n").unwrap();
+        fs::write(dir.path().join("motd"), "line one\nline two\r\n\n").unwrap();
+        fs::write(dir.path().join("empty"), "").unwrap();
+
+        let values = scan(dir.path(), false).unwrap();
+        assert_eq!(values.get("host").unwrap(), "localhost");
+        assert_eq!(values.get("motd").unwrap(), "line one\nline two");
+        assert_eq!(values.get("empty").unwrap(), "");
+    }
+
+    #[test]
+    fn test_scan_skips_internal_entries() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("..2026_10_18_12_00_00.123")).unwrap();
+        fs::write(dir.path().join("..data_tmp"), "tmp").unwrap();
+        fs::write(dir.path().join("key"), "value").unwrap();
+
+        let values = scan(dir.path(), true).unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values.get("key").unwrap(), "value");
+    }
+
+    #[test]
+    fn test_scan_nested_directories() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("database/replica")).unwrap();
+        fs::write(dir.path().join("database/host"), "localhost").unwrap();
+        fs::write(dir.path().join("database/replica/host"), "replica").unwrap();
+        fs::write(dir.path().join("name"), "myapp").unwrap();
+
+        let flat = scan(dir.path(), false).unwrap();
+        assert_eq!(flat.len(), 1);
+
+        let nested = scan(dir.path(), true).unwrap();
+        assert_eq!(nested.get("database.host").unwrap(), "localhost");
+        assert_eq!(nested.get("database.replica.host").unwrap(), "replica");
+        assert_eq!(nested.get("name").unwrap(), "myapp");
+    }
+
+    #[test]
+    fn test_scan_escapes_dots_in_file_names() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("database")).unwrap();
+        fs::write(dir.path().join("database/host"), "nested").unwrap();
+        fs::write(dir.path().join("database.host"), "dotted").unwrap();
+        fs::write(dir.path().join("tls.crt"), "cert").unwrap();
+
+        let values = scan(dir.path(), true).unwrap();
+        assert_eq!(values.get("database.host").unwrap(), "nested");
+        assert_eq!(values.get(r"database\.host").unwrap(), "dotted");
+        assert_eq!(
+            ConfigKey::from(r"tls\.crt").segments(),
+            vec![KeySegment::from("tls.crt")]
+        );
+        assert_eq!(values.get(r"tls\.crt").unwrap(), "cert");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_reads_symlink_loop_once() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("database")).unwrap();
+        fs::write(dir.path().join("database/host"), "localhost").unwrap();
+        std::os::unix::fs::symlink("..", dir.path().join("database/parent")).unwrap();
+
+        let values = scan(dir.path(), true).unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values.get("database.host").unwrap(), "localhost");
+    }
+
+    #[test]
+    fn test_scan_skips_binary_files() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("keystore"), [0xff, 0xfe, 0x00]).unwrap();
+        fs::write(dir.path().join("key"), "value").unwrap();
+
+        let values = scan(dir.path(), false).unwrap();
+        assert_eq!(values.len(), 1);
+    }
+
+    #[test]
+    fn test_state_replace_reports_changes() {
+        let mut state = KeyPerFileState::default();
+        let first = HashMap::from([
+            ("a".to_string(), "1".to_string()),
+            ("b".to_string(), "2".to_string()),
+        ]);
+        assert_eq!(state.replace(first.clone()).len(), 2);
+        assert!(state.replace(first).is_empty());
+
+        let second = HashMap::from([
+            ("a".to_string(), "1".to_string()),
+            ("c".to_string(), "3".to_string()),
+        ]);
+        let mut changed = state.replace(second);
+        changed.sort();
+        assert_eq!(changed, vec!["b".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn test_adapter_rejects_missing_directory() {
+        assert!(KeyPerFileAdapter::from_dir("/nonexistent/hexcfg/secrets").is_err());
+    }
+}
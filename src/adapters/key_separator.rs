@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Key separator translation for remote adapters.
+//!
+//! Remote stores use their own separators in key names (`/` in etcd, `:` in
+//! Redis), while configuration keys use `.`. This module provides
+//! `KeySeparator`, which translates between the two in both directions so that
+//! remote keys line up with YAML and environment variable keys.
+
+use crate::domain::{ConfigKey, KeySegment};
+
+/// Translation between the separator in stored keys and configuration keys.
+///
+/// The translation is applied when loading, to keys reported by watchers, and
+/// when writing, so a key read from the store is written back to the same place.
+/// A `.` inside a stored key is escaped, so it stays part of its segment and is
+/// written back as a `.`.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::KeySeparator;
+///
+/// let separator = KeySeparator::translate(":");
+/// assert_eq!(separator.to_config_key("database:host"), "database.host");
+/// assert_eq!(separator.to_storage_key("database.host"), "database:host");
+///
+/// // A dot in a stored key stays within its segment
+/// assert_eq!(separator.to_config_key("certs:example.com"), r"certs.example\.com");
+/// assert_eq!(separator.to_storage_key(r"certs.example\.com"), "certs:example.com");
+///
+/// let raw = KeySeparator::None;
+/// assert_eq!(raw.to_config_key("database:host"), "database:host");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum KeySeparator {
+    /// Keys are used exactly as stored.
+    #[default]
+    None,
+    /// The separator in stored keys becomes `.` in configuration keys.
+    Translate(String),
+}
+
+impl KeySeparator {
+    /// Translates `separator` in stored keys to `.`.
+    pub fn translate(separator: impl Into<String>) -> Self {
+        KeySeparator::Translate(separator.into())
+    }
+
+    /// Converts a stored key (without any prefix) to a configuration key.
+    pub fn to_config_key(&self, stored: &str) -> String {
+        match self {
+            KeySeparator::Translate(separator) if !separator.is_empty() => {
+                ConfigKey::from_segments(stored.split(separator.as_str()).map(KeySegment::from))
+                    .into_string()
+            }
+            _ => stored.to_string(),
+        }
+    }
+
+    /// Converts a configuration key to the stored key (without any prefix).
+    pub fn to_storage_key(&self, key: &str) -> String {
+        match self {
+            KeySeparator::Translate(separator) if !separator.is_empty() => ConfigKey::from(key)
+                .segments()
+                .iter()
+                .map(KeySegment::as_unescaped)
+                .collect::<Vec<_>>()
+                .join(separator),
+            _ => key.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_round_trip() {
+        let separator = KeySeparator::translate("/");
+        assert_eq!(separator.to_config_key("app/db/host"), "app.db.host");
+        assert_eq!(separator.to_storage_key("app.db.host"), "app/db/host");
+    }
+
+    #[test]
+    fn test_dotted_segment_round_trip() {
+        let separator = KeySeparator::translate("/");
+        let key = separator.to_config_key("certs/example.com/cert");
+        assert_eq!(key, r"certs.example\.com.cert");
+        assert_eq!(
+            ConfigKey::from(key.as_str()).segments()[1],
+            KeySegment::from("example.com")
+        );
+        assert_eq!(separator.to_storage_key(&key), "certs/example.com/cert");
+    }
+
+    #[test]
+    fn test_multi_character_separator() {
+        let separator = KeySeparator::translate("::");
+        assert_eq!(separator.to_config_key("db::host"), "db.host");
+        assert_eq!(separator.to_storage_key("db.host"), "db::host");
+    }
+
+    #[test]
+    fn test_none_and_empty_keep_keys() {
+        for separator in [KeySeparator::None, KeySeparator::translate("")] {
+            assert_eq!(separator.to_config_key("db:host"), "db:host");
+            assert_eq!(separator.to_storage_key("db.host"), "db.host");
+        }
+    }
+}
@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! In-memory configuration source adapter.
+//!
+//! This module provides a writable adapter that keeps configuration values in
+//! memory. It is useful for runtime overrides and for tests.
+
+use crate::domain::{ConfigKey, ConfigValue, Result};
+use crate::ports::{ConfigSink, ConfigSource};
+use std::collections::HashMap;
+
+/// Configuration source adapter backed by an in-memory map.
+///
+/// Values written through [`ConfigSource::as_sink`] are visible immediately.
+/// Reloading is a no-op.
+///
+/// # Priority
+///
+/// The in-memory source has a default priority of 4, above command-line
+/// arguments, so that runtime overrides win. This can be customized.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::InMemoryAdapter;
+/// use hexcfg::domain::ConfigurationService;
+/// use hexcfg::service::DefaultConfigService;
+///
+/// let mut service = DefaultConfigService::new();
+/// service.add_source(Box::new(InMemoryAdapter::new()));
+///
+/// service.set("feature.enabled", "true", "memory").unwrap();
+/// assert_eq!(service.get_str("feature.enabled").unwrap().as_str(), "true");
+/// ```
+#[derive(Debug, Clone)]
+pub struct InMemoryAdapter {
+    /// Configuration values, keeping whether each is secret
+    values: HashMap<String, ConfigValue>,
+    /// Priority for this source
+    priority: u8,
+}
+
+impl InMemoryAdapter {
+    /// Creates an empty in-memory adapter with the default priority.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::InMemoryAdapter;
+    ///
+    /// let adapter = InMemoryAdapter::new();
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+            priority: 4,
+        }
+    }
+
+    /// Creates an in-memory adapter with initial values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::InMemoryAdapter;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut values = HashMap::new();
+    /// values.insert("app.name".to_string(), "demo".to_string());
+    ///
+    /// let adapter = InMemoryAdapter::with_values(values);
+    /// ```
+    pub fn with_values(values: HashMap<String, String>) -> Self {
+        Self {
+            values: values
+                .into_iter()
+                .map(|(key, value)| (key, ConfigValue::from(value)))
+                .collect(),
+            ..Self::new()
+        }
+    }
+
+    /// Sets the priority of this source.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::InMemoryAdapter;
+    /// use hexcfg::ports::ConfigSource;
+    ///
+    /// let adapter = InMemoryAdapter::new().with_priority(0);
+    /// assert_eq!(adapter.priority(), 0);
+    /// ```
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+impl Default for InMemoryAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigSource for InMemoryAdapter {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        Ok(self.values.get(key.as_str()).cloned())
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        Ok(self
+            .values
+            .keys()
+            .map(|k| ConfigKey::from(k.as_str()))
+            .collect())
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn as_sink(&mut self) -> Option<&mut dyn ConfigSink> {
+        Some(self)
+    }
+}
+
+impl ConfigSink for InMemoryAdapter {
+    fn set(&mut self, key: &ConfigKey, value: &ConfigValue) -> Result<()> {
+        self.values.insert(key.as_str().to_string(), value.clone());
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &ConfigKey) -> Result<()> {
+        self.values.remove(key.as_str());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::ConfigChange;
+
+    #[test]
+    fn test_memory_adapter_name_and_priority() {
+        let adapter = InMemoryAdapter::new();
+        assert_eq!(adapter.name(), "memory");
+        assert_eq!(adapter.priority(), 4);
+        assert_eq!(adapter.with_priority(7).priority(), 7);
+        assert_eq!(InMemoryAdapter::default().priority(), 4);
+    }
+
+    #[test]
+    fn test_memory_adapter_with_values() {
+        let mut values = HashMap::new();
+        values.insert("key".to_string(), "value".to_string());
+
+        let adapter = InMemoryAdapter::with_values(values);
+        assert_eq!(adapter.get_str("key").unwrap().unwrap().as_str(), "value");
+        assert_eq!(adapter.all_keys().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_memory_adapter_set_and_delete() {
+        let mut adapter = InMemoryAdapter::new();
+        let key = ConfigKey::from("key");
+
+        let sink = adapter.as_sink().unwrap();
+        sink.set(&key, &ConfigValue::from("value")).unwrap();
+        assert_eq!(adapter.get(&key).unwrap().unwrap().as_str(), "value");
+
+        adapter.as_sink().unwrap().delete(&key).unwrap();
+        assert!(adapter.get(&key).unwrap().is_none());
+
+        // Deleting a missing key succeeds
+        assert!(adapter.as_sink().unwrap().delete(&key).is_ok());
+    }
+
+    #[test]
+    fn test_memory_adapter_keeps_secret_values() {
+        let mut adapter = InMemoryAdapter::new();
+        let key = ConfigKey::from("db.password");
+
+        let sink = adapter.as_sink().unwrap();
+        sink.set(&key, &ConfigValue::secret("hunter2")).unwrap();
+
+        let value = adapter.get(&key).unwrap().unwrap();
+        assert!(value.is_secret());
+        assert_eq!(value.as_str(), "hunter2");
+    }
+
+    #[test]
+    fn test_memory_adapter_apply() {
+        let mut adapter = InMemoryAdapter::new();
+        adapter
+            .as_sink()
+            .unwrap()
+            .apply(&[ConfigChange::set("a", "1"), ConfigChange::set("b", "2")])
+            .unwrap();
+
+        assert_eq!(adapter.all_keys().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_memory_adapter_reload_keeps_values() {
+        let mut adapter = InMemoryAdapter::new();
+        adapter
+            .as_sink()
+            .unwrap()
+            .set(&ConfigKey::from("key"), &ConfigValue::from("value"))
+            .unwrap();
+
+        adapter.reload().unwrap();
+        assert!(adapter.get_str("key").unwrap().is_some());
+    }
+}
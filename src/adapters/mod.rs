@@ -6,35 +6,92 @@
 //! traits defined in the ports layer. Each adapter implements the `ConfigSource`
 //! trait to provide configuration from a specific source.
 
+#[cfg(feature = "async")]
+pub mod async_bridge;
 #[cfg(feature = "cli")]
 pub mod cli;
+pub mod composition;
+#[cfg(feature = "consul")]
+pub mod consul;
+pub mod deferred;
+#[cfg(feature = "encryption")]
+pub mod encryption;
 #[cfg(feature = "env")]
 pub mod env_var;
 #[cfg(feature = "etcd")]
 pub mod etcd;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "key-per-file")]
+pub mod key_per_file;
+#[cfg(any(feature = "etcd", feature = "redis", feature = "consul"))]
+pub mod key_separator;
+pub mod memory;
+pub mod prioritized;
 #[cfg(feature = "redis")]
 pub mod redis;
+#[cfg(feature = "async")]
+pub(crate) mod runtime;
+#[cfg(feature = "secrets")]
+pub mod secrets;
+pub(crate) mod shared;
+#[cfg(feature = "vault")]
+pub mod vault;
+#[cfg(feature = "yaml")]
+pub(crate) mod yaml_editor;
 #[cfg(feature = "yaml")]
 pub mod yaml_file;
 
 pub mod watchers;
 
 // Re-export adapters based on feature flags
+#[cfg(feature = "async")]
+pub use async_bridge::{BlockingSourceAdapter, SyncSourceAdapter};
 #[cfg(feature = "cli")]
-pub use cli::CommandLineAdapter;
+pub use cli::{CliSchema, CommandLineAdapter};
+pub use composition::{Filtered, Mapped, Mounted};
+#[cfg(feature = "consul")]
+pub use consul::{ConsulAdapter, ConsulConnectOptions};
+pub use deferred::{DeferredSource, SourcePolicy};
+#[cfg(feature = "encryption")]
+pub use encryption::{AesGcmDecryptor, AgeDecryptor, DecryptingSource};
 #[cfg(feature = "env")]
 pub use env_var::EnvVarAdapter;
 #[cfg(feature = "etcd")]
-pub use etcd::EtcdAdapter;
+pub use etcd::{EtcdAdapter, EtcdConnectOptions};
+#[cfg(feature = "http")]
+pub use http::{HttpAdapter, HttpOptions};
+#[cfg(feature = "key-per-file")]
+pub use key_per_file::KeyPerFileAdapter;
+#[cfg(any(feature = "etcd", feature = "redis", feature = "consul"))]
+pub use key_separator::KeySeparator;
+pub use memory::InMemoryAdapter;
+pub use prioritized::Prioritized;
 #[cfg(feature = "redis")]
-pub use redis::{RedisAdapter, RedisStorageMode};
+pub use redis::{RedisAdapter, RedisConnectOptions, RedisStorageMode, RedisTopology};
+#[cfg(feature = "secrets")]
+pub use secrets::{FileSecretResolver, ResolvingSource};
+#[cfg(feature = "vault")]
+pub use vault::{VaultOptions, VaultResolver};
 #[cfg(feature = "reload")]
 pub use watchers::FileWatcher;
 
+#[cfg(feature = "consul")]
+pub use watchers::ConsulWatcher;
+
 #[cfg(feature = "etcd")]
 pub use watchers::EtcdWatcher;
 
+#[cfg(feature = "http")]
+pub use watchers::HttpPollingWatcher;
+
+#[cfg(all(feature = "key-per-file", feature = "reload"))]
+pub use watchers::KeyPerFileWatcher;
+
 #[cfg(feature = "redis")]
 pub use watchers::RedisWatcher;
+
+#[cfg(feature = "secrets")]
+pub use watchers::SecretLeaseWatcher;
 #[cfg(feature = "yaml")]
 pub use yaml_file::{YamlFileAdapter, YamlParser};
@@ -0,0 +1,113 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Priority override for configuration sources.
+//!
+//! This module provides a source wrapper that replaces the priority of any
+//! source, for sources that cannot set one themselves.
+
+use crate::domain::{ConfigKey, ConfigValue, Result};
+use crate::ports::{ConfigSink, ConfigSource, SourceHealth};
+
+/// Source wrapper that overrides the priority of another source.
+///
+/// Everything except the priority is passed through to the wrapped source,
+/// including its name and write interface. Built-in adapters also offer
+/// `with_priority` directly; the wrapper covers sources that do not, such as
+/// [`DecryptingSource`](crate::adapters::DecryptingSource) or sources from
+/// other crates.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::{InMemoryAdapter, Prioritized};
+/// use hexcfg::ports::ConfigSource;
+///
+/// let source = Prioritized::new(InMemoryAdapter::new(), 10);
+/// assert_eq!(source.name(), "memory");
+/// assert_eq!(source.priority(), 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Prioritized<S> {
+    /// The wrapped source
+    inner: S,
+    /// Priority reported instead of the wrapped source's
+    priority: u8,
+}
+
+impl<S: ConfigSource> Prioritized<S> {
+    /// Wraps `inner` so that it reports `priority`.
+    pub fn new(inner: S, priority: u8) -> Self {
+        Self { inner, priority }
+    }
+
+    /// Returns the wrapped source.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Unwraps the wrapped source.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: ConfigSource> ConfigSource for Prioritized<S> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        self.inner.get(key)
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        self.inner.all_keys()
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.inner.reload()
+    }
+
+    fn as_sink(&mut self) -> Option<&mut dyn ConfigSink> {
+        self.inner.as_sink()
+    }
+
+    fn health(&self) -> SourceHealth {
+        self.inner.health()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemoryAdapter;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_prioritized_overrides_only_priority() {
+        let inner = InMemoryAdapter::with_values(HashMap::from([(
+            "app.name".to_string(),
+            "demo".to_string(),
+        )]));
+        let mut source: Box<dyn ConfigSource> = Box::new(Prioritized::new(inner, 9));
+
+        assert_eq!(source.name(), "memory");
+        assert_eq!(source.priority(), 9);
+        assert_eq!(
+            source.get_str("app.name").unwrap().unwrap().as_str(),
+            "demo"
+        );
+
+        // Writes reach the wrapped source
+        source
+            .as_sink()
+            .unwrap()
+            .set(&ConfigKey::from("app.port"), &ConfigValue::from("80"))
+            .unwrap();
+        assert_eq!(source.all_keys().unwrap().len(), 2);
+    }
+}
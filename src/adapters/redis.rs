@@ -2,20 +2,711 @@
 
 //! Redis configuration source adapter.
 //!
-//! This module provides an adapter that reads configuration values from Redis.
+//! This module provides an adapter that reads configuration values from Redis,
+//! and the connection options and cache it shares with `RedisWatcher`.
 
-use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result};
-use crate::ports::ConfigSource;
-use once_cell::sync::Lazy;
-use redis::aio::MultiplexedConnection;
-use redis::{AsyncCommands, Client};
+use crate::adapters::runtime::block_on;
+use crate::adapters::shared::Shared;
+use crate::adapters::{KeySeparator, RedisWatcher};
+use crate::domain::{ConfigError, ConfigKey, ConfigValue, KeySegment, Result};
+use crate::ports::{
+    AsyncConfigSource, BoxFuture, ConfigChange, ConfigParser, ConfigSink, ConfigSource,
+};
+use redis::aio::{ConnectionLike, MultiplexedConnection};
+use redis::cluster::ClusterClient;
+use redis::cluster_async::ClusterConnection;
+use redis::sentinel::{Sentinel, SentinelNodeConnectionInfo};
+use redis::{
+    AsyncCommands, Client, Cmd, ConnectionAddr, ConnectionInfo, IntoConnectionInfo, Pipeline,
+    RedisConnectionInfo, RedisFuture, RedisResult, TlsMode, Value,
+};
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
 
-/// Shared runtime for reload operations to avoid expensive runtime creation on every reload
-static RELOAD_RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
-    tokio::runtime::Runtime::new().expect("Failed to create reload runtime for Redis adapter")
-});
+/// How the Redis deployment is reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedisTopology {
+    /// A single Redis server.
+    Standalone,
+    /// A master discovered through Redis Sentinel. The master is looked up
+    /// again on every connection, so failovers are followed.
+    Sentinel {
+        /// Name of the monitored master
+        master_name: String,
+    },
+    /// A Redis Cluster. Key scans are run on every master node.
+    Cluster,
+}
+
+/// Connection options for Redis deployments.
+///
+/// Covers the database index, ACL credentials, TLS and the deployment topology.
+/// The same options are used by [`RedisAdapter`] and
+/// [`RedisWatcher`](crate::adapters::RedisWatcher), so both work against the
+/// same database; the watcher subscribes to `__keyspace@<db>__` accordingly.
+///
+/// Addresses may be given as `redis://` or `rediss://` URLs or as plain
+/// `host:port`. Settings made with the builder methods override those in URLs.
+///
+/// The options also carry the key separator translation for string keys and
+/// hash fields, so the adapter and its watchers agree on key names.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::{RedisAdapter, RedisConnectOptions, RedisStorageMode};
+///
+/// # #[tokio::main]
+/// # async fn main() -> hexcfg::domain::Result<()> {
+/// let options = RedisConnectOptions::sentinel(
+///         vec!["sentinel-1:26379", "sentinel-2:26379"],
+///         "config-master",
+///     )
+///     .with_db(3)
+///     .with_user("myapp", "secret")
+///     .with_tls(true);
+///
+/// let adapter = RedisAdapter::with_options(options, "myapp:", RedisStorageMode::StringKeys)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RedisConnectOptions {
+    /// How the deployment is reached
+    topology: RedisTopology,
+    /// Server, sentinel or cluster seed addresses
+    addresses: Vec<String>,
+    /// Database index, overriding the one in the URL
+    db: Option<i64>,
+    /// ACL user name
+    username: Option<String>,
+    /// Password for the ACL user or the default user
+    password: Option<String>,
+    /// Whether to connect over TLS
+    tls: bool,
+    /// Translation between stored key names and configuration keys
+    key_separator: KeySeparator,
+}
+
+impl fmt::Debug for RedisConnectOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisConnectOptions")
+            .field("topology", &self.topology)
+            .field("addresses", &self.addresses)
+            .field("db", &self.db)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("tls", &self.tls)
+            .field("key_separator", &self.key_separator)
+            .finish()
+    }
+}
+
+impl RedisConnectOptions {
+    /// Creates options for a single Redis server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::RedisConnectOptions;
+    ///
+    /// let options = RedisConnectOptions::new("redis://localhost:6379/2");
+    /// assert_eq!(options.db(), 2);
+    /// ```
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            topology: RedisTopology::Standalone,
+            addresses: vec![url.into()],
+            db: None,
+            username: None,
+            password: None,
+            tls: false,
+            key_separator: KeySeparator::None,
+        }
+    }
+
+    /// Creates options for a master monitored by Redis Sentinel.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentinels` - Addresses of the sentinels
+    /// * `master_name` - Name of the master the sentinels monitor
+    pub fn sentinel<S: AsRef<str>>(sentinels: Vec<S>, master_name: impl Into<String>) -> Self {
+        Self {
+            topology: RedisTopology::Sentinel {
+                master_name: master_name.into(),
+            },
+            addresses: sentinels.iter().map(|s| s.as_ref().to_string()).collect(),
+            ..Self::new(String::new())
+        }
+    }
+
+    /// Creates options for a Redis Cluster.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - Addresses of one or more cluster nodes used to discover the rest
+    pub fn cluster<S: AsRef<str>>(nodes: Vec<S>) -> Self {
+        Self {
+            topology: RedisTopology::Cluster,
+            addresses: nodes.iter().map(|s| s.as_ref().to_string()).collect(),
+            ..Self::new(String::new())
+        }
+    }
+
+    /// Selects the database index. Redis Cluster only supports database 0.
+    pub fn with_db(mut self, db: i64) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Authenticates as an ACL user.
+    pub fn with_user(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Authenticates the default user with a password (`requirepass`).
+    pub fn with_password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Connects over TLS, as with a `rediss://` URL.
+    pub fn with_tls(mut self, enabled: bool) -> Self {
+        self.tls = enabled;
+        self
+    }
+
+    /// Sets how key names map to configuration keys.
+    ///
+    /// Applies to the key suffix in [`RedisStorageMode::StringKeys`] mode and
+    /// to field names in [`RedisStorageMode::Hash`] mode, when loading, for
+    /// keyspace events and when writing. By default names are used as stored,
+    /// so `myapp:db:host` is the configuration key `db:host`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::{KeySeparator, RedisConnectOptions};
+    ///
+    /// // `myapp:db:host` becomes `db.host`
+    /// let options = RedisConnectOptions::new("redis://localhost:6379")
+    ///     .with_key_separator(KeySeparator::translate(":"));
+    /// assert_eq!(options.key_separator().to_config_key("db:host"), "db.host");
+    /// ```
+    pub fn with_key_separator(mut self, separator: KeySeparator) -> Self {
+        self.key_separator = separator;
+        self
+    }
+
+    /// Returns the key separator translation.
+    pub fn key_separator(&self) -> &KeySeparator {
+        &self.key_separator
+    }
+
+    /// Returns the topology of the deployment.
+    pub fn topology(&self) -> &RedisTopology {
+        &self.topology
+    }
+
+    /// Returns the database index used by the adapter and the watcher.
+    ///
+    /// This is the index set with [`with_db`](Self::with_db), or else the one
+    /// in a standalone server URL, or else 0.
+    pub fn db(&self) -> i64 {
+        if let Some(db) = self.db {
+            return db;
+        }
+        match self.topology {
+            RedisTopology::Standalone => self
+                .addresses
+                .first()
+                .and_then(|url| Self::to_url(url).into_connection_info().ok())
+                .map_or(0, |info| info.redis.db),
+            _ => 0,
+        }
+    }
+
+    /// Adds a `redis://` scheme to plain `host:port` addresses.
+    fn to_url(address: &str) -> String {
+        if address.contains("://") {
+            address.to_string()
+        } else {
+            format!("redis://{}", address)
+        }
+    }
+
+    /// Returns the connection info for a data node, applying these options.
+    pub(crate) fn node_info(&self, address: &str) -> RedisResult<ConnectionInfo> {
+        let mut info = Self::to_url(address).into_connection_info()?;
+        info.redis = self.redis_info(&info.redis);
+        if self.tls {
+            if let ConnectionAddr::Tcp(host, port) = info.addr {
+                info.addr = ConnectionAddr::TcpTls {
+                    host,
+                    port,
+                    insecure: false,
+                };
+            }
+        }
+        Ok(info)
+    }
+
+    fn redis_info(&self, base: &RedisConnectionInfo) -> RedisConnectionInfo {
+        RedisConnectionInfo {
+            db: self.db.unwrap_or(base.db),
+            username: self.username.clone().or_else(|| base.username.clone()),
+            password: self.password.clone().or_else(|| base.password.clone()),
+        }
+    }
+
+    fn validate(&self) -> RedisResult<()> {
+        if self.addresses.is_empty() || self.addresses.iter().any(|a| a.is_empty()) {
+            return Err((
+                redis::ErrorKind::InvalidClientConfig,
+                "No Redis address given",
+            )
+                .into());
+        }
+        if self.topology == RedisTopology::Cluster && self.db() != 0 {
+            return Err((
+                redis::ErrorKind::InvalidClientConfig,
+                "Redis Cluster only supports database 0",
+            )
+                .into());
+        }
+        for address in &self.addresses {
+            Self::to_url(address).into_connection_info()?;
+        }
+        Ok(())
+    }
+
+    /// Returns a client for every master node holding data.
+    ///
+    /// Standalone and Sentinel deployments have one master; for a cluster the
+    /// masters are discovered with `CLUSTER NODES`.
+    pub(crate) async fn master_clients(&self) -> RedisResult<Vec<Client>> {
+        match &self.topology {
+            RedisTopology::Standalone => {
+                Ok(vec![Client::open(self.node_info(&self.addresses[0])?)?])
+            }
+            RedisTopology::Sentinel { master_name } => {
+                let sentinels: Vec<String> =
+                    self.addresses.iter().map(|a| Self::to_url(a)).collect();
+                let node_info = SentinelNodeConnectionInfo {
+                    tls_mode: self.tls.then_some(TlsMode::Secure),
+                    redis_connection_info: Some(self.redis_info(&RedisConnectionInfo::default())),
+                };
+                let client = Sentinel::build(sentinels)?
+                    .async_master_for(master_name, Some(&node_info))
+                    .await?;
+                Ok(vec![client])
+            }
+            RedisTopology::Cluster => {
+                let mut conn = self.cluster_client()?.get_async_connection().await?;
+                let nodes: String = redis::cmd("CLUSTER")
+                    .arg("NODES")
+                    .query_async(&mut conn)
+                    .await?;
+                Self::parse_cluster_masters(&nodes)?
+                    .iter()
+                    .map(|address| Client::open(self.node_info(address)?))
+                    .collect()
+            }
+        }
+    }
+
+    fn cluster_client(&self) -> RedisResult<ClusterClient> {
+        let nodes = self
+            .addresses
+            .iter()
+            .map(|address| self.node_info(address))
+            .collect::<RedisResult<Vec<_>>>()?;
+        ClusterClient::new(nodes)
+    }
+
+    /// Extracts the `host:port` of every healthy master from `CLUSTER NODES` output.
+    ///
+    /// Fails if the output lists no usable master, so callers never end up
+    /// with an empty set of nodes.
+    fn parse_cluster_masters(nodes: &str) -> RedisResult<Vec<String>> {
+        let masters: Vec<String> = nodes
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let address = fields.nth(1)?;
+                let flags = fields.next()?;
+                let is_master = flags.split(',').any(|flag| flag == "master");
+                let is_unusable = flags
+                    .split(',')
+                    .any(|flag| matches!(flag, "fail" | "noaddr" | "handshake"));
+                let address = address.split('@').next()?;
+                (is_master && !is_unusable && !address.starts_with(':'))
+                    .then(|| address.to_string())
+            })
+            .collect();
+        if masters.is_empty() {
+            return Err((
+                redis::ErrorKind::ClusterDown,
+                "CLUSTER NODES lists no usable master",
+            )
+                .into());
+        }
+        Ok(masters)
+    }
+
+    /// Opens the connection used for hash reads and writes.
+    async fn connect(&self) -> RedisResult<RedisConnection> {
+        match self.topology {
+            RedisTopology::Cluster => Ok(RedisConnection::Cluster(
+                self.cluster_client()?.get_async_connection().await?,
+            )),
+            _ => {
+                let client = self.master_clients().await?.remove(0);
+                Ok(RedisConnection::Node(
+                    client.get_multiplexed_async_connection().await?,
+                ))
+            }
+        }
+    }
+}
+
+/// A connection to a single node or to a whole cluster.
+#[derive(Clone)]
+enum RedisConnection {
+    Node(MultiplexedConnection),
+    Cluster(ClusterConnection),
+}
+
+impl ConnectionLike for RedisConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        match self {
+            RedisConnection::Node(conn) => conn.req_packed_command(cmd),
+            RedisConnection::Cluster(conn) => conn.req_packed_command(cmd),
+        }
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        match self {
+            RedisConnection::Node(conn) => conn.req_packed_commands(cmd, offset, count),
+            RedisConnection::Cluster(conn) => conn.req_packed_commands(cmd, offset, count),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            RedisConnection::Node(conn) => conn.get_db(),
+            RedisConnection::Cluster(conn) => conn.get_db(),
+        }
+    }
+}
+
+/// Cached configuration values shared between an adapter and its watchers.
+pub(crate) type SharedRedisCache = Shared<HashMap<String, String>>;
+
+impl SharedRedisCache {
+    /// Replaces all values, returning the keys whose values changed.
+    fn replace(&self, values: HashMap<String, String>) -> Vec<String> {
+        let mut cache = self.write();
+
+        let mut changed: Vec<String> = values
+            .iter()
+            .filter(|(key, value)| cache.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .collect();
+        changed.extend(
+            cache
+                .keys()
+                .filter(|key| !values.contains_key(*key))
+                .cloned(),
+        );
+
+        *cache = values;
+        changed
+    }
+
+    /// Replaces the values read from one nested hash, returning the keys whose
+    /// values changed.
+    ///
+    /// `path` is the section the hash holds; its keys are `path.<field>`.
+    fn replace_section(&self, path: &str, fields: HashMap<String, String>) -> Vec<String> {
+        let mut cache = self.write();
+        let mut changed = Vec::new();
+
+        let stale: Vec<String> = cache
+            .keys()
+            .filter(|key| {
+                let key = ConfigKey::from(key.as_str());
+                let parent = key.parent().map(ConfigKey::into_string);
+                let field = key.segments().pop().map(|s| s.as_unescaped());
+                parent.as_deref().unwrap_or("") == path
+                    && !fields.contains_key(&field.unwrap_or_default())
+            })
+            .cloned()
+            .collect();
+        for key in stale {
+            cache.remove(&key);
+            changed.push(key);
+        }
+
+        for (field, value) in fields {
+            let key = section_key(path, &field);
+            if cache.get(&key) != Some(&value) {
+                cache.insert(key.clone(), value);
+                changed.push(key);
+            }
+        }
+
+        changed
+    }
+
+    /// Sets or removes a single value.
+    fn update(&self, key: &str, value: Option<String>) {
+        let mut cache = self.write();
+        match value {
+            Some(value) => cache.insert(key.to_string(), value),
+            None => cache.remove(key),
+        };
+    }
+}
+
+/// Keyspace events after which a key no longer holds a value.
+const REMOVAL_EVENTS: &[&str] = &["del", "expired", "evicted", "rename_from"];
+
+/// Returns the separator between the sections of nested hash keys: the
+/// configured key separator, or `:` if none is set.
+fn section_separator(separator: &KeySeparator) -> &str {
+    match separator {
+        KeySeparator::Translate(separator) if !separator.is_empty() => separator,
+        _ => ":",
+    }
+}
+
+/// Joins a nested hash section path and a field into a configuration key.
+///
+/// Dots in the field name are escaped, so the field stays one key segment.
+fn section_key(path: &str, field: &str) -> String {
+    ConfigKey::from(path).child(field).into_string()
+}
+
+/// Returns the section path of a nested hash key under `namespace`.
+///
+/// With `:` as separator, `prefix:database:replica` becomes `database.replica`.
+fn section_path(namespace: &str, redis_key: &str, separator: &str) -> Option<String> {
+    let rest = redis_key.strip_prefix(namespace)?;
+    let segments = rest
+        .split(separator)
+        .filter(|section| !section.is_empty())
+        .map(KeySegment::from);
+    Some(ConfigKey::from_segments(segments).into_string())
+}
+
+/// Returns the nested hash and field a configuration key is stored in.
+///
+/// The last key segment is the field; with `:` as separator, `database.host`
+/// is the `host` field of `prefix:database`.
+fn nested_hash_location(namespace: &str, key: &str, separator: &str) -> (String, String) {
+    let mut segments = ConfigKey::from(key).segments();
+    let field = segments
+        .pop()
+        .map(|field| field.as_unescaped())
+        .unwrap_or_default();
+    let path: Vec<String> = segments.iter().map(KeySegment::as_unescaped).collect();
+    (format!("{}{}", namespace, path.join(separator)), field)
+}
+
+/// Parser for documents stored with `Document` or `JsonDocument`.
+#[derive(Clone)]
+struct DocumentParser(Arc<dyn ConfigParser + Send + Sync>);
+
+impl fmt::Debug for DocumentParser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DocumentParser")
+            .field(&self.0.supported_extensions())
+            .finish()
+    }
+}
+
+impl DocumentParser {
+    /// Returns the YAML parser, which also reads JSON documents.
+    #[cfg(feature = "yaml")]
+    fn default_parser() -> Option<Self> {
+        Some(Self(Arc::new(crate::adapters::YamlParser::new())))
+    }
+
+    /// Without the `yaml` feature a parser must be given explicitly.
+    #[cfg(not(feature = "yaml"))]
+    fn default_parser() -> Option<Self> {
+        None
+    }
+}
+
+/// Patches a [`RedisAdapter`]'s cache from the events its watcher receives.
+#[derive(Clone)]
+pub(crate) struct RedisCacheSync {
+    /// Connection options of the adapter
+    options: RedisConnectOptions,
+    /// Key prefix or hash key name
+    namespace: String,
+    /// Storage mode of the adapter
+    storage_mode: RedisStorageMode,
+    /// Parser for document storage modes
+    parser: Option<DocumentParser>,
+    /// Channel on which changed hash fields are published
+    hash_change_channel: Option<String>,
+    /// The adapter's cache
+    cache: SharedRedisCache,
+    /// Connection for fetching changed values, opened on first use
+    conn: Option<RedisConnection>,
+}
+
+impl fmt::Debug for RedisCacheSync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisCacheSync")
+            .field("namespace", &self.namespace)
+            .field("storage_mode", &self.storage_mode)
+            .field("hash_change_channel", &self.hash_change_channel)
+            .finish()
+    }
+}
+
+impl RedisCacheSync {
+    /// Returns the channel on which changed hash fields are published, if any.
+    pub(crate) fn hash_change_channel(&self) -> Option<&str> {
+        match self.storage_mode {
+            RedisStorageMode::Hash => self.hash_change_channel.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Reloads every value, returning the keys that changed.
+    pub(crate) async fn resync(&self) -> Result<Vec<String>> {
+        let values = RedisAdapter::fetch_all(
+            &self.options,
+            &self.namespace,
+            self.storage_mode,
+            self.parser.as_ref(),
+        )
+        .await?;
+        Ok(self.cache.replace(values))
+    }
+
+    /// Applies a keyspace event for a Redis key, returning the configuration
+    /// keys it changed.
+    ///
+    /// Keyspace events for a hash do not name the field, so without a hash
+    /// change channel the whole hash is reloaded. A changed nested hash is
+    /// reloaded on its own, and a changed document is parsed again.
+    pub(crate) async fn key_event(&mut self, redis_key: &str, event: &str) -> Result<Vec<String>> {
+        let removed = REMOVAL_EVENTS.contains(&event);
+
+        match self.storage_mode {
+            RedisStorageMode::StringKeys => {
+                let Some(key) = redis_key.strip_prefix(&self.namespace) else {
+                    return Ok(Vec::new());
+                };
+                let key = self.options.key_separator().to_config_key(key);
+                let value = if removed {
+                    None
+                } else {
+                    let conn = self.connection().await?;
+                    let key_type: String = redis::cmd("TYPE")
+                        .arg(redis_key)
+                        .query_async(conn)
+                        .await
+                        .map_err(Self::fetch_error)?;
+                    match key_type.as_str() {
+                        "string" => conn.get(redis_key).await.map_err(Self::fetch_error)?,
+                        "none" => None,
+                        // Other key types under the prefix are not configuration
+                        _ => return Ok(Vec::new()),
+                    }
+                };
+                self.cache.update(&key, value);
+                Ok(vec![key])
+            }
+            RedisStorageMode::Hash => {
+                if redis_key != self.namespace || (!removed && self.hash_change_channel.is_some()) {
+                    return Ok(Vec::new());
+                }
+                self.resync().await
+            }
+            RedisStorageMode::NestedHash => {
+                let separator = section_separator(self.options.key_separator());
+                let Some(path) = section_path(&self.namespace, redis_key, separator) else {
+                    return Ok(Vec::new());
+                };
+                let conn = self.connection().await?;
+                let key_type: String = redis::cmd("TYPE")
+                    .arg(redis_key)
+                    .query_async(conn)
+                    .await
+                    .map_err(Self::fetch_error)?;
+                let fields = match key_type.as_str() {
+                    "hash" => conn.hgetall(redis_key).await.map_err(Self::fetch_error)?,
+                    "none" => HashMap::new(),
+                    // Other key types under the prefix are not configuration
+                    _ => return Ok(Vec::new()),
+                };
+                Ok(self.cache.replace_section(&path, fields))
+            }
+            RedisStorageMode::Document | RedisStorageMode::JsonDocument => {
+                if redis_key != self.namespace {
+                    return Ok(Vec::new());
+                }
+                self.resync().await
+            }
+        }
+    }
+
+    /// Applies a hash field published on the hash change channel, returning
+    /// the configuration key it changed.
+    pub(crate) async fn field_event(&mut self, field: &str) -> Result<Vec<String>> {
+        let namespace = self.namespace.clone();
+        let conn = self.connection().await?;
+        let value = conn
+            .hget(&namespace, field)
+            .await
+            .map_err(Self::fetch_error)?;
+        let key = self.options.key_separator().to_config_key(field);
+        self.cache.update(&key, value);
+        Ok(vec![key])
+    }
+
+    /// Returns the connection for fetching values, opening it if needed.
+    async fn connection(&mut self) -> Result<&mut RedisConnection> {
+        if self.conn.is_none() {
+            let conn = self
+                .options
+                .connect()
+                .await
+                .map_err(|e| ConfigError::SourceError {
+                    source_name: "redis".to_string(),
+                    message: format!("Failed to connect to Redis: {}", e),
+                    source: Some(Box::new(e)),
+                })?;
+            self.conn = Some(conn);
+        }
+        Ok(self.conn.as_mut().expect("connection was just opened"))
+    }
+
+    fn fetch_error(e: redis::RedisError) -> ConfigError {
+        ConfigError::SourceError {
+            source_name: "redis".to_string(),
+            message: format!("Failed to fetch value from Redis: {}", e),
+            source: Some(Box::new(e)),
+        }
+    }
+}
 
 /// Storage mode for Redis configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,6 +717,21 @@ pub enum RedisStorageMode {
     /// Store all configuration in a single Redis hash.
     /// Example: HGETALL config_hash
     Hash,
+    /// Store all configuration as a YAML or JSON document in a single Redis
+    /// string, parsed with the adapter's `ConfigParser`. Read-only.
+    /// Example: GET myapp:config
+    Document,
+    /// Store all configuration as a RedisJSON document, parsed with the
+    /// adapter's `ConfigParser`. Requires the RedisJSON module. Read-only.
+    /// Example: JSON.GET myapp:config
+    JsonDocument,
+    /// Store each section in its own hash under a prefix. The rest of the hash
+    /// key is the section path and the fields are the keys in it; the last key
+    /// segment is always the field. Sections are separated by the configured
+    /// key separator, or `:` if none is set, and dots in field names are
+    /// escaped as `\.` in configuration keys.
+    /// Example: HGETALL prefix:database holds database.host, database.port
+    NestedHash,
 }
 
 /// Configuration source adapter for Redis.
@@ -37,6 +743,33 @@ pub enum RedisStorageMode {
 ///
 /// Redis has a default priority of 1, but this can be customized.
 ///
+/// # Structured Configuration
+///
+/// With [`RedisStorageMode::Document`] or [`RedisStorageMode::JsonDocument`],
+/// the namespace is the key holding the document, which is flattened like a
+/// YAML file (`database: { host: ... }` gives `database.host`). The YAML parser
+/// is used by default and also reads JSON; use [`with_parser`](Self::with_parser)
+/// for other formats. With [`RedisStorageMode::NestedHash`], the namespace is a
+/// prefix and each hash below it holds one section.
+///
+/// # Watching
+///
+/// A watcher created with [`watcher`](Self::watcher) patches the adapter's
+/// cache from keyspace notifications instead of requiring a full reload: a
+/// changed key is fetched with `GET`, and deleted or expired keys are removed.
+/// Keyspace notifications for a hash do not name the changed field, so in
+/// `Hash` mode writers can publish the field name on a channel set with
+/// [`with_hash_change_channel`](Self::with_hash_change_channel) and only that
+/// field is fetched with `HGET`. After (re)subscribing, the watcher reloads
+/// everything once so no change is missed while it was disconnected.
+///
+/// # Deployments
+///
+/// Use [`RedisAdapter::with_options`] to select a database, authenticate, use
+/// TLS, or connect through Sentinel or to a Cluster. In a cluster, `StringKeys`
+/// scans run on every master, and write batches are not atomic unless all keys
+/// share a hash slot (for example with a `{myapp}:` namespace).
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -63,16 +796,20 @@ pub enum RedisStorageMode {
 /// ```
 #[derive(Debug)]
 pub struct RedisAdapter {
-    /// Redis client
-    client: Arc<Client>,
+    /// Connection options, used for every connection
+    options: RedisConnectOptions,
     /// Key prefix or hash key name
     namespace: String,
     /// Storage mode (string keys or hash)
     storage_mode: RedisStorageMode,
+    /// Parser for document storage modes
+    parser: Option<DocumentParser>,
     /// Priority for this source
     priority: u8,
+    /// Channel on which changed hash fields are published
+    hash_change_channel: Option<String>,
     /// Cached configuration values
-    cache: HashMap<String, String>,
+    cache: SharedRedisCache,
 }
 
 impl RedisAdapter {
@@ -113,21 +850,114 @@ impl RedisAdapter {
     /// # }
     /// ```
     pub async fn new(url: &str, namespace: &str, storage_mode: RedisStorageMode) -> Result<Self> {
+        Self::with_options(RedisConnectOptions::new(url), namespace, storage_mode).await
+    }
+
+    /// Creates a new Redis adapter with database, authentication, TLS or
+    /// topology options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Connection options, also used when reloading and writing
+    /// * `namespace` - Key prefix (for StringKeys mode) or hash key name (for Hash mode)
+    /// * `storage_mode` - Whether to use string keys or hash storage
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{RedisAdapter, RedisConnectOptions, RedisStorageMode};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let options = RedisConnectOptions::cluster(vec!["node-1:6379", "node-2:6379"])
+    ///     .with_password("secret");
+    ///
+    /// let adapter = RedisAdapter::with_options(options, "{myapp}:", RedisStorageMode::StringKeys)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_options(
+        options: RedisConnectOptions,
+        namespace: &str,
+        storage_mode: RedisStorageMode,
+    ) -> Result<Self> {
+        Self::connect_with(
+            options,
+            namespace,
+            storage_mode,
+            DocumentParser::default_parser(),
+        )
+        .await
+    }
+
+    /// Creates a new Redis adapter that parses documents with the given parser.
+    ///
+    /// Only used with [`RedisStorageMode::Document`] and
+    /// [`RedisStorageMode::JsonDocument`].
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Connection options, also used when reloading
+    /// * `namespace` - Key holding the document
+    /// * `storage_mode` - `Document` or `JsonDocument`
+    /// * `parser` - Parser that flattens the document into configuration keys
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{RedisAdapter, RedisConnectOptions, RedisStorageMode, YamlParser};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let adapter = RedisAdapter::with_parser(
+    ///     RedisConnectOptions::new("redis://localhost:6379"),
+    ///     "myapp:config",
+    ///     RedisStorageMode::Document,
+    ///     YamlParser::new().escape_dots_in_keys(true),
+    /// ).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_parser(
+        options: RedisConnectOptions,
+        namespace: &str,
+        storage_mode: RedisStorageMode,
+        parser: impl ConfigParser + Send + Sync + 'static,
+    ) -> Result<Self> {
+        Self::connect_with(
+            options,
+            namespace,
+            storage_mode,
+            Some(DocumentParser(Arc::new(parser))),
+        )
+        .await
+    }
+
+    /// Validates the settings and performs the initial load.
+    async fn connect_with(
+        options: RedisConnectOptions,
+        namespace: &str,
+        storage_mode: RedisStorageMode,
+        parser: Option<DocumentParser>,
+    ) -> Result<Self> {
         // Validate namespace to prevent injection attacks
         Self::validate_namespace(namespace)?;
 
-        let client = Client::open(url).map_err(|e| ConfigError::SourceError {
+        options.validate().map_err(|e| ConfigError::SourceError {
             source_name: "redis".to_string(),
             message: format!("Failed to create Redis client: {}", e),
             source: Some(Box::new(e)),
         })?;
 
         let mut adapter = Self {
-            client: Arc::new(client),
+            options,
             namespace: namespace.to_string(),
             storage_mode,
+            parser,
             priority: 1,
-            cache: HashMap::new(),
+            hash_change_channel: None,
+            cache: SharedRedisCache::default(),
         };
 
         // Initial load of all keys
@@ -172,10 +1002,78 @@ impl RedisAdapter {
         Ok(adapter)
     }
 
-    /// Gets a multiplexed connection to Redis.
-    async fn get_connection(&self) -> Result<MultiplexedConnection> {
-        self.client
-            .get_multiplexed_async_connection()
+    /// Sets the channel on which changed hash fields are published.
+    ///
+    /// Only used in `Hash` mode. Writes made through this adapter publish each
+    /// changed field name on the channel, and other writers should do the same
+    /// (`PUBLISH <channel> <field>` after `HSET` or `HDEL`). A watcher created
+    /// with [`watcher`](Self::watcher) then fetches only the published field
+    /// instead of reloading the whole hash on every change.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{RedisAdapter, RedisStorageMode};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let adapter = RedisAdapter::new("redis://localhost:6379", "myapp:config", RedisStorageMode::Hash)
+    ///     .await?
+    ///     .with_hash_change_channel("myapp:config:changes");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_hash_change_channel(mut self, channel: impl Into<String>) -> Self {
+        self.hash_change_channel = Some(channel.into());
+        self
+    }
+
+    /// Creates a watcher that keeps this adapter's cache up to date.
+    ///
+    /// Changes are applied to the cache before the callback runs, so callbacks
+    /// only need to clear cached values in the service (see
+    /// [`DefaultConfigService::clear_cache`](crate::service::DefaultConfigService::clear_cache)).
+    /// Keyspace notifications must be enabled, as for [`RedisWatcher`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{RedisAdapter, RedisStorageMode};
+    /// use hexcfg::ports::ConfigWatcher;
+    /// use std::sync::Arc;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let adapter = RedisAdapter::new("redis://localhost:6379", "myapp:", RedisStorageMode::StringKeys)
+    ///     .await?;
+    ///
+    /// let mut watcher = adapter.watcher();
+    /// watcher.watch(Arc::new(|key| {
+    ///     println!("Configuration changed: {}", key);
+    /// }))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watcher(&self) -> RedisWatcher {
+        RedisWatcher::with_sync(
+            self.options.clone(),
+            &self.namespace,
+            RedisCacheSync {
+                options: self.options.clone(),
+                namespace: self.namespace.clone(),
+                storage_mode: self.storage_mode,
+                parser: self.parser.clone(),
+                hash_change_channel: self.hash_change_channel.clone(),
+                cache: self.cache.clone(),
+                conn: None,
+            },
+        )
+    }
+
+    /// Gets a connection to Redis, or to the cluster.
+    async fn get_connection(&self) -> Result<RedisConnection> {
+        self.options
+            .connect()
             .await
             .map_err(|e| ConfigError::SourceError {
                 source_name: "redis".to_string(),
@@ -186,258 +1084,155 @@ impl RedisAdapter {
 
     /// Loads all keys from Redis into the cache.
     async fn load_all_keys(&mut self) -> Result<()> {
-        let mut conn = self.get_connection().await?;
+        let values = Self::fetch_all(
+            &self.options,
+            &self.namespace,
+            self.storage_mode,
+            self.parser.as_ref(),
+        )
+        .await?;
+        self.cache.replace(values);
+        Ok(())
+    }
 
-        self.cache.clear();
+    /// Fetches all configuration values stored under the namespace.
+    async fn fetch_all(
+        options: &RedisConnectOptions,
+        namespace: &str,
+        storage_mode: RedisStorageMode,
+        parser: Option<&DocumentParser>,
+    ) -> Result<HashMap<String, String>> {
+        let connect_error = |e: redis::RedisError| ConfigError::SourceError {
+            source_name: "redis".to_string(),
+            message: format!("Failed to connect to Redis: {}", e),
+            source: Some(Box::new(e)),
+        };
+        let fetch_error = |what: &str, e: redis::RedisError| ConfigError::SourceError {
+            source_name: "redis".to_string(),
+            message: format!("Failed to fetch {} from Redis: {}", what, e),
+            source: Some(Box::new(e)),
+        };
 
-        match self.storage_mode {
+        match storage_mode {
             RedisStorageMode::Hash => {
                 // Load all fields from hash
-                let hash: HashMap<String, String> =
-                    conn.hgetall(&self.namespace)
+                let mut conn = options.connect().await.map_err(connect_error)?;
+                let fields: HashMap<String, String> = conn
+                    .hgetall(namespace)
+                    .await
+                    .map_err(|e| fetch_error("hash", e))?;
+                let separator = options.key_separator();
+                Ok(fields
+                    .into_iter()
+                    .map(|(field, value)| (separator.to_config_key(&field), value))
+                    .collect())
+            }
+            RedisStorageMode::StringKeys | RedisStorageMode::NestedHash => {
+                // Only keys of the mode's type are configuration, so a hash or
+                // list under the prefix does not fail the load with WRONGTYPE
+                let nested = storage_mode == RedisStorageMode::NestedHash;
+                let key_type = if nested { "hash" } else { "string" };
+                let separator = section_separator(options.key_separator());
+
+                // Keys are spread over every master in a cluster, so scan each one
+                let mut values = HashMap::new();
+                for client in options.master_clients().await.map_err(connect_error)? {
+                    let mut conn = client
+                        .get_multiplexed_async_connection()
                         .await
-                        .map_err(|e| ConfigError::SourceError {
-                            source_name: "redis".to_string(),
-                            message: format!("Failed to fetch hash from Redis: {}", e),
-                            source: Some(Box::new(e)),
-                        })?;
+                        .map_err(connect_error)?;
 
-                self.cache = hash;
+                    for key in Self::scan_node(&mut conn, namespace, key_type).await? {
+                        if nested {
+                            let fields: HashMap<String, String> = conn
+                                .hgetall(&key)
+                                .await
+                                .map_err(|e| fetch_error("hash", e))?;
+                            let path = section_path(namespace, &key, separator).unwrap_or_default();
+                            for (field, value) in fields {
+                                values.insert(section_key(&path, &field), value);
+                            }
+                        } else {
+                            let value: String =
+                                conn.get(&key).await.map_err(|e| fetch_error("value", e))?;
+
+                            // Strip prefix from key
+                            let key = key.strip_prefix(namespace).unwrap_or(&key);
+                            values.insert(options.key_separator().to_config_key(key), value);
+                        }
+                    }
+                }
+                Ok(values)
             }
-            RedisStorageMode::StringKeys => {
-                // Use SCAN instead of KEYS to avoid blocking the Redis server
-                let pattern = format!("{}*", self.namespace);
-                let mut cursor: u64 = 0;
-                let mut all_keys = Vec::new();
-
-                loop {
-                    let (new_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
-                        .arg(cursor)
-                        .arg("MATCH")
-                        .arg(&pattern)
-                        .arg("COUNT")
-                        .arg(100)
+            RedisStorageMode::Document | RedisStorageMode::JsonDocument => {
+                let parser = parser.ok_or_else(|| ConfigError::SourceError {
+                    source_name: "redis".to_string(),
+                    message: "Document storage requires a parser; enable the `yaml` feature or use RedisAdapter::with_parser".to_string(),
+                    source: None,
+                })?;
+
+                let mut conn = options.connect().await.map_err(connect_error)?;
+                let document: Option<String> = if storage_mode == RedisStorageMode::JsonDocument {
+                    redis::cmd("JSON.GET")
+                        .arg(namespace)
                         .query_async(&mut conn)
                         .await
-                        .map_err(|e| ConfigError::SourceError {
-                            source_name: "redis".to_string(),
-                            message: format!("Failed to scan keys from Redis: {}", e),
-                            source: Some(Box::new(e)),
-                        })?;
-
-                    all_keys.extend(keys);
-                    cursor = new_cursor;
-                    if cursor == 0 {
-                        break;
-                    }
+                } else {
+                    conn.get(namespace).await
                 }
+                .map_err(|e| fetch_error("document", e))?;
 
-                // Fetch all values
-                for key in all_keys {
-                    let value: String =
-                        conn.get(&key).await.map_err(|e| ConfigError::SourceError {
-                            source_name: "redis".to_string(),
-                            message: format!("Failed to fetch value from Redis: {}", e),
-                            source: Some(Box::new(e)),
-                        })?;
-
-                    // Strip prefix from key
-                    let key = if key.starts_with(&self.namespace) {
-                        &key[self.namespace.len()..]
-                    } else {
-                        &key
-                    };
-
-                    self.cache.insert(key.to_string(), value);
+                match document {
+                    Some(document) => parser.0.parse(&document),
+                    None => Ok(HashMap::new()),
                 }
             }
         }
-
-        Ok(())
     }
 
-    /// Reloads all keys from Redis synchronously.
-    ///
-    /// Note: This method uses a shared runtime to perform async operations efficiently.
-    /// If called from an async context, it will spawn a separate thread to avoid blocking.
-    fn reload_sync(&mut self) -> Result<()> {
-        let client = Arc::clone(&self.client);
-        let namespace = self.namespace.clone();
-        let storage_mode = self.storage_mode;
-
-        // Try to use the current runtime if available, otherwise use the shared runtime
-        let new_cache = if tokio::runtime::Handle::try_current().is_ok() {
-            // We're in an async context, need to spawn a separate thread with the shared runtime
-            // to avoid blocking the current runtime's executor
-            let handle = std::thread::spawn(move || {
-                RELOAD_RUNTIME.block_on(async move {
-                    let mut conn =
-                        client
-                            .get_multiplexed_async_connection()
-                            .await
-                            .map_err(|e| ConfigError::SourceError {
-                                source_name: "redis".to_string(),
-                                message: format!("Failed to connect to Redis: {}", e),
-                                source: Some(Box::new(e)),
-                            })?;
-
-                    let mut new_cache = HashMap::new();
-
-                    match storage_mode {
-                        RedisStorageMode::Hash => {
-                            // Load all fields from hash
-                            let hash: HashMap<String, String> = conn
-                                .hgetall(&namespace)
-                                .await
-                                .map_err(|e| ConfigError::SourceError {
-                                    source_name: "redis".to_string(),
-                                    message: format!("Failed to fetch hash from Redis: {}", e),
-                                    source: Some(Box::new(e)),
-                                })?;
-
-                            new_cache = hash;
-                        }
-                        RedisStorageMode::StringKeys => {
-                            // Use SCAN instead of KEYS to avoid blocking the Redis server
-                            let pattern = format!("{}*", namespace);
-                            let mut cursor: u64 = 0;
-                            let mut all_keys = Vec::new();
-
-                            loop {
-                                let (new_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
-                                    .arg(cursor)
-                                    .arg("MATCH")
-                                    .arg(&pattern)
-                                    .arg("COUNT")
-                                    .arg(100)
-                                    .query_async(&mut conn)
-                                    .await
-                                    .map_err(|e| ConfigError::SourceError {
-                                        source_name: "redis".to_string(),
-                                        message: format!("Failed to scan keys from Redis: {}", e),
-                                        source: Some(Box::new(e)),
-                                    })?;
-
-                                all_keys.extend(keys);
-                                cursor = new_cursor;
-                                if cursor == 0 {
-                                    break;
-                                }
-                            }
-
-                            // Fetch all values
-                            for key in all_keys {
-                                let value: String =
-                                    conn.get(&key).await.map_err(|e| ConfigError::SourceError {
-                                        source_name: "redis".to_string(),
-                                        message: format!("Failed to fetch value from Redis: {}", e),
-                                        source: Some(Box::new(e)),
-                                    })?;
-
-                                // Strip prefix from key
-                                let key = if key.starts_with(&namespace) {
-                                    &key[namespace.len()..]
-                                } else {
-                                    &key
-                                };
-
-                                new_cache.insert(key.to_string(), value);
-                            }
-                        }
-                    }
+    /// Lists all keys of `key_type` under the namespace on one node.
+    async fn scan_node(
+        conn: &mut MultiplexedConnection,
+        namespace: &str,
+        key_type: &str,
+    ) -> Result<Vec<String>> {
+        // Use SCAN instead of KEYS to avoid blocking the Redis server
+        let pattern = format!("{}*", namespace);
+        let mut cursor: u64 = 0;
+        let mut all_keys = Vec::new();
 
-                    Ok::<HashMap<String, String>, ConfigError>(new_cache)
-                })
-            });
+        loop {
+            let mut cmd = redis::cmd("SCAN");
+            cmd.arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(100)
+                .arg("TYPE")
+                .arg(key_type);
 
-            handle.join().map_err(|_| ConfigError::SourceError {
-                source_name: "redis".to_string(),
-                message: "Failed to join reload thread".to_string(),
-                source: None,
-            })?
-        } else {
-            // No runtime available, use the shared runtime
-            RELOAD_RUNTIME.block_on(async move {
-                let mut conn = client
-                    .get_multiplexed_async_connection()
+            let (new_cursor, keys): (u64, Vec<String>) =
+                cmd.query_async(conn)
                     .await
                     .map_err(|e| ConfigError::SourceError {
                         source_name: "redis".to_string(),
-                        message: format!("Failed to connect to Redis: {}", e),
+                        message: format!("Failed to scan keys from Redis: {}", e),
                         source: Some(Box::new(e)),
                     })?;
 
-                let mut new_cache = HashMap::new();
-
-                match storage_mode {
-                    RedisStorageMode::Hash => {
-                        // Load all fields from hash
-                        let hash: HashMap<String, String> = conn
-                            .hgetall(&namespace)
-                            .await
-                            .map_err(|e| ConfigError::SourceError {
-                                source_name: "redis".to_string(),
-                                message: format!("Failed to fetch hash from Redis: {}", e),
-                                source: Some(Box::new(e)),
-                            })?;
-
-                        new_cache = hash;
-                    }
-                    RedisStorageMode::StringKeys => {
-                        // Use SCAN instead of KEYS to avoid blocking the Redis server
-                        let pattern = format!("{}*", namespace);
-                        let mut cursor: u64 = 0;
-                        let mut all_keys = Vec::new();
-
-                        loop {
-                            let (new_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
-                                .arg(cursor)
-                                .arg("MATCH")
-                                .arg(&pattern)
-                                .arg("COUNT")
-                                .arg(100)
-                                .query_async(&mut conn)
-                                .await
-                                .map_err(|e| ConfigError::SourceError {
-                                    source_name: "redis".to_string(),
-                                    message: format!("Failed to scan keys from Redis: {}", e),
-                                    source: Some(Box::new(e)),
-                                })?;
-
-                            all_keys.extend(keys);
-                            cursor = new_cursor;
-                            if cursor == 0 {
-                                break;
-                            }
-                        }
-
-                        // Fetch all values
-                        for key in all_keys {
-                            let value: String =
-                                conn.get(&key).await.map_err(|e| ConfigError::SourceError {
-                                    source_name: "redis".to_string(),
-                                    message: format!("Failed to fetch value from Redis: {}", e),
-                                    source: Some(Box::new(e)),
-                                })?;
-
-                            // Strip prefix from key
-                            let key = if key.starts_with(&namespace) {
-                                &key[namespace.len()..]
-                            } else {
-                                &key
-                            };
-
-                            new_cache.insert(key.to_string(), value);
-                        }
-                    }
-                }
+            all_keys.extend(keys);
+            cursor = new_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
 
-                Ok::<HashMap<String, String>, ConfigError>(new_cache)
-            })
-        }?;
+        Ok(all_keys)
+    }
 
-        self.cache = new_cache;
-        Ok(())
+    /// Reloads all keys from Redis synchronously on the shared blocking runtime.
+    fn reload_sync(&mut self) -> Result<()> {
+        block_on(self.load_all_keys())
     }
 }
 
@@ -453,6 +1248,7 @@ impl ConfigSource for RedisAdapter {
     fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
         Ok(self
             .cache
+            .read()
             .get(key.as_str())
             .map(|v| ConfigValue::from(v.as_str())))
     }
@@ -460,6 +1256,7 @@ impl ConfigSource for RedisAdapter {
     fn all_keys(&self) -> Result<Vec<ConfigKey>> {
         Ok(self
             .cache
+            .read()
             .keys()
             .map(|k| ConfigKey::from(k.as_str()))
             .collect())
@@ -468,6 +1265,145 @@ impl ConfigSource for RedisAdapter {
     fn reload(&mut self) -> Result<()> {
         self.reload_sync()
     }
+
+    fn as_sink(&mut self) -> Option<&mut dyn ConfigSink> {
+        match self.storage_mode {
+            RedisStorageMode::Document | RedisStorageMode::JsonDocument => None,
+            _ => Some(self),
+        }
+    }
+}
+
+impl ConfigSink for RedisAdapter {
+    fn set(&mut self, key: &ConfigKey, value: &ConfigValue) -> Result<()> {
+        self.apply(&[ConfigChange::Set {
+            key: key.clone(),
+            value: value.clone(),
+        }])
+    }
+
+    fn delete(&mut self, key: &ConfigKey) -> Result<()> {
+        self.apply(&[ConfigChange::Delete { key: key.clone() }])
+    }
+
+    /// Applies all changes in a single MULTI/EXEC transaction.
+    ///
+    /// In a cluster the changes are sent one at a time instead.
+    fn apply(&mut self, changes: &[ConfigChange]) -> Result<()> {
+        if matches!(
+            self.storage_mode,
+            RedisStorageMode::Document | RedisStorageMode::JsonDocument
+        ) {
+            return Err(ConfigError::SourceError {
+                source_name: "redis".to_string(),
+                message: "Redis document storage is read-only".to_string(),
+                source: None,
+            });
+        }
+
+        let separator = self.options.key_separator();
+        let nested_separator = section_separator(separator);
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+
+        for change in changes {
+            let stored = separator.to_storage_key(change.key().as_str());
+            match (change, self.storage_mode) {
+                (ConfigChange::Set { value, .. }, RedisStorageMode::Hash) => {
+                    pipe.hset(&self.namespace, &stored, value.as_str());
+                }
+                (ConfigChange::Set { value, .. }, RedisStorageMode::StringKeys) => {
+                    pipe.set(format!("{}{}", self.namespace, stored), value.as_str());
+                }
+                (ConfigChange::Delete { .. }, RedisStorageMode::Hash) => {
+                    pipe.hdel(&self.namespace, &stored);
+                }
+                (ConfigChange::Delete { .. }, RedisStorageMode::StringKeys) => {
+                    pipe.del(format!("{}{}", self.namespace, stored));
+                }
+                (ConfigChange::Set { key, value }, RedisStorageMode::NestedHash) => {
+                    let (hash, field) =
+                        nested_hash_location(&self.namespace, key.as_str(), nested_separator);
+                    pipe.hset(hash, field, value.as_str());
+                }
+                (ConfigChange::Delete { key }, RedisStorageMode::NestedHash) => {
+                    let (hash, field) =
+                        nested_hash_location(&self.namespace, key.as_str(), nested_separator);
+                    pipe.hdel(hash, field);
+                }
+                (_, RedisStorageMode::Document | RedisStorageMode::JsonDocument) => {
+                    unreachable!("document storage is rejected above")
+                }
+            }
+            pipe.ignore();
+
+            if let (Some(channel), RedisStorageMode::Hash) =
+                (&self.hash_change_channel, self.storage_mode)
+            {
+                pipe.publish(channel, &stored).ignore();
+            }
+        }
+
+        let cluster = *self.options.topology() == RedisTopology::Cluster;
+        let write_error = |e: redis::RedisError| ConfigError::SourceError {
+            source_name: "redis".to_string(),
+            message: format!("Failed to write to Redis: {}", e),
+            source: Some(Box::new(e)),
+        };
+
+        block_on(async {
+            let mut conn = self.get_connection().await?;
+            if cluster {
+                // MULTI/EXEC cannot span hash slots, so cluster writes go one by one
+                for cmd in pipe.cmd_iter() {
+                    cmd.query_async::<_, ()>(&mut conn)
+                        .await
+                        .map_err(write_error)?;
+                }
+                Ok(())
+            } else {
+                pipe.query_async::<_, ()>(&mut conn)
+                    .await
+                    .map_err(write_error)
+            }
+        })?;
+
+        for change in changes {
+            match change {
+                ConfigChange::Set { key, value } => {
+                    self.cache
+                        .update(key.as_str(), Some(value.as_str().to_string()));
+                }
+                ConfigChange::Delete { key } => self.cache.update(key.as_str(), None),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl AsyncConfigSource for RedisAdapter {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+        let result = ConfigSource::get(self, key);
+        Box::pin(async move { result })
+    }
+
+    fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> {
+        let result = ConfigSource::all_keys(self);
+        Box::pin(async move { result })
+    }
+
+    fn reload(&mut self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(self.load_all_keys())
+    }
 }
 
 #[cfg(test)]
@@ -481,4 +1417,174 @@ mod tests {
         assert_eq!(RedisStorageMode::Hash, RedisStorageMode::Hash);
         assert_ne!(RedisStorageMode::StringKeys, RedisStorageMode::Hash);
     }
+
+    #[test]
+    fn test_shared_cache_replace_reports_changes() {
+        let cache = SharedRedisCache::default();
+        cache.update("kept", Some("1".to_string()));
+        cache.update("changed", Some("1".to_string()));
+        cache.update("removed", Some("1".to_string()));
+
+        let mut values = HashMap::new();
+        values.insert("kept".to_string(), "1".to_string());
+        values.insert("changed".to_string(), "2".to_string());
+        values.insert("added".to_string(), "1".to_string());
+
+        let mut changed = cache.replace(values);
+        changed.sort();
+        assert_eq!(changed, vec!["added", "changed", "removed"]);
+        assert_eq!(cache.read().len(), 3);
+
+        cache.update("added", None);
+        assert!(!cache.read().contains_key("added"));
+    }
+
+    #[test]
+    fn test_nested_hash_key_mapping() {
+        assert_eq!(
+            nested_hash_location("cfg:", "database.replica.host", ":"),
+            ("cfg:database:replica".to_string(), "host".to_string())
+        );
+        assert_eq!(
+            nested_hash_location("cfg:", "name", ":"),
+            ("cfg:".to_string(), "name".to_string())
+        );
+        assert_eq!(
+            section_path("cfg:", "cfg:database:replica", ":").as_deref(),
+            Some("database.replica")
+        );
+        assert_eq!(section_path("cfg:", "other:database", ":"), None);
+    }
+
+    #[test]
+    fn test_nested_hash_key_mapping_with_separator_and_dotted_fields() {
+        let key_separator = KeySeparator::translate("/");
+        let separator = section_separator(&key_separator);
+        assert_eq!(separator, "/");
+        assert_eq!(section_separator(&KeySeparator::None), ":");
+
+        let path = section_path("cfg/", "cfg/hosts/primary", separator).unwrap();
+        assert_eq!(path, "hosts.primary");
+        let key = section_key(&path, "db.example.com");
+        assert_eq!(key, r"hosts.primary.db\.example\.com");
+
+        // A dotted field name maps back to the hash and field it came from
+        assert_eq!(
+            nested_hash_location("cfg/", &key, separator),
+            (
+                "cfg/hosts/primary".to_string(),
+                "db.example.com".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_shared_cache_replace_section() {
+        let cache = SharedRedisCache::default();
+        cache.update("database.host", Some("db".to_string()));
+        cache.update("database.port", Some("5432".to_string()));
+        cache.update("database.replica.host", Some("replica".to_string()));
+        cache.update(r"database.pool\.size", Some("10".to_string()));
+
+        let mut fields = HashMap::new();
+        fields.insert("host".to_string(), "db2".to_string());
+
+        let mut changed = cache.replace_section("database", fields);
+        changed.sort();
+        assert_eq!(
+            changed,
+            vec!["database.host", "database.pool\\.size", "database.port"]
+        );
+
+        // Keys of nested sections are untouched
+        assert!(cache.read().contains_key("database.replica.host"));
+    }
+
+    #[test]
+    fn test_connect_options_db() {
+        assert_eq!(RedisConnectOptions::new("redis://localhost:6379").db(), 0);
+        assert_eq!(RedisConnectOptions::new("redis://localhost:6379/4").db(), 4);
+        assert_eq!(
+            RedisConnectOptions::new("redis://localhost:6379/4")
+                .with_db(7)
+                .db(),
+            7
+        );
+        assert_eq!(
+            RedisConnectOptions::sentinel(vec!["localhost:26379"], "master")
+                .with_db(2)
+                .db(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_connect_options_node_info() {
+        let options = RedisConnectOptions::new("localhost:6379")
+            .with_db(3)
+            .with_user("app", "secret")
+            .with_tls(true);
+        let info = options.node_info("localhost:6379").unwrap();
+
+        assert_eq!(info.redis.db, 3);
+        assert_eq!(info.redis.username.as_deref(), Some("app"));
+        assert_eq!(info.redis.password.as_deref(), Some("secret"));
+        assert!(matches!(
+            info.addr,
+            ConnectionAddr::TcpTls { port: 6379, .. }
+        ));
+
+        // Credentials in the URL are kept unless overridden
+        let info = RedisConnectOptions::new("redis://:pw@localhost:6379")
+            .node_info("redis://:pw@localhost:6379")
+            .unwrap();
+        assert_eq!(info.redis.password.as_deref(), Some("pw"));
+    }
+
+    #[test]
+    fn test_connect_options_validate() {
+        assert!(RedisConnectOptions::new("redis://localhost")
+            .validate()
+            .is_ok());
+        assert!(RedisConnectOptions::new("").validate().is_err());
+        assert!(RedisConnectOptions::cluster(vec!["localhost:7000"])
+            .with_db(1)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_connect_options_debug_redacts_password() {
+        let options = RedisConnectOptions::new("redis://localhost").with_user("app", "hunter2");
+        let debug = format!("{:?}", options);
+
+        assert!(debug.contains("app"));
+        assert!(!debug.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_parse_cluster_masters() {
+        let nodes = "\
+07c3 10.0.0.1:7000@17000 myself,master - 0 0 1 connected 0-5460
+67ed 10.0.0.2:7001@17001,node-2 master - 0 1426238316232 2 connected 5461-10922
+292f 10.0.0.3:7002@17002 slave 07c3 0 1426238318243 3 connected
+e7d1 10.0.0.4:7003@17003 master,fail - 1426238316232 0 4 disconnected
+6ec2 :0@0 master,noaddr - 0 0 5 disconnected
+";
+        assert_eq!(
+            RedisConnectOptions::parse_cluster_masters(nodes).unwrap(),
+            vec!["10.0.0.1:7000", "10.0.0.2:7001"]
+        );
+    }
+
+    #[test]
+    fn test_parse_cluster_masters_without_masters() {
+        assert!(RedisConnectOptions::parse_cluster_masters("").is_err());
+
+        let nodes = "\
+292f 10.0.0.3:7002@17002 slave 07c3 0 1426238318243 3 connected
+e7d1 10.0.0.4:7003@17003 master,fail - 1426238316232 0 4 disconnected
+";
+        assert!(RedisConnectOptions::parse_cluster_masters(nodes).is_err());
+    }
 }
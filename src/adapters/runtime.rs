@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Blocking execution of futures for the synchronous adapter APIs.
+//!
+//! Remote adapters are async internally but also implement the synchronous
+//! `ConfigSource` trait. This module runs their futures to completion on a
+//! shared runtime, moving to a scoped thread when the caller is already inside
+//! a tokio runtime (where blocking in place would panic).
+
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::thread;
+
+/// Shared runtime for blocking operations to avoid creating a runtime per call
+static BLOCKING_RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
+    tokio::runtime::Runtime::new().expect("Failed to create blocking runtime for hexcfg adapters")
+});
+
+/// Runs a future to completion from synchronous code.
+///
+/// Panics in the future are propagated to the caller.
+pub(crate) fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    if tokio::runtime::Handle::try_current().is_err() {
+        return BLOCKING_RUNTIME.block_on(future);
+    }
+
+    thread::scope(
+        |scope| match scope.spawn(|| BLOCKING_RUNTIME.block_on(future)).join() {
+            Ok(output) => output,
+            Err(panic) => std::panic::resume_unwind(panic),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_on_outside_runtime() {
+        assert_eq!(block_on(async { 21 * 2 }), 42);
+    }
+
+    #[test]
+    fn test_block_on_inside_runtime() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let value = String::from("borrowed");
+        let result = runtime.block_on(async { block_on(async { value.len() }) });
+        assert_eq!(result, 8);
+    }
+}
@@ -0,0 +1,764 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Resolution of secret references.
+//!
+//! This module provides a source wrapper that replaces `secret://...` and
+//! `${secret:...}` references with secrets looked up through `SecretResolver`s,
+//! the cache of resolved secrets it shares with `SecretLeaseWatcher`, and a
+//! resolver that reads secrets from files.
+
+use crate::adapters::shared::Shared;
+use crate::adapters::SecretLeaseWatcher;
+use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result};
+use crate::ports::{ConfigSource, ResolvedSecret, SecretReference, SecretResolver, SourceHealth};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Opening of a `${secret:...}` placeholder.
+const PLACEHOLDER_START: &str = "${secret:";
+
+/// Maximum size of a secret file (1 MB).
+const MAX_SECRET_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Delay before retrying the first failed refresh of a secret; doubled after
+/// every further failure.
+const REFRESH_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest delay between retries of a failed refresh.
+const MAX_REFRESH_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Creates an error for a secret that could not be resolved.
+fn secret_error(message: impl Into<String>) -> ConfigError {
+    ConfigError::SourceError {
+        source_name: "secrets".to_string(),
+        message: message.into(),
+        source: None,
+    }
+}
+
+/// One part of a configuration value holding secret references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// Text copied as-is
+    Literal(String),
+    /// Text replaced by a secret
+    Secret(SecretReference),
+}
+
+/// A configuration value split into text and secret references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Template(Vec<Segment>);
+
+impl Template {
+    /// Parses a value, returning `None` if it holds no secret references.
+    ///
+    /// A whole value of the form `secret://...` is a single reference;
+    /// otherwise every `${secret:...}` placeholder is one.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        if let Some(reference) = SecretReference::parse(value) {
+            return Some(Self(vec![Segment::Secret(reference)]));
+        }
+
+        let mut segments = Vec::new();
+        let mut rest = value;
+        while let Some(start) = rest.find(PLACEHOLDER_START) {
+            let inner = &rest[start + PLACEHOLDER_START.len()..];
+            let Some(end) = inner.find('}') else {
+                break;
+            };
+            let Some(reference) = SecretReference::parse_path(&inner[..end]) else {
+                // Not a valid reference; keep the text and look further on
+                segments.push(Segment::Literal(rest[..start + 1].to_string()));
+                rest = &rest[start + 1..];
+                continue;
+            };
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+            segments.push(Segment::Secret(reference));
+            rest = &inner[end + 1..];
+        }
+
+        if !segments.iter().any(|s| matches!(s, Segment::Secret(_))) {
+            return None;
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+        Some(Self(segments))
+    }
+
+    /// Returns the secret references of the template.
+    pub(crate) fn references(&self) -> impl Iterator<Item = &SecretReference> {
+        self.0.iter().filter_map(|segment| match segment {
+            Segment::Secret(reference) => Some(reference),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    /// Fills in the template, or returns `None` if a secret is missing.
+    fn render(&self, secret: impl Fn(&SecretReference) -> Option<String>) -> Option<String> {
+        let mut value = String::new();
+        for segment in &self.0 {
+            match segment {
+                Segment::Literal(text) => value.push_str(text),
+                Segment::Secret(reference) => value.push_str(&secret(reference)?),
+            }
+        }
+        Some(value)
+    }
+}
+
+/// A resolved secret and when it has to be resolved again.
+struct CachedSecret {
+    /// The secret value
+    value: String,
+    /// When the value expires; `None` keeps it until the source is reloaded
+    expires_at: Option<Instant>,
+    /// Refreshes that failed since the value was resolved
+    failures: u32,
+}
+
+impl CachedSecret {
+    /// Caches `secret` as resolved at `now`.
+    fn new(secret: ResolvedSecret, now: Instant) -> Self {
+        Self {
+            value: secret.value,
+            expires_at: secret.ttl.map(|ttl| now + ttl),
+            failures: 0,
+        }
+    }
+
+    /// Records a failed refresh at `now`, keeping the value and retrying
+    /// after a backoff that doubles with every failure.
+    fn postpone(&mut self, now: Instant) {
+        let backoff = REFRESH_BACKOFF
+            .saturating_mul(2u32.saturating_pow(self.failures))
+            .min(MAX_REFRESH_BACKOFF);
+        self.failures = self.failures.saturating_add(1);
+        self.expires_at = Some(now + backoff);
+    }
+
+    /// Returns whether the value has expired at `now`.
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// Resolved values of the keys holding secret references.
+///
+/// Shared between a [`ResolvingSource`] and the watchers it creates, so a
+/// secret refreshed by either is seen by both.
+#[derive(Default)]
+pub(crate) struct SecretState {
+    /// Templates of the keys holding secret references
+    templates: HashMap<String, Template>,
+    /// Resolved values of those keys
+    values: HashMap<String, ConfigValue>,
+    /// Resolved secrets, keyed by reference
+    cache: HashMap<SecretReference, CachedSecret>,
+    /// Keys whose values changed since a watcher last reported changes
+    unreported: HashSet<String>,
+}
+
+/// Handle to a [`SecretState`] shared between a source and its watchers.
+pub(crate) type SharedSecretState = Shared<SecretState>;
+
+impl SecretState {
+    /// Returns the references whose secrets have expired at `now`.
+    pub(crate) fn expired(&self, now: Instant) -> Vec<SecretReference> {
+        self.cache
+            .iter()
+            .filter(|(_, cached)| cached.is_expired(now))
+            .map(|(reference, _)| reference.clone())
+            .collect()
+    }
+
+    /// Returns the references of `key` whose secrets have expired at `now`.
+    fn expired_for(&self, key: &str, now: Instant) -> Vec<SecretReference> {
+        let Some(template) = self.templates.get(key) else {
+            return Vec::new();
+        };
+        template
+            .references()
+            .filter(|reference| {
+                self.cache
+                    .get(*reference)
+                    .is_some_and(|cached| cached.is_expired(now))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Stores freshly resolved secrets and fills in the keys using them again,
+    /// returning the keys whose values changed.
+    pub(crate) fn apply(
+        &mut self,
+        resolved: Vec<(SecretReference, ResolvedSecret)>,
+        now: Instant,
+    ) -> Vec<String> {
+        let refreshed: HashSet<SecretReference> = resolved.iter().map(|(r, _)| r.clone()).collect();
+        for (reference, secret) in resolved {
+            self.cache.insert(reference, CachedSecret::new(secret, now));
+        }
+
+        let mut changed = Vec::new();
+        for (key, template) in &self.templates {
+            if !template.references().any(|r| refreshed.contains(r)) {
+                continue;
+            }
+            let Some(value) = template.render(|r| self.cache.get(r).map(|c| c.value.clone()))
+            else {
+                continue;
+            };
+            if self.values.get(key).map(ConfigValue::as_str) != Some(value.as_str()) {
+                self.values.insert(key.clone(), ConfigValue::secret(value));
+                changed.push(key.clone());
+            }
+        }
+
+        self.unreported.extend(changed.iter().cloned());
+        changed
+    }
+
+    /// Keeps the values of secrets whose refresh failed at `now`, and retries
+    /// them after a backoff.
+    fn postpone(&mut self, references: &[SecretReference], now: Instant) {
+        for reference in references {
+            if let Some(cached) = self.cache.get_mut(reference) {
+                cached.postpone(now);
+            }
+        }
+    }
+
+    /// Returns and forgets the keys whose values changed since the last call.
+    pub(crate) fn take_unreported(&mut self) -> Vec<String> {
+        self.unreported.drain().collect()
+    }
+}
+
+/// Secret resolvers shared between a source and its watchers.
+#[derive(Clone)]
+pub(crate) struct SecretResolvers(Arc<Vec<Box<dyn SecretResolver>>>);
+
+impl SecretResolvers {
+    /// Looks up `reference` with the resolver it names, or the first one.
+    pub(crate) fn resolve(&self, reference: &SecretReference) -> Result<ResolvedSecret> {
+        let resolver = match reference.store() {
+            Some(store) => self.0.iter().find(|r| r.name() == store),
+            None => self.0.first(),
+        }
+        .ok_or_else(|| {
+            secret_error(format!(
+                "No secret resolver for '{}'",
+                reference.store().unwrap_or("${secret:...}")
+            ))
+        })?;
+        resolver.resolve(reference)
+    }
+
+    /// Resolves `references` again and applies them to `state`, returning the
+    /// keys whose values changed.
+    ///
+    /// Secrets that cannot be resolved keep their last value and are retried
+    /// after a backoff; the first such error is returned once the others have
+    /// been applied. No lock is held while the resolvers run.
+    pub(crate) fn refresh(
+        &self,
+        state: &SharedSecretState,
+        references: Vec<SecretReference>,
+    ) -> Result<Vec<String>> {
+        let mut resolved = Vec::new();
+        let mut failed = Vec::new();
+        let mut error = None;
+        for reference in references {
+            match self.resolve(&reference) {
+                Ok(secret) => resolved.push((reference, secret)),
+                Err(e) => {
+                    failed.push(reference);
+                    error.get_or_insert(e);
+                }
+            }
+        }
+
+        let now = Instant::now();
+        let mut state = state.write();
+        state.postpone(&failed, now);
+        let changed = state.apply(resolved, now);
+        match error {
+            Some(e) => Err(e),
+            None => Ok(changed),
+        }
+    }
+}
+
+/// Configuration source wrapper that resolves secret references.
+///
+/// Values of the wrapped source of the form `secret://<store>/<path>#<field>`
+/// are replaced by the secret the resolver named `<store>` returns, and
+/// `${secret:<path>#<field>}` placeholders anywhere in a value are replaced
+/// using the first resolver. All references are resolved when the wrapper is
+/// created and on every reload, so a missing secret is reported up front.
+/// Resolved values are marked as secret, so their `Debug` output is redacted.
+///
+/// Secrets are cached for the TTL their resolver reports, such as a Vault
+/// lease duration. An expired secret is resolved again the next time its key
+/// is read, and [`watcher`](Self::watcher) refreshes expired secrets in the
+/// background and reports the keys whose values changed. A read that finds an
+/// expired secret blocks while it is resolved, for up to the resolver's
+/// request timeout; with a watcher running, secrets are usually refreshed
+/// before they are read. If a refresh fails, the last value is kept and the
+/// refresh is retried after a backoff that doubles up to five minutes, so a
+/// resolver that is down is not asked again on every read.
+///
+/// The wrapper is read-only: write changes to the inner source before wrapping
+/// it.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::{FileSecretResolver, ResolvingSource, YamlFileAdapter};
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// // database.password: secret://file/db#password
+/// let yaml = YamlFileAdapter::from_file("/etc/myapp/config.yaml")?;
+/// let resolver = FileSecretResolver::new("/run/secrets")?;
+///
+/// let source = ResolvingSource::new(Box::new(yaml), resolver)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ResolvingSource {
+    /// The source holding secret references
+    inner: Box<dyn ConfigSource>,
+    /// Resolvers, selected by name
+    resolvers: SecretResolvers,
+    /// Resolved values, shared with watchers
+    state: SharedSecretState,
+}
+
+impl ResolvingSource {
+    /// Wraps `inner`, resolving its secret references with `resolver`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError::SourceError`] naming the key if any reference
+    /// cannot be resolved.
+    pub fn new(
+        inner: Box<dyn ConfigSource>,
+        resolver: impl SecretResolver + 'static,
+    ) -> Result<Self> {
+        Self::with_resolvers(inner, vec![Box::new(resolver)])
+    }
+
+    /// Wraps `inner`, resolving each reference with the resolver it names.
+    ///
+    /// `${secret:...}` placeholders use the first resolver.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{FileSecretResolver, ResolvingSource, YamlFileAdapter};
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let source = ResolvingSource::with_resolvers(
+    ///     Box::new(YamlFileAdapter::from_file("/etc/myapp/config.yaml")?),
+    ///     vec![
+    ///         Box::new(FileSecretResolver::new("/run/secrets")?),
+    ///         Box::new(FileSecretResolver::new("/etc/myapp/credentials")?.with_name("local")),
+    ///     ],
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_resolvers(
+        inner: Box<dyn ConfigSource>,
+        resolvers: Vec<Box<dyn SecretResolver>>,
+    ) -> Result<Self> {
+        let source = Self {
+            inner,
+            resolvers: SecretResolvers(Arc::new(resolvers)),
+            state: SharedSecretState::default(),
+        };
+        source.resolve_all()?;
+        Ok(source)
+    }
+
+    /// Creates a watcher that refreshes secrets as they expire.
+    ///
+    /// The watcher shares this source's cache, so refreshed values are read
+    /// through the source without reloading it. The callback is called for
+    /// each key whose value changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `check_interval` - How often to look for expired secrets (default 1s)
+    pub fn watcher(&self, check_interval: Option<Duration>) -> SecretLeaseWatcher {
+        SecretLeaseWatcher::with_state(
+            self.resolvers.clone(),
+            self.state.clone(),
+            check_interval.unwrap_or(Duration::from_secs(1)),
+        )
+    }
+
+    /// Resolves every secret reference of the inner source, replacing the
+    /// cache.
+    fn resolve_all(&self) -> Result<()> {
+        let mut templates = HashMap::new();
+        for key in self.inner.all_keys()? {
+            if let Some(value) = self.inner.get(&key)? {
+                if let Some(template) = Template::parse(value.as_str()) {
+                    templates.insert(key.as_str().to_string(), template);
+                }
+            }
+        }
+
+        let now = Instant::now();
+        let mut cache = HashMap::new();
+        let mut values = HashMap::new();
+        for (key, template) in &templates {
+            for reference in template.references() {
+                if cache.contains_key(reference) {
+                    continue;
+                }
+                let secret =
+                    self.resolvers
+                        .resolve(reference)
+                        .map_err(|e| ConfigError::SourceError {
+                            source_name: "secrets".to_string(),
+                            message: format!("Failed to resolve secret for key '{}': {}", key, e),
+                            source: Some(Box::new(e)),
+                        })?;
+                cache.insert(reference.clone(), CachedSecret::new(secret, now));
+            }
+            if let Some(value) = template.render(|r| cache.get(r).map(|c| c.value.clone())) {
+                values.insert(key.clone(), ConfigValue::secret(value));
+            }
+        }
+
+        tracing::debug!(
+            "Resolved {} secrets for source '{}'",
+            cache.len(),
+            self.inner.name()
+        );
+        *self.state.write() = SecretState {
+            templates,
+            values,
+            cache,
+            unreported: HashSet::new(),
+        };
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ResolvingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let resolvers: Vec<&str> = self.resolvers.0.iter().map(|r| r.name()).collect();
+        let state = self.state.read();
+        f.debug_struct("ResolvingSource")
+            .field("inner", &self.inner.name())
+            .field("resolvers", &resolvers)
+            .field("resolved", &state.values.len())
+            .finish()
+    }
+}
+
+impl ConfigSource for ResolvingSource {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        let expired = self.state.read().expired_for(key.as_str(), Instant::now());
+        if !expired.is_empty() {
+            if let Err(e) = self.resolvers.refresh(&self.state, expired) {
+                tracing::warn!("Keeping expired secret for key '{}': {}", key, e);
+            }
+        }
+        if let Some(value) = self.state.read().values.get(key.as_str()) {
+            return Ok(Some(value.clone()));
+        }
+
+        // Keys the inner source does not list are resolved on demand
+        let Some(value) = self.inner.get(key)? else {
+            return Ok(None);
+        };
+        let Some(template) = Template::parse(value.as_str()) else {
+            return Ok(Some(value));
+        };
+        let mut secrets = HashMap::new();
+        for reference in template.references() {
+            secrets.insert(reference.clone(), self.resolvers.resolve(reference)?.value);
+        }
+        Ok(template
+            .render(|r| secrets.get(r).cloned())
+            .map(ConfigValue::secret))
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        self.inner.all_keys()
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.inner.reload()?;
+        self.resolve_all()
+    }
+
+    fn health(&self) -> SourceHealth {
+        self.inner.health()
+    }
+}
+
+/// Resolver that reads secrets from files in a directory.
+///
+/// A reference's path names a file below the directory, and its field, if
+/// any, names a file inside that path: `secret://file/db#password` reads
+/// `<dir>/db/password`. Trailing newlines are removed. This suits mounted
+/// secret volumes and tests; set a TTL with [`with_ttl`](Self::with_ttl) to
+/// pick up rotated files through a
+/// [`SecretLeaseWatcher`](crate::adapters::SecretLeaseWatcher).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::FileSecretResolver;
+/// use std::time::Duration;
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// let resolver = FileSecretResolver::new("/run/secrets")?.with_ttl(Duration::from_secs(60));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileSecretResolver {
+    /// Name references select this resolver by
+    name: String,
+    /// Directory holding the secret files
+    dir: PathBuf,
+    /// How long secrets may be cached
+    ttl: Option<Duration>,
+}
+
+impl FileSecretResolver {
+    /// Creates a resolver named `file` for the secrets in `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::SourceError` if `dir` is not a directory.
+    pub fn new(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        if !dir.is_dir() {
+            return Err(secret_error(format!(
+                "Secret directory does not exist: {}",
+                dir.display()
+            )));
+        }
+        Ok(Self {
+            name: "file".to_string(),
+            dir,
+            ttl: None,
+        })
+    }
+
+    /// Sets the name references select this resolver by.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets how long secrets may be cached before the files are read again.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+impl SecretResolver for FileSecretResolver {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resolve(&self, reference: &SecretReference) -> Result<ResolvedSecret> {
+        let mut relative = PathBuf::from(reference.path());
+        if let Some(field) = reference.field() {
+            relative.push(field);
+        }
+        // References must not reach outside the directory
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(secret_error(format!("Invalid secret path: {}", reference)));
+        }
+
+        let path = self.dir.join(relative);
+        let read_error = |e: std::io::Error| ConfigError::SourceError {
+            source_name: "secrets".to_string(),
+            message: format!("Failed to read secret {}", reference),
+            source: Some(Box::new(e)),
+        };
+        let size = fs::metadata(&path).map_err(read_error)?.len();
+        if size > MAX_SECRET_FILE_SIZE {
+            return Err(secret_error(format!("Secret {} is too large", reference)));
+        }
+        let value = fs::read_to_string(&path).map_err(read_error)?;
+
+        let secret = ResolvedSecret::new(value.trim_end_matches(['\n', '\r']));
+        Ok(match self.ttl {
+            Some(ttl) => secret.with_ttl(ttl),
+            None => secret,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemoryAdapter;
+    use tempfile::TempDir;
+
+    fn secrets_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("db")).unwrap();
+        fs::write(dir.path().join("db/password"), "hunter2\n").unwrap();
+        fs::write(dir.path().join("api_token"), "t0k3n").unwrap();
+        dir
+    }
+
+    fn inner(values: &[(&str, &str)]) -> Box<dyn ConfigSource> {
+        Box::new(InMemoryAdapter::with_values(
+            values
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_template_parse() {
+        assert!(Template::parse("plain value").is_none());
+        assert!(Template::parse("${secret:}").is_none());
+        assert!(Template::parse("${secret:unterminated").is_none());
+
+        let template = Template::parse("a ${secret:db#password} b ${secret:api_token}").unwrap();
+        let references: Vec<String> = template.references().map(|r| r.to_string()).collect();
+        assert_eq!(
+            references,
+            vec!["${secret:db#password}", "${secret:api_token}"]
+        );
+        assert_eq!(
+            template.render(|r| Some(r.path().to_uppercase())).unwrap(),
+            "a DB b API_TOKEN"
+        );
+    }
+
+    #[test]
+    fn test_resolving_source_resolves_references() {
+        let dir = secrets_dir();
+        let source = ResolvingSource::new(
+            inner(&[
+                ("db.password", "secret://file/db#password"),
+                ("db.url", "postgres://app:${secret:db#password}@db/app"),
+                ("api.token", "${secret:api_token}"),
+                ("db.host", "localhost"),
+            ]),
+            FileSecretResolver::new(dir.path()).unwrap(),
+        )
+        .unwrap();
+
+        let password = source.get_str("db.password").unwrap().unwrap();
+        assert_eq!(password.as_str(), "hunter2");
+        assert!(password.is_secret());
+        assert_eq!(
+            source.get_str("db.url").unwrap().unwrap().as_str(),
+            "postgres://app:hunter2@db/app"
+        );
+        assert_eq!(
+            source.get_str("api.token").unwrap().unwrap().as_str(),
+            "t0k3n"
+        );
+
+        let host = source.get_str("db.host").unwrap().unwrap();
+        assert!(!host.is_secret());
+        assert!(!format!("{:?}", source).contains("hunter2"));
+    }
+
+    #[test]
+    fn test_resolving_source_missing_secret_names_key() {
+        let dir = secrets_dir();
+        let err = ResolvingSource::new(
+            inner(&[("db.user", "secret://file/db#user")]),
+            FileSecretResolver::new(dir.path()).unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("db.user"));
+
+        let err = ResolvingSource::new(
+            inner(&[("db.user", "secret://vault/kv/db#user")]),
+            FileSecretResolver::new(dir.path()).unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("No secret resolver for 'vault'"));
+    }
+
+    #[test]
+    fn test_expired_secret_is_resolved_again_on_read() {
+        let dir = secrets_dir();
+        let source = ResolvingSource::new(
+            inner(&[("api.token", "secret://file/api_token")]),
+            FileSecretResolver::new(dir.path())
+                .unwrap()
+                .with_ttl(Duration::from_millis(10)),
+        )
+        .unwrap();
+
+        fs::write(dir.path().join("api_token"), "rotated").unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(
+            source.get_str("api.token").unwrap().unwrap().as_str(),
+            "rotated"
+        );
+        assert_eq!(source.state.write().take_unreported(), vec!["api.token"]);
+    }
+
+    #[test]
+    fn test_failed_refresh_keeps_value_and_backs_off() {
+        let dir = secrets_dir();
+        let source = ResolvingSource::new(
+            inner(&[("api.token", "secret://file/api_token")]),
+            FileSecretResolver::new(dir.path())
+                .unwrap()
+                .with_ttl(Duration::from_millis(10)),
+        )
+        .unwrap();
+
+        fs::remove_file(dir.path().join("api_token")).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(
+            source.get_str("api.token").unwrap().unwrap().as_str(),
+            "t0k3n"
+        );
+        // The secret is not retried until the backoff has passed
+        let now = Instant::now();
+        assert!(source.state.read().expired(now).is_empty());
+        assert_eq!(source.state.read().expired(now + REFRESH_BACKOFF).len(), 1);
+    }
+
+    #[test]
+    fn test_file_secret_resolver_rejects_traversal() {
+        let dir = secrets_dir();
+        let resolver = FileSecretResolver::new(dir.path().join("db")).unwrap();
+        let reference = SecretReference::parse("secret://file/../api_token").unwrap();
+        assert!(resolver.resolve(&reference).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! State shared between adapters and their watchers.
+//!
+//! This module provides the handle through which an adapter and the watchers
+//! or background threads it creates read and update the same cache.
+
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Handle to state shared between an adapter and its watchers.
+///
+/// Clones refer to the same state. Locks are taken even if a thread panicked
+/// while holding them, since a poisoned lock still holds a usable cache.
+#[derive(Debug, Default)]
+pub(crate) struct Shared<T>(Arc<RwLock<T>>);
+
+impl<T> Shared<T> {
+    /// Shares `state`.
+    pub(crate) fn new(state: T) -> Self {
+        Self(Arc::new(RwLock::new(state)))
+    }
+
+    /// Locks the state for reading.
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the state for writing.
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, T> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
@@ -0,0 +1,646 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! HashiCorp Vault secret resolver.
+//!
+//! This module provides a `SecretResolver` that reads secrets from Vault's KV
+//! version 2 secrets engine over its HTTP API, authenticating with a token or
+//! with AppRole and renewing the token before its lease runs out.
+
+use crate::adapters::runtime::block_on;
+use crate::domain::{ConfigError, Result};
+use crate::ports::{ResolvedSecret, SecretReference, SecretResolver};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// How long KV secrets are cached when Vault reports no lease.
+const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// How long a request may take unless overridden.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Creates an error for a failed Vault request.
+fn vault_error(message: String, e: Option<reqwest::Error>) -> ConfigError {
+    ConfigError::SourceError {
+        source_name: "vault".to_string(),
+        message,
+        source: e.map(|e| Box::new(e) as _),
+    }
+}
+
+/// How a [`VaultResolver`] authenticates.
+#[derive(Clone)]
+enum VaultAuth {
+    /// A token issued out of band
+    Token(String),
+    /// AppRole credentials exchanged for a token at the given auth mount
+    AppRole {
+        role_id: String,
+        secret_id: String,
+        mount: String,
+    },
+}
+
+/// Connection options for Vault servers.
+///
+/// Covers authentication, the Vault Enterprise namespace, the request timeout
+/// and how long secrets without a lease are cached.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::{VaultOptions, VaultResolver};
+/// use std::time::Duration;
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// let options = VaultOptions::new()
+///     .with_approle("my-role-id", "my-secret-id")
+///     .with_namespace("team-a")
+///     .with_default_ttl(Duration::from_secs(60));
+///
+/// let resolver = VaultResolver::new("https://vault.internal:8200", options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct VaultOptions {
+    /// Authentication method; required before resolving
+    auth: Option<VaultAuth>,
+    /// Namespace sent as `X-Vault-Namespace`
+    namespace: Option<String>,
+    /// Timeout for each request
+    request_timeout: Option<Duration>,
+    /// How long secrets without a lease are cached
+    default_ttl: Option<Duration>,
+}
+
+impl fmt::Debug for VaultOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let auth = match &self.auth {
+            Some(VaultAuth::Token(_)) => Some("token".to_string()),
+            Some(VaultAuth::AppRole { mount, .. }) => Some(format!("approle ({})", mount)),
+            None => None,
+        };
+        f.debug_struct("VaultOptions")
+            .field("auth", &auth)
+            .field("namespace", &self.namespace)
+            .field("request_timeout", &self.request_timeout())
+            .field("default_ttl", &self.default_ttl())
+            .finish()
+    }
+}
+
+impl VaultOptions {
+    /// Creates options without authentication.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Authenticates with a token (`X-Vault-Token`).
+    ///
+    /// Renewable tokens are renewed before they expire.
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.auth = Some(VaultAuth::Token(token.into()));
+        self
+    }
+
+    /// Authenticates with AppRole credentials at the `approle` auth mount.
+    ///
+    /// The token obtained is renewed before it expires, and the resolver logs
+    /// in again once it can no longer be renewed.
+    pub fn with_approle(
+        mut self,
+        role_id: impl Into<String>,
+        secret_id: impl Into<String>,
+    ) -> Self {
+        self.auth = Some(VaultAuth::AppRole {
+            role_id: role_id.into(),
+            secret_id: secret_id.into(),
+            mount: "approle".to_string(),
+        });
+        self
+    }
+
+    /// Sets the auth mount AppRole logins use, if not `approle`.
+    pub fn with_approle_mount(mut self, mount: impl Into<String>) -> Self {
+        if let Some(VaultAuth::AppRole { mount: current, .. }) = &mut self.auth {
+            *current = mount.into().trim_matches('/').to_string();
+        }
+        self
+    }
+
+    /// Sends requests to a Vault Enterprise namespace (`X-Vault-Namespace`).
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Sets the timeout for each request, 30 seconds by default.
+    ///
+    /// Secrets that expire are resolved again when their key is read, so this
+    /// also bounds how long such a read can block on an unreachable server.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long secrets are cached when Vault reports no lease, as for
+    /// KV version 2 secrets.
+    pub fn with_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    /// Returns how long secrets without a lease are cached, five minutes
+    /// unless overridden.
+    pub fn default_ttl(&self) -> Duration {
+        self.default_ttl.unwrap_or(DEFAULT_TTL)
+    }
+
+    /// Returns the timeout for each request, 30 seconds unless overridden.
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT)
+    }
+}
+
+/// A Vault token and its lease.
+#[derive(Clone)]
+struct VaultToken {
+    /// The token
+    token: String,
+    /// Lease duration; `None` for tokens that do not expire
+    ttl: Option<Duration>,
+    /// Whether the lease can be extended with `renew-self`
+    renewable: bool,
+    /// When the lease started
+    issued_at: Instant,
+}
+
+impl VaultToken {
+    /// Returns whether two thirds of the lease have passed at `now`.
+    fn needs_renewal(&self, now: Instant) -> bool {
+        self.ttl
+            .is_some_and(|ttl| now.duration_since(self.issued_at) >= ttl * 2 / 3)
+    }
+
+    /// Returns whether the lease has run out at `now`.
+    fn is_expired(&self, now: Instant) -> bool {
+        self.ttl
+            .is_some_and(|ttl| now.duration_since(self.issued_at) >= ttl)
+    }
+}
+
+/// The `auth` block of login and renewal responses.
+#[derive(Deserialize)]
+struct AuthResponse {
+    auth: AuthInfo,
+}
+
+#[derive(Deserialize)]
+struct AuthInfo {
+    client_token: String,
+    lease_duration: u64,
+    renewable: bool,
+}
+
+/// Response of `GET /v1/auth/token/lookup-self`.
+#[derive(Deserialize)]
+struct LookupResponse {
+    data: LookupData,
+}
+
+#[derive(Deserialize)]
+struct LookupData {
+    /// Remaining lease in seconds; 0 for tokens that do not expire
+    ttl: u64,
+    #[serde(default)]
+    renewable: bool,
+}
+
+/// Body of an AppRole login request.
+#[derive(Serialize)]
+struct AppRoleLogin<'a> {
+    role_id: &'a str,
+    secret_id: &'a str,
+}
+
+/// Response of `GET /v1/<mount>/data/<path>`.
+#[derive(Deserialize)]
+struct KvResponse {
+    data: KvData,
+    #[serde(default)]
+    lease_duration: u64,
+}
+
+#[derive(Deserialize)]
+struct KvData {
+    data: HashMap<String, KvField>,
+}
+
+/// A field of a KV secret; only scalars can be used as configuration values.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KvField {
+    String(String),
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Other(IgnoredAny),
+}
+
+impl KvField {
+    /// Returns the field as a string, or `None` for objects and arrays.
+    fn into_string(self) -> Option<String> {
+        match self {
+            KvField::String(value) => Some(value),
+            KvField::Bool(value) => Some(value.to_string()),
+            KvField::Integer(value) => Some(value.to_string()),
+            KvField::Float(value) => Some(value.to_string()),
+            KvField::Other(_) => None,
+        }
+    }
+}
+
+/// Secret resolver for HashiCorp Vault's KV version 2 secrets engine.
+///
+/// Handles references naming the `vault` store. The first segment of the path
+/// is the mount of the secrets engine and the rest is the secret's path, so
+/// `secret://vault/kv/db#password` reads the `password` field of
+/// `GET /v1/kv/data/db`. The field may be left out when the secret has a
+/// single field.
+///
+/// Secrets are cached for their lease duration, or for the options' default
+/// TTL when Vault reports none, as it does for KV version 2. Tokens are
+/// renewed once two thirds of their lease have passed; AppRole logins are
+/// repeated when renewal fails or the token was revoked.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::{ResolvingSource, VaultOptions, VaultResolver, YamlFileAdapter};
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// // database.password: secret://vault/kv/myapp/db#password
+/// let resolver = VaultResolver::new(
+///     "https://vault.internal:8200",
+///     VaultOptions::new().with_token("hvs.b1gs33cr3t"),
+/// )?;
+///
+/// let yaml = YamlFileAdapter::from_file("/etc/myapp/config.yaml")?;
+/// let source = ResolvingSource::new(Box::new(yaml), resolver)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct VaultResolver {
+    /// HTTP client
+    http: reqwest::Client,
+    /// Server address, including the scheme and without a trailing slash
+    address: String,
+    /// Connection options
+    options: VaultOptions,
+    /// Current token; obtained on first use
+    token: Mutex<Option<VaultToken>>,
+}
+
+impl fmt::Debug for VaultResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VaultResolver")
+            .field("address", &self.address)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl VaultResolver {
+    /// Creates a resolver for the Vault server at `address`.
+    ///
+    /// Addresses without a scheme use `http://`. No request is made until the
+    /// first secret is resolved.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::SourceError` if the options set no authentication
+    /// method or the HTTP client cannot be created.
+    pub fn new(address: &str, options: VaultOptions) -> Result<Self> {
+        if options.auth.is_none() {
+            return Err(vault_error(
+                "No Vault authentication method configured".to_string(),
+                None,
+            ));
+        }
+
+        let http = reqwest::Client::builder()
+            .timeout(options.request_timeout())
+            .build()
+            .map_err(|e| vault_error(format!("Failed to create HTTP client: {}", e), Some(e)))?;
+
+        let address = address.trim_end_matches('/');
+        let address = if address.contains("://") {
+            address.to_string()
+        } else {
+            format!("http://{}", address)
+        };
+
+        Ok(Self {
+            http,
+            address,
+            options,
+            token: Mutex::new(None),
+        })
+    }
+
+    /// Returns the connection options.
+    pub fn options(&self) -> &VaultOptions {
+        &self.options
+    }
+
+    /// Adds the namespace header to a request.
+    fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+        match &self.options.namespace {
+            Some(namespace) => builder.header("X-Vault-Namespace", namespace),
+            None => builder,
+        }
+    }
+
+    /// Sends a request and decodes a successful JSON response.
+    ///
+    /// `what` describes the request in error messages.
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder, what: &str) -> Result<T> {
+        let response = self
+            .request(request)
+            .send()
+            .await
+            .map_err(|e| vault_error(format!("Failed to {}: {}", what, e), Some(e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(vault_error(
+                format!(
+                    "Vault returned {} when trying to {}: {}",
+                    status,
+                    what,
+                    body.trim()
+                ),
+                None,
+            ));
+        }
+        response.json().await.map_err(|e| {
+            vault_error(
+                format!("Invalid response from Vault to {}: {}", what, e),
+                Some(e),
+            )
+        })
+    }
+
+    /// Obtains a new token with the configured authentication method.
+    async fn authenticate(&self) -> Result<VaultToken> {
+        let issued_at = Instant::now();
+        match self.options.auth.as_ref() {
+            Some(VaultAuth::Token(token)) => {
+                let lookup: LookupResponse = self
+                    .send(
+                        self.http
+                            .get(format!("{}/v1/auth/token/lookup-self", self.address))
+                            .header("X-Vault-Token", token),
+                        "look up the Vault token",
+                    )
+                    .await?;
+                Ok(VaultToken {
+                    token: token.clone(),
+                    ttl: (lookup.data.ttl > 0).then(|| Duration::from_secs(lookup.data.ttl)),
+                    renewable: lookup.data.renewable,
+                    issued_at,
+                })
+            }
+            Some(VaultAuth::AppRole {
+                role_id,
+                secret_id,
+                mount,
+            }) => {
+                let login: AuthResponse = self
+                    .send(
+                        self.http
+                            .post(format!("{}/v1/auth/{}/login", self.address, mount))
+                            .json(&AppRoleLogin { role_id, secret_id }),
+                        "log in to Vault with AppRole",
+                    )
+                    .await?;
+                tracing::debug!("Logged in to Vault with AppRole");
+                Ok(Self::token_from(login.auth, issued_at))
+            }
+            None => Err(vault_error(
+                "No Vault authentication method configured".to_string(),
+                None,
+            )),
+        }
+    }
+
+    /// Extends the lease of `token`.
+    async fn renew(&self, token: &VaultToken) -> Result<VaultToken> {
+        let issued_at = Instant::now();
+        let renewed: AuthResponse = self
+            .send(
+                self.http
+                    .post(format!("{}/v1/auth/token/renew-self", self.address))
+                    .header("X-Vault-Token", &token.token)
+                    .json(&HashMap::<String, String>::new()),
+                "renew the Vault token",
+            )
+            .await?;
+        tracing::debug!("Renewed Vault token lease");
+        Ok(Self::token_from(renewed.auth, issued_at))
+    }
+
+    /// Creates a token from an `auth` block.
+    fn token_from(auth: AuthInfo, issued_at: Instant) -> VaultToken {
+        VaultToken {
+            token: auth.client_token,
+            ttl: (auth.lease_duration > 0).then(|| Duration::from_secs(auth.lease_duration)),
+            renewable: auth.renewable,
+            issued_at,
+        }
+    }
+
+    /// Returns a valid token, logging in or renewing the lease as needed.
+    async fn token(&self) -> Result<String> {
+        let current = self
+            .token
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let now = Instant::now();
+
+        let token = match current {
+            Some(token) if !token.needs_renewal(now) => return Ok(token.token),
+            Some(token) if token.renewable && !token.is_expired(now) => {
+                match self.renew(&token).await {
+                    Ok(renewed) => renewed,
+                    Err(e) if matches!(self.options.auth, Some(VaultAuth::AppRole { .. })) => {
+                        tracing::warn!("Logging in to Vault again: {}", e);
+                        self.authenticate().await?
+                    }
+                    // A token that cannot be renewed is used until it expires
+                    Err(e) => {
+                        tracing::warn!("{}", e);
+                        return Ok(token.token);
+                    }
+                }
+            }
+            Some(token) if !token.is_expired(now) => return Ok(token.token),
+            _ => self.authenticate().await?,
+        };
+
+        let value = token.token.clone();
+        *self.token.lock().unwrap_or_else(PoisonError::into_inner) = Some(token);
+        Ok(value)
+    }
+
+    /// Forgets the current token so the next request obtains a new one.
+    fn forget_token(&self) {
+        *self.token.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// Reads the secret `reference` points to.
+    async fn read(&self, reference: &SecretReference) -> Result<ResolvedSecret> {
+        let (mount, path) = reference
+            .path()
+            .split_once('/')
+            .filter(|(mount, path)| !mount.is_empty() && !path.is_empty())
+            .ok_or_else(|| {
+                vault_error(
+                    format!("Secret path must start with the KV mount: {}", reference),
+                    None,
+                )
+            })?;
+        let url = format!("{}/v1/{}/data/{}", self.address, mount, path);
+
+        let mut retried = false;
+        let response = loop {
+            let token = self.token().await?;
+            let response = self
+                .request(self.http.get(&url).header("X-Vault-Token", token))
+                .send()
+                .await
+                .map_err(|e| {
+                    vault_error(
+                        format!("Failed to read secret {}: {}", reference, e),
+                        Some(e),
+                    )
+                })?;
+
+            // A revoked AppRole token is replaced by logging in again once
+            let relogin = matches!(self.options.auth, Some(VaultAuth::AppRole { .. }));
+            if response.status() == StatusCode::FORBIDDEN && relogin && !retried {
+                self.forget_token();
+                retried = true;
+                continue;
+            }
+            break response;
+        };
+
+        let kv: KvResponse = match response.status() {
+            StatusCode::NOT_FOUND => {
+                return Err(vault_error(
+                    format!("Secret not found: {}", reference),
+                    None,
+                ))
+            }
+            status if status.is_success() => response.json().await.map_err(|e| {
+                vault_error(
+                    format!("Invalid response from Vault for {}: {}", reference, e),
+                    Some(e),
+                )
+            })?,
+            status => {
+                return Err(vault_error(
+                    format!("Vault returned {} for {}", status, reference),
+                    None,
+                ))
+            }
+        };
+
+        let mut fields = kv.data.data;
+        let field = match reference.field() {
+            Some(field) => fields.remove(field),
+            None if fields.len() == 1 => fields.into_values().next(),
+            None => {
+                return Err(vault_error(
+                    format!(
+                        "Secret {} has {} fields; name one with #<field>",
+                        reference,
+                        fields.len()
+                    ),
+                    None,
+                ))
+            }
+        };
+        let value = field
+            .ok_or_else(|| vault_error(format!("Secret field not found: {}", reference), None))?
+            .into_string()
+            .ok_or_else(|| {
+                vault_error(format!("Secret field is not a scalar: {}", reference), None)
+            })?;
+
+        let ttl = match kv.lease_duration {
+            0 => self.options.default_ttl(),
+            seconds => Duration::from_secs(seconds),
+        };
+        Ok(ResolvedSecret::new(value).with_ttl(ttl))
+    }
+}
+
+impl SecretResolver for VaultResolver {
+    fn name(&self) -> &str {
+        "vault"
+    }
+
+    fn resolve(&self, reference: &SecretReference) -> Result<ResolvedSecret> {
+        block_on(self.read(reference))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_requires_authentication() {
+        assert!(VaultResolver::new("127.0.0.1:8200", VaultOptions::new()).is_err());
+    }
+
+    #[test]
+    fn test_vault_options_debug_is_redacted() {
+        let options = VaultOptions::new()
+            .with_approle("role", "s3cr3t-id")
+            .with_approle_mount("/custom/");
+        let debug = format!("{:?}", options);
+        assert!(!debug.contains("s3cr3t-id"));
+        assert!(debug.contains("approle (custom)"));
+
+        let debug = format!("{:?}", VaultOptions::new().with_token("hvs.s3cr3t"));
+        assert!(!debug.contains("hvs.s3cr3t"));
+    }
+
+    #[test]
+    fn test_vault_token_renewal_schedule() {
+        let issued_at = Instant::now();
+        let token = VaultToken {
+            token: "t".to_string(),
+            ttl: Some(Duration::from_secs(30)),
+            renewable: true,
+            issued_at,
+        };
+        assert!(!token.needs_renewal(issued_at + Duration::from_secs(10)));
+        assert!(token.needs_renewal(issued_at + Duration::from_secs(20)));
+        assert!(!token.is_expired(issued_at + Duration::from_secs(20)));
+        assert!(token.is_expired(issued_at + Duration::from_secs(30)));
+
+        let root = VaultToken { ttl: None, ..token };
+        assert!(!root.needs_renewal(issued_at + Duration::from_secs(3600)));
+    }
+}
@@ -0,0 +1,251 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Consul configuration watcher.
+//!
+//! This module provides a watcher that monitors Consul's KV store for
+//! configuration changes using blocking queries.
+
+use crate::adapters::consul::{ConsulClient, SharedConsulState};
+use crate::adapters::ConsulConnectOptions;
+use crate::domain::{ConfigError, ConfigKey, Result};
+use crate::ports::{ChangeCallback, ConfigWatcher};
+use std::sync::mpsc::{channel, Sender};
+use std::thread::{self, JoinHandle};
+
+/// Watcher for Consul configuration changes.
+///
+/// This watcher issues blocking queries against the prefix, passing the last
+/// `X-Consul-Index` it saw. Consul answers when something under the prefix
+/// changes or the wait time runs out; the watcher compares the result with
+/// what it had and calls the callback for every key that changed.
+///
+/// Use [`ConsulAdapter::watcher`](crate::adapters::ConsulAdapter::watcher) to
+/// start from the adapter's snapshot and keep its cache up to date.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::ConsulWatcher;
+/// use hexcfg::ports::ConfigWatcher;
+/// use std::sync::Arc;
+///
+/// # #[tokio::main]
+/// # async fn main() -> hexcfg::domain::Result<()> {
+/// let mut watcher = ConsulWatcher::new("http://localhost:8500", Some("myapp/")).await?;
+///
+/// watcher.watch(Arc::new(|key| {
+///     println!("Configuration changed: {}", key);
+/// }))?;
+///
+/// // Later, stop watching
+/// watcher.stop()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConsulWatcher {
+    /// Consul agent address
+    address: String,
+    /// Connection options
+    options: ConsulConnectOptions,
+    /// Key prefix to watch
+    prefix: Option<String>,
+    /// Values and index seen so far, shared with an adapter if created by one
+    state: SharedConsulState,
+    /// Stop signal sender
+    stop_tx: Option<Sender<()>>,
+    /// Watch thread handle
+    watch_thread: Option<JoinHandle<()>>,
+}
+
+impl ConsulWatcher {
+    /// Creates a new Consul watcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Agent address (e.g., `"http://localhost:8500"`)
+    /// * `prefix` - Optional key prefix to watch (e.g., `"myapp/"`)
+    pub async fn new(address: &str, prefix: Option<&str>) -> Result<Self> {
+        Self::with_options(address, prefix, ConsulConnectOptions::new()).await
+    }
+
+    /// Creates a new Consul watcher with an ACL token, datacenter or timeouts.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Agent address
+    /// * `prefix` - Optional key prefix to watch
+    /// * `options` - Connection options
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{ConsulConnectOptions, ConsulWatcher};
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let options = ConsulConnectOptions::new()
+    ///     .with_token("b1gs33cr3t")
+    ///     .with_wait_time(Duration::from_secs(60));
+    /// let watcher =
+    ///     ConsulWatcher::with_options("http://localhost:8500", Some("myapp/"), options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_options(
+        address: &str,
+        prefix: Option<&str>,
+        options: ConsulConnectOptions,
+    ) -> Result<Self> {
+        // Test connection
+        let client = ConsulClient::new(address, options.clone())?;
+        client
+            .fetch(prefix.unwrap_or(""), None)
+            .await
+            .map_err(|e| ConfigError::WatcherError {
+                message: format!("Failed to connect to Consul: {}", e),
+                source: Some(Box::new(e)),
+            })?;
+
+        Ok(Self::with_state(
+            address.to_string(),
+            options,
+            prefix.map(|s| s.to_string()),
+            SharedConsulState::default(),
+        ))
+    }
+
+    /// Creates a watcher that applies changes to an existing snapshot.
+    pub(crate) fn with_state(
+        address: String,
+        options: ConsulConnectOptions,
+        prefix: Option<String>,
+        state: SharedConsulState,
+    ) -> Self {
+        Self {
+            address,
+            options,
+            prefix,
+            state,
+            stop_tx: None,
+            watch_thread: None,
+        }
+    }
+}
+
+impl ConfigWatcher for ConsulWatcher {
+    fn watch(&mut self, callback: ChangeCallback) -> Result<()> {
+        if self.watch_thread.is_some() {
+            return Err(ConfigError::WatcherError {
+                message: "Watcher is already running".to_string(),
+                source: None,
+            });
+        }
+
+        let client = ConsulClient::new(&self.address, self.options.clone())?;
+
+        let (stop_tx, stop_rx) = channel();
+        self.stop_tx = Some(stop_tx);
+
+        let prefix = self.prefix.clone();
+        let state = self.state.clone();
+
+        let watch_thread = thread::spawn(move || {
+            // Create a new runtime for this thread
+            let runtime = match tokio::runtime::Runtime::new() {
+                Ok(rt) => rt,
+                Err(e) => {
+                    tracing::error!("Failed to create tokio runtime for Consul watcher: {}", e);
+                    return;
+                }
+            };
+
+            runtime.block_on(async move {
+                let watch_prefix = prefix.as_deref().unwrap_or("");
+                // Index of the last result seen; 0 until the prefix is loaded
+                let mut last_index = state.read().index();
+
+                loop {
+                    // Check for stop signal
+                    if stop_rx.try_recv().is_ok() {
+                        tracing::debug!("Consul watcher stopping");
+                        break;
+                    }
+
+                    let wait_index = (last_index > 0).then_some(last_index);
+                    let request = client.fetch(watch_prefix, wait_index);
+                    tokio::pin!(request);
+
+                    // Wait for the blocking query while checking for the stop signal
+                    let result = loop {
+                        if stop_rx.try_recv().is_ok() {
+                            tracing::debug!("Consul watcher stopping");
+                            return;
+                        }
+
+                        tokio::select! {
+                            result = &mut request => break result,
+                            _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {
+                                // Timeout to check stop signal
+                            }
+                        }
+                    };
+
+                    let snapshot = match result {
+                        Ok(snapshot) => snapshot,
+                        Err(e) => {
+                            tracing::error!("Consul blocking query failed: {}", e);
+                            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                            continue;
+                        }
+                    };
+
+                    // An unchanged index means the wait time ran out
+                    if wait_index == Some(snapshot.index()) {
+                        continue;
+                    }
+
+                    let index = snapshot.index();
+                    let changed = state.write().replace(snapshot);
+                    if wait_index.is_some() {
+                        for key in changed {
+                            tracing::debug!("Consul key changed: {}", key);
+                            callback(ConfigKey::from(key));
+                        }
+                    }
+
+                    // Consul asks clients to never block on index 0
+                    last_index = index.max(1);
+                }
+            });
+        });
+
+        self.watch_thread = Some(watch_thread);
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        // Send stop signal
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+
+        // Wait for the thread to finish
+        if let Some(handle) = self.watch_thread.take() {
+            handle.join().map_err(|_| ConfigError::WatcherError {
+                message: "Failed to join Consul watcher thread".to_string(),
+                source: None,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for ConsulWatcher {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
@@ -5,9 +5,11 @@
 //! This module provides a watcher that monitors etcd for configuration changes
 //! using etcd's native watch API.
 
+use crate::adapters::etcd::{fetch_snapshot, SharedEtcdState};
+use crate::adapters::EtcdConnectOptions;
 use crate::domain::{ConfigError, ConfigKey, Result};
 use crate::ports::{ChangeCallback, ConfigWatcher};
-use etcd_client::{Client, WatchOptions};
+use etcd_client::WatchOptions;
 use std::sync::mpsc::{channel, Sender};
 use std::thread::{self, JoinHandle};
 
@@ -17,6 +19,13 @@ use std::thread::{self, JoinHandle};
 /// when configuration values change. It monitors all keys with a specified prefix
 /// and triggers callbacks when changes are detected.
 ///
+/// The watcher tracks the etcd revision it has seen. It loads the prefix when it
+/// starts and watches from the following revision, and after a reconnect it
+/// resumes where it left off, so no change is missed. If etcd has compacted those
+/// revisions, it reloads the prefix and calls the callback for every key that
+/// changed in between. Use [`EtcdAdapter::watcher`](crate::adapters::EtcdAdapter::watcher)
+/// to share the snapshot with an adapter and keep its cache up to date.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -44,8 +53,12 @@ use std::thread::{self, JoinHandle};
 pub struct EtcdWatcher {
     /// etcd endpoints
     endpoints: Vec<String>,
+    /// Connection options, used for every reconnect
+    options: EtcdConnectOptions,
     /// Key prefix to watch
     prefix: Option<String>,
+    /// Values and revision seen so far, shared with an adapter if created by one
+    state: SharedEtcdState,
     /// Stop signal sender
     stop_tx: Option<Sender<()>>,
     /// Watch thread handle
@@ -75,23 +88,70 @@ impl EtcdWatcher {
     /// # }
     /// ```
     pub async fn new<S: AsRef<str>>(endpoints: Vec<S>, prefix: Option<&str>) -> Result<Self> {
+        Self::with_options(endpoints, prefix, EtcdConnectOptions::new()).await
+    }
+
+    /// Creates a new etcd watcher with authentication, TLS or timeout options.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - List of etcd endpoints
+    /// * `prefix` - Optional key prefix to watch
+    /// * `options` - Connection options, also used when reconnecting
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{EtcdConnectOptions, EtcdWatcher};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let options = EtcdConnectOptions::new()
+    ///     .with_ca_cert_file("/etc/etcd/ca.pem")?;
+    /// let watcher = EtcdWatcher::with_options(vec!["etcd.internal:2379"], Some("myapp/"), options)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_options<S: AsRef<str>>(
+        endpoints: Vec<S>,
+        prefix: Option<&str>,
+        options: EtcdConnectOptions,
+    ) -> Result<Self> {
         let endpoints: Vec<String> = endpoints.iter().map(|s| s.as_ref().to_string()).collect();
 
         // Test connection
-        let _client =
-            Client::connect(&endpoints, None)
-                .await
-                .map_err(|e| ConfigError::WatcherError {
-                    message: format!("Failed to connect to etcd: {}", e),
-                    source: Some(Box::new(e)),
-                })?;
-
-        Ok(Self {
+        let _client = options
+            .connect(&endpoints)
+            .await
+            .map_err(|e| ConfigError::WatcherError {
+                message: format!("Failed to connect to etcd: {}", e),
+                source: Some(Box::new(e)),
+            })?;
+
+        Ok(Self::with_state(
             endpoints,
-            prefix: prefix.map(|s| s.to_string()),
+            options,
+            prefix.map(|s| s.to_string()),
+            SharedEtcdState::default(),
+        ))
+    }
+
+    /// Creates a watcher that applies changes to an existing snapshot.
+    pub(crate) fn with_state(
+        endpoints: Vec<String>,
+        options: EtcdConnectOptions,
+        prefix: Option<String>,
+        state: SharedEtcdState,
+    ) -> Self {
+        Self {
+            endpoints,
+            options,
+            prefix,
+            state,
             stop_tx: None,
             watch_thread: None,
-        })
+        }
     }
 }
 
@@ -108,7 +168,9 @@ impl ConfigWatcher for EtcdWatcher {
         self.stop_tx = Some(stop_tx);
 
         let endpoints = self.endpoints.clone();
+        let options = self.options.clone();
         let prefix = self.prefix.clone();
+        let state = self.state.clone();
 
         let watch_thread = thread::spawn(move || {
             // Create a new runtime for this thread
@@ -121,6 +183,13 @@ impl ConfigWatcher for EtcdWatcher {
             };
 
             runtime.block_on(async move {
+                let watch_prefix = prefix.as_deref().unwrap_or("");
+                let separator = options.key_separator();
+                // Last revision delivered to the callback, resumed after reconnects
+                let mut last_revision = state.read().revision();
+                // Set when the revisions after `last_revision` have been compacted
+                let mut resync = false;
+
                 loop {
                     // Check for stop signal
                     if stop_rx.try_recv().is_ok() {
@@ -129,7 +198,7 @@ impl ConfigWatcher for EtcdWatcher {
                     }
 
                     // Connect to etcd
-                    let mut client = match Client::connect(&endpoints, None).await {
+                    let mut client = match options.connect(&endpoints).await {
                         Ok(c) => c,
                         Err(e) => {
                             tracing::error!("Failed to connect to etcd for watching: {}", e);
@@ -138,21 +207,53 @@ impl ConfigWatcher for EtcdWatcher {
                         }
                     };
 
-                    let watch_prefix = prefix.as_deref().unwrap_or("");
-                    tracing::info!("Starting etcd watch on prefix: {}", watch_prefix);
+                    // Load a snapshot to start from, or to catch up after compaction
+                    if last_revision == 0 || resync {
+                        let snapshot = match fetch_snapshot(&mut client, prefix.as_deref(), &separator).await
+                        {
+                            Ok(snapshot) => snapshot,
+                            Err(e) => {
+                                tracing::error!("Failed to load etcd snapshot for watching: {}", e);
+                                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                                continue;
+                            }
+                        };
 
-                    // Create watch stream
-                    let options = WatchOptions::new().with_prefix();
-                    let (mut _watcher, mut stream) = match client.watch(watch_prefix, Some(options)).await {
-                        Ok((w, s)) => (w, s),
-                        Err(e) => {
-                            tracing::error!("Failed to create etcd watch: {}", e);
-                            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                            continue;
+                        let mut guard = state.write();
+                        let changed = guard.replace(snapshot);
+                        last_revision = guard.revision();
+                        drop(guard);
+
+                        if resync {
+                            for key in changed {
+                                tracing::debug!("etcd key changed during compaction: {}", key);
+                                callback(ConfigKey::from(key));
+                            }
+                            resync = false;
                         }
-                    };
+                    }
+
+                    tracing::info!(
+                        "Starting etcd watch on prefix '{}' at revision {}",
+                        watch_prefix,
+                        last_revision + 1
+                    );
 
-                    // Process watch events
+                    // Create watch stream
+                    let watch_options = WatchOptions::new()
+                        .with_prefix()
+                        .with_start_revision(last_revision + 1);
+                    let (mut _watcher, mut stream) =
+                        match client.watch(watch_prefix, Some(watch_options)).await {
+                            Ok((w, s)) => (w, s),
+                            Err(e) => {
+                                tracing::error!("Failed to create etcd watch: {}", e);
+                                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                                continue;
+                            }
+                        };
+
+                    // Process watch events until the stream ends
                     loop {
                         // Check for stop signal
                         if stop_rx.try_recv().is_ok() {
@@ -161,26 +262,46 @@ impl ConfigWatcher for EtcdWatcher {
                         }
 
                         tokio::select! {
-                            Ok(resp) = stream.message() => {
-                                if let Some(watch_resp) = resp {
-                                    for event in watch_resp.events() {
-                                        if let Some(kv) = event.kv() {
-                                            if let Ok(key_str) = kv.key_str() {
-                                                // Strip prefix from key
-                                                let key = if !watch_prefix.is_empty() && key_str.starts_with(watch_prefix) {
-                                                    &key_str[watch_prefix.len()..]
-                                                } else {
-                                                    key_str
-                                                };
-
-                                                // Convert slashes to dots for consistency
-                                                let key = key.replace('/', ".");
-
-                                                tracing::debug!("etcd key changed: {}", key);
-                                                callback(ConfigKey::from(key));
-                                            }
-                                        }
+                            message = stream.message() => {
+                                let watch_resp = match message {
+                                    Ok(Some(resp)) => resp,
+                                    Ok(None) => {
+                                        tracing::warn!("etcd watch stream closed, reconnecting");
+                                        break;
                                     }
+                                    Err(e) => {
+                                        tracing::warn!("etcd watch failed, reconnecting: {}", e);
+                                        break;
+                                    }
+                                };
+
+                                if watch_resp.compact_revision() > 0 {
+                                    tracing::warn!(
+                                        "etcd compacted revisions up to {}, resynchronizing",
+                                        watch_resp.compact_revision()
+                                    );
+                                    resync = true;
+                                    break;
+                                }
+                                if watch_resp.canceled() {
+                                    tracing::warn!(
+                                        "etcd watch canceled, reconnecting: {}",
+                                        watch_resp.cancel_reason()
+                                    );
+                                    break;
+                                }
+
+                                let events = watch_resp.events();
+                                let keys = state.write().apply_events(watch_prefix, &separator, events);
+                                for event in events {
+                                    if let Some(kv) = event.kv() {
+                                        last_revision = last_revision.max(kv.mod_revision());
+                                    }
+                                }
+
+                                for key in keys {
+                                    tracing::debug!("etcd key changed: {}", key);
+                                    callback(ConfigKey::from(key));
                                 }
                             }
                             _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! HTTP polling configuration watcher.
+//!
+//! This module provides a watcher that polls a configuration document served
+//! over HTTP(S) and reports the keys whose values changed.
+
+use crate::adapters::http::{HttpClient, SharedHttpState};
+use crate::adapters::HttpOptions;
+use crate::domain::{ConfigError, ConfigKey, Result};
+use crate::ports::{ChangeCallback, ConfigWatcher};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Watcher for configuration documents served over HTTP(S).
+///
+/// HTTP has no change notifications, so this watcher downloads the document
+/// every `interval` with `If-None-Match` and `If-Modified-Since` set from the
+/// previous response. Unchanged documents are answered with `304 Not Modified`.
+/// The callback only runs for keys whose values actually changed, so a server
+/// that ignores the validators and resends the same document triggers nothing.
+///
+/// Use [`HttpAdapter::watcher`](crate::adapters::HttpAdapter::watcher) to share
+/// the downloaded document with an adapter and keep its values up to date.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::HttpPollingWatcher;
+/// use hexcfg::ports::ConfigWatcher;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// # #[tokio::main]
+/// # async fn main() -> hexcfg::domain::Result<()> {
+/// let mut watcher = HttpPollingWatcher::new(
+///     "https://config.internal/myapp.yaml",
+///     Duration::from_secs(30),
+/// ).await?;
+///
+/// watcher.watch(Arc::new(|key| {
+///     println!("Configuration changed: {}", key);
+/// }))?;
+///
+/// // Later, stop watching
+/// watcher.stop()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct HttpPollingWatcher {
+    /// Client for the watched document
+    client: HttpClient,
+    /// Time between polls
+    interval: Duration,
+    /// Document values and validators, shared with an adapter if created by one
+    state: SharedHttpState,
+    /// Stop signal sender
+    stop_tx: Option<Sender<()>>,
+    /// Watch thread handle
+    watch_thread: Option<JoinHandle<()>>,
+}
+
+impl HttpPollingWatcher {
+    /// Creates a new HTTP polling watcher.
+    ///
+    /// The document is downloaded once so later polls can tell what changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Document URL
+    /// * `interval` - Time between polls
+    pub async fn new(url: &str, interval: Duration) -> Result<Self> {
+        Self::with_options(url, HttpOptions::new(), interval).await
+    }
+
+    /// Creates a new HTTP polling watcher with custom headers, authentication
+    /// or parsers.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Document URL
+    /// * `options` - Request options
+    /// * `interval` - Time between polls
+    pub async fn with_options(url: &str, options: HttpOptions, interval: Duration) -> Result<Self> {
+        let client = HttpClient::new(url, options)?;
+        let state = SharedHttpState::default();
+
+        client
+            .fetch(&state)
+            .await
+            .map_err(|e| ConfigError::WatcherError {
+                message: format!("Failed to fetch {}: {}", url, e),
+                source: Some(Box::new(e)),
+            })?;
+
+        Ok(Self::with_state(client, interval, state))
+    }
+
+    /// Creates a watcher that applies changes to an existing download.
+    pub(crate) fn with_state(
+        client: HttpClient,
+        interval: Duration,
+        state: SharedHttpState,
+    ) -> Self {
+        Self {
+            client,
+            interval,
+            state,
+            stop_tx: None,
+            watch_thread: None,
+        }
+    }
+}
+
+impl ConfigWatcher for HttpPollingWatcher {
+    fn watch(&mut self, callback: ChangeCallback) -> Result<()> {
+        if self.watch_thread.is_some() {
+            return Err(ConfigError::WatcherError {
+                message: "Watcher is already running".to_string(),
+                source: None,
+            });
+        }
+
+        let (stop_tx, stop_rx) = channel();
+        self.stop_tx = Some(stop_tx);
+
+        let client = self.client.clone();
+        let interval = self.interval;
+        let state = self.state.clone();
+
+        let watch_thread = thread::spawn(move || {
+            // Create a new runtime for this thread
+            let runtime = match tokio::runtime::Runtime::new() {
+                Ok(rt) => rt,
+                Err(e) => {
+                    tracing::error!("Failed to create tokio runtime for HTTP watcher: {}", e);
+                    return;
+                }
+            };
+
+            loop {
+                // Wait for the next poll, stopping early on the stop signal
+                match stop_rx.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => {
+                        tracing::debug!("HTTP watcher stopping");
+                        break;
+                    }
+                }
+
+                match runtime.block_on(client.fetch(&state)) {
+                    Ok(keys) => {
+                        for key in keys {
+                            tracing::debug!("HTTP configuration key changed: {}", key);
+                            callback(ConfigKey::from(key));
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to poll {}: {}", client.url(), e);
+                    }
+                }
+            }
+        });
+
+        self.watch_thread = Some(watch_thread);
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        // Send stop signal
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+
+        // Wait for the thread to finish
+        if let Some(handle) = self.watch_thread.take() {
+            handle.join().map_err(|_| ConfigError::WatcherError {
+                message: "Failed to join HTTP watcher thread".to_string(),
+                source: None,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for HttpPollingWatcher {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
@@ -0,0 +1,259 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Key-per-file directory watcher.
+//!
+//! This module provides a watcher for directories with one file per
+//! configuration key, including Kubernetes volumes that are updated by
+//! swapping the `..data` symlink.
+
+use crate::adapters::key_per_file::{scan, SharedKeyPerFileState};
+use crate::domain::{ConfigError, ConfigKey, Result};
+use crate::ports::{ChangeCallback, ConfigWatcher};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Watcher for directories with one file per configuration key.
+///
+/// Kubernetes updates a mounted ConfigMap or Secret by writing a new
+/// timestamped directory and renaming a `..data_tmp` symlink over `..data`.
+/// None of the events involve the paths of the visible files, so
+/// [`FileWatcher`](crate::adapters::FileWatcher) does not notice the update.
+/// This watcher treats any event in the directory as a reason to rescan it,
+/// waits until events settle for the debounce delay, and calls the callback
+/// for every key whose value changed. Events that leave the values unchanged
+/// trigger nothing.
+///
+/// Use [`KeyPerFileAdapter::watcher`](crate::adapters::KeyPerFileAdapter::watcher)
+/// to share the values with an adapter, or to watch nested directories.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::KeyPerFileWatcher;
+/// use hexcfg::ports::ConfigWatcher;
+/// use std::sync::Arc;
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// let mut watcher = KeyPerFileWatcher::new("/etc/myapp", None)?;
+///
+/// watcher.watch(Arc::new(|key| {
+///     println!("Configuration changed: {}", key);
+/// }))?;
+///
+/// // Later, stop watching
+/// watcher.stop()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct KeyPerFileWatcher {
+    /// Directory being watched
+    dir: PathBuf,
+    /// Whether subdirectories are read as dotted prefixes
+    nested: bool,
+    /// Quiet period after the last event before rescanning (default 500ms)
+    debounce_delay: Duration,
+    /// Values read from the directory, shared with an adapter if created by one
+    state: SharedKeyPerFileState,
+    /// Internal watcher
+    watcher: Option<RecommendedWatcher>,
+    /// Stop signal sender
+    stop_tx: Option<Sender<()>>,
+    /// Watch thread handle
+    watch_thread: Option<JoinHandle<()>>,
+}
+
+impl KeyPerFileWatcher {
+    /// Creates a new watcher for the files directly in `dir`.
+    ///
+    /// The directory is read once so later changes can be compared against it.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory holding one file per key
+    /// * `debounce_delay` - Optional debounce delay (default 500ms)
+    pub fn new(dir: impl AsRef<Path>, debounce_delay: Option<Duration>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+
+        if !dir.is_dir() {
+            return Err(ConfigError::WatcherError {
+                message: format!("Directory does not exist: {}", dir.display()),
+                source: None,
+            });
+        }
+
+        let state = SharedKeyPerFileState::default();
+        let values = scan(&dir, false).map_err(|e| ConfigError::WatcherError {
+            message: format!("Failed to read {}: {}", dir.display(), e),
+            source: Some(Box::new(e)),
+        })?;
+        state.write().replace(values);
+
+        Ok(Self::with_state(
+            dir,
+            false,
+            debounce_delay.unwrap_or(Duration::from_millis(500)),
+            state,
+        ))
+    }
+
+    /// Creates a watcher that applies changes to values already read.
+    pub(crate) fn with_state(
+        dir: PathBuf,
+        nested: bool,
+        debounce_delay: Duration,
+        state: SharedKeyPerFileState,
+    ) -> Self {
+        Self {
+            dir,
+            nested,
+            debounce_delay,
+            state,
+            watcher: None,
+            stop_tx: None,
+            watch_thread: None,
+        }
+    }
+}
+
+impl ConfigWatcher for KeyPerFileWatcher {
+    fn watch(&mut self, callback: ChangeCallback) -> Result<()> {
+        if self.watch_thread.is_some() {
+            return Err(ConfigError::WatcherError {
+                message: "Watcher is already running".to_string(),
+                source: None,
+            });
+        }
+
+        let (event_tx, event_rx) = channel();
+        let (stop_tx, stop_rx) = channel::<()>();
+
+        let mut watcher =
+            RecommendedWatcher::new(event_tx, notify::Config::default()).map_err(|e| {
+                ConfigError::WatcherError {
+                    message: format!("Failed to create file watcher: {}", e),
+                    source: Some(Box::new(e)),
+                }
+            })?;
+
+        // The `..data` swap happens directly in the directory, so nested
+        // directories only need a recursive watch for plain updates
+        let mode = if self.nested {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher
+            .watch(&self.dir, mode)
+            .map_err(|e| ConfigError::WatcherError {
+                message: format!("Failed to start watching: {}", e),
+                source: Some(Box::new(e)),
+            })?;
+
+        self.watcher = Some(watcher);
+        self.stop_tx = Some(stop_tx);
+
+        let dir = self.dir.clone();
+        let nested = self.nested;
+        let debounce_delay = self.debounce_delay;
+        let state = self.state.clone();
+
+        let watch_thread = thread::spawn(move || {
+            let mut last_event_time: Option<Instant> = None;
+
+            loop {
+                // Check for stop signal (non-blocking)
+                if stop_rx.try_recv().is_ok() {
+                    break;
+                }
+
+                match event_rx.recv_timeout(Duration::from_millis(100)) {
+                    Ok(Ok(_)) => last_event_time = Some(Instant::now()),
+                    Ok(Err(e)) => tracing::warn!("File watcher error: {}", e),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                // Debounce: rescan once events have settled
+                let settled = last_event_time
+                    .map(|last| last.elapsed() >= debounce_delay)
+                    .unwrap_or(false);
+                if !settled {
+                    continue;
+                }
+                last_event_time = None;
+
+                match scan(&dir, nested) {
+                    Ok(values) => {
+                        let changed = state.write().replace(values);
+                        for key in changed {
+                            tracing::debug!("Key-per-file configuration key changed: {}", key);
+                            callback(ConfigKey::from(key));
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to rescan {}: {}", dir.display(), e);
+                    }
+                }
+            }
+        });
+
+        self.watch_thread = Some(watch_thread);
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        // Send stop signal
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+
+        // Wait for the thread to finish
+        if let Some(handle) = self.watch_thread.take() {
+            handle.join().map_err(|_| ConfigError::WatcherError {
+                message: "Failed to join key-per-file watcher thread".to_string(),
+                source: None,
+            })?;
+        }
+
+        // Drop the watcher
+        self.watcher = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for KeyPerFileWatcher {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_key_per_file_watcher_missing_directory() {
+        let watcher = KeyPerFileWatcher::new("/nonexistent/hexcfg/secrets", None);
+        assert!(watcher.is_err());
+    }
+
+    #[test]
+    fn test_key_per_file_watcher_double_watch() {
+        let dir = TempDir::new().unwrap();
+        let mut watcher = KeyPerFileWatcher::new(dir.path(), None).unwrap();
+
+        let callback = std::sync::Arc::new(|_key: ConfigKey| {});
+
+        assert!(watcher.watch(callback.clone()).is_ok());
+        assert!(watcher.watch(callback).is_err());
+
+        watcher.stop().unwrap();
+    }
+}
@@ -11,14 +11,38 @@ pub mod file_watcher;
 #[cfg(feature = "reload")]
 pub use file_watcher::FileWatcher;
 
+#[cfg(feature = "consul")]
+pub mod consul_watcher;
+
+#[cfg(feature = "consul")]
+pub use consul_watcher::ConsulWatcher;
+
 #[cfg(feature = "etcd")]
 pub mod etcd_watcher;
 
 #[cfg(feature = "etcd")]
 pub use etcd_watcher::EtcdWatcher;
 
+#[cfg(feature = "http")]
+pub mod http_polling_watcher;
+
+#[cfg(feature = "http")]
+pub use http_polling_watcher::HttpPollingWatcher;
+
+#[cfg(all(feature = "key-per-file", feature = "reload"))]
+pub mod key_per_file_watcher;
+
+#[cfg(all(feature = "key-per-file", feature = "reload"))]
+pub use key_per_file_watcher::KeyPerFileWatcher;
+
 #[cfg(feature = "redis")]
 pub mod redis_watcher;
 
 #[cfg(feature = "redis")]
 pub use redis_watcher::RedisWatcher;
+
+#[cfg(feature = "secrets")]
+pub mod secret_lease_watcher;
+
+#[cfg(feature = "secrets")]
+pub use secret_lease_watcher::SecretLeaseWatcher;
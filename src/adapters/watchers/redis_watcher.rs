@@ -5,6 +5,9 @@
 //! This module provides a watcher that monitors Redis for configuration changes
 //! using Redis keyspace notifications (pub/sub).
 
+use crate::adapters::redis::RedisCacheSync;
+use crate::adapters::runtime::block_on;
+use crate::adapters::RedisConnectOptions;
 use crate::domain::{ConfigError, ConfigKey, Result};
 use crate::ports::{ChangeCallback, ConfigWatcher};
 use redis::Client;
@@ -23,6 +26,13 @@ use std::thread::{self, JoinHandle};
 /// ```
 /// Or via CLI: `CONFIG SET notify-keyspace-events KEA`
 ///
+/// Notifications are received for the database selected in the connection
+/// options. In a cluster, every master node is subscribed to, since keyspace
+/// notifications are local to each node.
+///
+/// Use [`RedisAdapter::watcher`](crate::adapters::RedisAdapter::watcher) to
+/// create a watcher that also keeps the adapter's cache up to date.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -47,10 +57,12 @@ use std::thread::{self, JoinHandle};
 /// ```
 #[derive(Debug)]
 pub struct RedisWatcher {
-    /// Redis connection URL
-    url: String,
+    /// Connection options, used for every reconnect
+    options: RedisConnectOptions,
     /// Key prefix/pattern to watch
     namespace: String,
+    /// Cache to patch, when created by an adapter
+    sync: Option<RedisCacheSync>,
     /// Stop signal sender
     stop_tx: Option<Sender<()>>,
     /// Watch thread handle
@@ -82,30 +94,69 @@ impl RedisWatcher {
     /// # }
     /// ```
     pub fn new(url: &str, namespace: &str) -> Result<Self> {
-        // Test connection
-        let client = Client::open(url).map_err(|e| ConfigError::WatcherError {
-            message: format!("Failed to create Redis client: {}", e),
-            source: Some(Box::new(e)),
-        })?;
+        Self::with_options(RedisConnectOptions::new(url), namespace)
+    }
 
+    /// Creates a new Redis watcher with database, authentication, TLS or
+    /// topology options.
+    ///
+    /// Use the same options as the `RedisAdapter` so that both work against
+    /// the same database.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Connection options, also used when reconnecting
+    /// * `namespace` - Key prefix to watch (e.g., `"myapp:"`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{RedisConnectOptions, RedisWatcher};
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let options = RedisConnectOptions::new("redis://localhost:6379").with_db(3);
+    /// let watcher = RedisWatcher::with_options(options, "myapp:")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_options(options: RedisConnectOptions, namespace: &str) -> Result<Self> {
         // Test connection
-        let _conn = client
-            .get_connection()
-            .map_err(|e| ConfigError::WatcherError {
-                message: format!("Failed to connect to Redis: {}", e),
-                source: Some(Box::new(e)),
-            })?;
+        let clients = Self::master_clients(&options)?;
+        for client in &clients {
+            let _conn = client
+                .get_connection()
+                .map_err(|e| ConfigError::WatcherError {
+                    message: format!("Failed to connect to Redis: {}", e),
+                    source: Some(Box::new(e)),
+                })?;
+        }
 
         tracing::debug!("Redis watcher initialized. Note: keyspace notifications must be enabled manually with: CONFIG SET notify-keyspace-events KEA");
 
         Ok(Self {
-            url: url.to_string(),
+            options,
             namespace: namespace.to_string(),
+            sync: None,
             stop_tx: None,
             watch_thread: None,
         })
     }
 
+    /// Creates a watcher that patches an adapter's cache.
+    pub(crate) fn with_sync(
+        options: RedisConnectOptions,
+        namespace: &str,
+        sync: RedisCacheSync,
+    ) -> Self {
+        Self {
+            options,
+            namespace: namespace.to_string(),
+            sync: Some(sync),
+            stop_tx: None,
+            watch_thread: None,
+        }
+    }
+
     /// Attempts to enable keyspace notifications if they're not already enabled.
     ///
     /// This method tries to set `notify-keyspace-events` to `KEA` (Keyspace events,
@@ -113,34 +164,39 @@ impl RedisWatcher {
     ///
     /// Returns `Ok(())` if successful or if already enabled.
     pub fn try_enable_keyspace_notifications(&self) -> Result<()> {
-        let client = Client::open(self.url.as_str()).map_err(|e| ConfigError::WatcherError {
-            message: format!("Failed to create Redis client: {}", e),
-            source: Some(Box::new(e)),
-        })?;
+        for client in Self::master_clients(&self.options)? {
+            let mut conn = client
+                .get_connection()
+                .map_err(|e| ConfigError::WatcherError {
+                    message: format!("Failed to connect to Redis: {}", e),
+                    source: Some(Box::new(e)),
+                })?;
 
-        let mut conn = client
-            .get_connection()
-            .map_err(|e| ConfigError::WatcherError {
-                message: format!("Failed to connect to Redis: {}", e),
-                source: Some(Box::new(e)),
-            })?;
-
-        redis::cmd("CONFIG")
-            .arg("SET")
-            .arg("notify-keyspace-events")
-            .arg("KEA")
-            .query::<()>(&mut conn)
-            .map_err(|e| ConfigError::WatcherError {
-                message: format!(
-                    "Failed to enable keyspace notifications. Enable manually with: CONFIG SET notify-keyspace-events KEA. Error: {}",
-                    e
-                ),
-                source: Some(Box::new(e)),
-            })?;
+            redis::cmd("CONFIG")
+                .arg("SET")
+                .arg("notify-keyspace-events")
+                .arg("KEA")
+                .query::<()>(&mut conn)
+                .map_err(|e| ConfigError::WatcherError {
+                    message: format!(
+                        "Failed to enable keyspace notifications. Enable manually with: CONFIG SET notify-keyspace-events KEA. Error: {}",
+                        e
+                    ),
+                    source: Some(Box::new(e)),
+                })?;
+        }
 
         tracing::info!("Enabled Redis keyspace notifications");
         Ok(())
     }
+
+    /// Resolves the master nodes to subscribe to.
+    fn master_clients(options: &RedisConnectOptions) -> Result<Vec<Client>> {
+        block_on(options.master_clients()).map_err(|e| ConfigError::WatcherError {
+            message: format!("Failed to create Redis client: {}", e),
+            source: Some(Box::new(e)),
+        })
+    }
 }
 
 impl ConfigWatcher for RedisWatcher {
@@ -155,8 +211,9 @@ impl ConfigWatcher for RedisWatcher {
         let (stop_tx, stop_rx) = channel();
         self.stop_tx = Some(stop_tx);
 
-        let url = self.url.clone();
+        let options = self.options.clone();
         let namespace = self.namespace.clone();
+        let mut sync = self.sync.clone();
 
         let watch_thread = thread::spawn(move || {
             loop {
@@ -166,9 +223,9 @@ impl ConfigWatcher for RedisWatcher {
                     break;
                 }
 
-                // Connect to Redis
-                let client = match Client::open(url.as_str()) {
-                    Ok(c) => c,
+                // Connect to Redis (every master in a cluster)
+                let clients = match Self::master_clients(&options) {
+                    Ok(clients) => clients,
                     Err(e) => {
                         tracing::error!("Failed to create Redis client for watching: {}", e);
                         thread::sleep(std::time::Duration::from_secs(5));
@@ -176,7 +233,11 @@ impl ConfigWatcher for RedisWatcher {
                     }
                 };
 
-                let mut conn = match client.get_connection() {
+                let mut conns = match clients
+                    .iter()
+                    .map(|client| client.get_connection())
+                    .collect::<redis::RedisResult<Vec<_>>>()
+                {
                     Ok(c) => c,
                     Err(e) => {
                         tracing::error!("Failed to connect to Redis for watching: {}", e);
@@ -186,56 +247,128 @@ impl ConfigWatcher for RedisWatcher {
                 };
 
                 // Subscribe to keyspace notifications for keys with our prefix
-                // Pattern: __keyspace@0__:namespace*
-                let pattern = format!("__keyspace@0__:{}*", namespace);
+                // Pattern: __keyspace@<db>__:namespace*
+                let channel_prefix = format!("__keyspace@{}__:", options.db());
+                let pattern = format!("{}{}*", channel_prefix, namespace);
                 tracing::info!("Starting Redis watch on pattern: {}", pattern);
 
-                let mut pubsub = conn.as_pubsub();
-                if let Err(e) = pubsub.psubscribe(&pattern) {
+                let mut pubsubs: Vec<_> = conns.iter_mut().map(|conn| conn.as_pubsub()).collect();
+                if let Err(e) = pubsubs
+                    .iter_mut()
+                    .try_for_each(|pubsub| pubsub.psubscribe(&pattern))
+                {
                     tracing::error!("Failed to subscribe to Redis keyspace events: {}. Ensure keyspace notifications are enabled with: CONFIG SET notify-keyspace-events KEA", e);
                     thread::sleep(std::time::Duration::from_secs(5));
                     continue;
                 }
 
+                // Published messages reach every node, so one subscription is enough
+                let hash_change_channel = sync
+                    .as_ref()
+                    .and_then(|sync| sync.hash_change_channel())
+                    .map(str::to_string);
+                if let Some(channel) = &hash_change_channel {
+                    if let Err(e) = pubsubs[0].subscribe(channel) {
+                        tracing::error!(
+                            "Failed to subscribe to Redis channel '{}': {}",
+                            channel,
+                            e
+                        );
+                        thread::sleep(std::time::Duration::from_secs(5));
+                        continue;
+                    }
+                }
+
+                // Catch up on changes made while not subscribed
+                if let Some(sync) = &sync {
+                    match block_on(sync.resync()) {
+                        Ok(changed) => {
+                            for key in changed {
+                                tracing::debug!("Redis key changed while not subscribed: {}", key);
+                                callback(ConfigKey::from(key));
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to resynchronize Redis cache: {}", e);
+                            thread::sleep(std::time::Duration::from_secs(5));
+                            continue;
+                        }
+                    }
+                }
+
+                // Set a timeout to periodically check stop signal, shared between nodes
+                let read_timeout = std::time::Duration::from_millis(100) / pubsubs.len() as u32;
+                for pubsub in &mut pubsubs {
+                    pubsub.set_read_timeout(Some(read_timeout)).ok();
+                }
+
                 // Process messages
-                loop {
+                'messages: loop {
                     // Check for stop signal
                     if stop_rx.try_recv().is_ok() {
                         tracing::debug!("Redis watcher stopping");
                         return;
                     }
 
-                    // Set a timeout to periodically check stop signal
-                    pubsub
-                        .set_read_timeout(Some(std::time::Duration::from_millis(100)))
-                        .ok();
-
-                    match pubsub.get_message() {
-                        Ok(msg) => {
-                            let channel: String = msg.get_channel_name().to_string();
-
-                            // Extract key from channel name: __keyspace@0__:namespace:key
-                            if let Some(key_with_namespace) =
-                                channel.strip_prefix("__keyspace@0__:")
-                            {
-                                // Strip namespace prefix
-                                let key = if key_with_namespace.starts_with(&namespace) {
-                                    &key_with_namespace[namespace.len()..]
-                                } else {
-                                    key_with_namespace
-                                };
-
-                                tracing::debug!("Redis key changed: {}", key);
-                                callback(ConfigKey::from(key.to_string()));
+                    for pubsub in &mut pubsubs {
+                        match pubsub.get_message() {
+                            Ok(msg) => {
+                                let channel: String = msg.get_channel_name().to_string();
+
+                                if let Some(sync) = &mut sync {
+                                    let payload: String = msg.get_payload().unwrap_or_default();
+                                    let update = if hash_change_channel.as_deref()
+                                        == Some(channel.as_str())
+                                    {
+                                        block_on(sync.field_event(&payload))
+                                    } else if let Some(redis_key) =
+                                        channel.strip_prefix(&channel_prefix)
+                                    {
+                                        block_on(sync.key_event(redis_key, &payload))
+                                    } else {
+                                        continue;
+                                    };
+
+                                    match update {
+                                        Ok(keys) => {
+                                            for key in keys {
+                                                tracing::debug!("Redis key changed: {}", key);
+                                                callback(ConfigKey::from(key));
+                                            }
+                                        }
+                                        Err(e) => {
+                                            // Resynchronize after reconnecting
+                                            tracing::error!("Failed to update Redis cache: {}", e);
+                                            break 'messages;
+                                        }
+                                    }
+                                    continue;
+                                }
+
+                                // Extract key from channel name: __keyspace@<db>__:namespace:key
+                                if let Some(key_with_namespace) =
+                                    channel.strip_prefix(&channel_prefix)
+                                {
+                                    // Strip namespace prefix
+                                    let key = if key_with_namespace.starts_with(&namespace) {
+                                        &key_with_namespace[namespace.len()..]
+                                    } else {
+                                        key_with_namespace
+                                    };
+
+                                    let key = options.key_separator().to_config_key(key);
+                                    tracing::debug!("Redis key changed: {}", key);
+                                    callback(ConfigKey::from(key));
+                                }
                             }
-                        }
-                        Err(e) => {
-                            // Timeout errors are expected when checking stop signal
-                            if e.is_timeout() {
-                                continue;
+                            Err(e) => {
+                                // Timeout errors are expected when checking stop signal
+                                if e.is_timeout() {
+                                    continue;
+                                }
+                                tracing::error!("Redis pub/sub error: {}", e);
+                                break 'messages; // Reconnect
                             }
-                            tracing::error!("Redis pub/sub error: {}", e);
-                            break; // Reconnect
                         }
                     }
                 }
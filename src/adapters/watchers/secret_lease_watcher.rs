@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Secret lease watcher.
+//!
+//! This module provides a watcher that resolves secrets again when their
+//! leases expire and reports the configuration keys whose values changed.
+
+use crate::adapters::secrets::{SecretResolvers, SharedSecretState};
+use crate::domain::{ConfigError, ConfigKey, Result};
+use crate::ports::{ChangeCallback, ConfigWatcher};
+use std::fmt;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Watcher that refreshes secrets as their leases expire.
+///
+/// Secrets resolved by a [`ResolvingSource`](crate::adapters::ResolvingSource)
+/// are cached for the TTL their resolver reports. This watcher checks for
+/// expired secrets at a fixed interval, resolves them again, and calls the
+/// callback for every key whose value changed, including keys refreshed when
+/// they were read through the source. A failed refresh is logged and retried
+/// after a backoff that doubles with every failure, and the last value is kept
+/// meanwhile.
+///
+/// Create one with
+/// [`ResolvingSource::watcher`](crate::adapters::ResolvingSource::watcher).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use hexcfg::adapters::{FileSecretResolver, ResolvingSource, YamlFileAdapter};
+/// use hexcfg::ports::ConfigWatcher;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// let resolver = FileSecretResolver::new("/run/secrets")?.with_ttl(Duration::from_secs(60));
+/// let yaml = YamlFileAdapter::from_file("/etc/myapp/config.yaml")?;
+/// let source = ResolvingSource::new(Box::new(yaml), resolver)?;
+///
+/// let mut watcher = source.watcher(None);
+/// watcher.watch(Arc::new(|key| {
+///     println!("Secret changed: {}", key);
+/// }))?;
+///
+/// // Later, stop watching
+/// watcher.stop()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SecretLeaseWatcher {
+    /// Resolvers shared with the source
+    resolvers: SecretResolvers,
+    /// Resolved values shared with the source
+    state: SharedSecretState,
+    /// How often to look for expired secrets
+    check_interval: Duration,
+    /// Stop signal sender
+    stop_tx: Option<Sender<()>>,
+    /// Watch thread handle
+    watch_thread: Option<JoinHandle<()>>,
+}
+
+impl fmt::Debug for SecretLeaseWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretLeaseWatcher")
+            .field("check_interval", &self.check_interval)
+            .field("running", &self.watch_thread.is_some())
+            .finish()
+    }
+}
+
+impl SecretLeaseWatcher {
+    /// Creates a watcher that refreshes the secrets of a source.
+    pub(crate) fn with_state(
+        resolvers: SecretResolvers,
+        state: SharedSecretState,
+        check_interval: Duration,
+    ) -> Self {
+        Self {
+            resolvers,
+            state,
+            check_interval,
+            stop_tx: None,
+            watch_thread: None,
+        }
+    }
+}
+
+impl ConfigWatcher for SecretLeaseWatcher {
+    fn watch(&mut self, callback: ChangeCallback) -> Result<()> {
+        if self.watch_thread.is_some() {
+            return Err(ConfigError::WatcherError {
+                message: "Watcher is already running".to_string(),
+                source: None,
+            });
+        }
+
+        let (stop_tx, stop_rx) = channel::<()>();
+        self.stop_tx = Some(stop_tx);
+
+        // Only changes after the watch starts are reported
+        self.state.write().take_unreported();
+
+        let resolvers = self.resolvers.clone();
+        let state = self.state.clone();
+        let check_interval = self.check_interval;
+
+        let watch_thread = thread::spawn(move || {
+            // Check at every interval until stopped
+            while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(check_interval) {
+                let expired = state.read().expired(Instant::now());
+                for reference in expired {
+                    if let Err(e) = resolvers.refresh(&state, vec![reference.clone()]) {
+                        tracing::warn!("Failed to refresh secret {}: {}", reference, e);
+                    }
+                }
+
+                let changed = state.write().take_unreported();
+                for key in changed {
+                    tracing::debug!("Secret configuration key changed: {}", key);
+                    callback(ConfigKey::from(key));
+                }
+            }
+        });
+
+        self.watch_thread = Some(watch_thread);
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        // Send stop signal
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+
+        // Wait for the thread to finish
+        if let Some(handle) = self.watch_thread.take() {
+            handle.join().map_err(|_| ConfigError::WatcherError {
+                message: "Failed to join secret lease watcher thread".to_string(),
+                source: None,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for SecretLeaseWatcher {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
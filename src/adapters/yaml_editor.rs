@@ -0,0 +1,629 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Line-based editing of YAML documents.
+//!
+//! This module updates values in a YAML document by rewriting only the lines
+//! that hold them, so comments, key order and the formatting of untouched
+//! sections survive a write-back. It understands block-style mappings and
+//! sequences of scalars, which covers hand-written configuration files; flow
+//! collections and sequences of mappings are reported as unsupported instead
+//! of being rewritten.
+
+use crate::domain::{ConfigError, Result};
+
+/// A YAML document held as lines for in-place editing.
+#[derive(Debug, Clone)]
+pub(crate) struct YamlDocument {
+    /// Lines of the document without line endings
+    lines: Vec<String>,
+    /// Line ending used when writing the document back
+    line_ending: &'static str,
+    /// Whether the document ends with a line ending
+    trailing_newline: bool,
+    /// Number of spaces used for one level of indentation
+    indent_unit: usize,
+}
+
+/// A mapping entry found on a line.
+#[derive(Debug)]
+struct Entry {
+    /// The unquoted key
+    key: String,
+    /// Byte offset just past the `:` separator
+    value_col: usize,
+}
+
+impl YamlDocument {
+    /// Splits a document into lines, remembering its line endings and indentation.
+    pub(crate) fn parse(content: &str) -> Self {
+        let lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let indent_unit = lines
+            .iter()
+            .filter(|line| !is_ignorable(line))
+            .map(|line| indent_of(line))
+            .filter(|&indent| indent > 0)
+            .min()
+            .unwrap_or(2);
+
+        Self {
+            lines,
+            line_ending: if content.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            },
+            trailing_newline: content.is_empty() || content.ends_with('\n'),
+            indent_unit,
+        }
+    }
+
+    /// Sets the value at `path`, creating missing mappings along the way.
+    pub(crate) fn set(&mut self, path: &[String], value: &str) -> Result<()> {
+        let end = self.lines.len();
+        self.set_in(0, end, None, path, value)
+    }
+
+    /// Deletes the value at `path`, returning `false` if it did not exist.
+    ///
+    /// Mappings left empty by the deletion are removed as well.
+    pub(crate) fn delete(&mut self, path: &[String]) -> Result<bool> {
+        let end = self.lines.len();
+        self.delete_in(0, end, path)
+    }
+
+    fn set_in(
+        &mut self,
+        start: usize,
+        end: usize,
+        parent_indent: Option<usize>,
+        path: &[String],
+        value: &str,
+    ) -> Result<()> {
+        let Some(first) = self.first_content(start, end) else {
+            let indent = parent_indent.map_or(0, |indent| indent + self.indent_unit);
+            let at = if parent_indent.is_some() { start } else { end };
+            self.insert_new(at, indent, path, value);
+            return Ok(());
+        };
+
+        let indent = indent_of(&self.lines[first]);
+        if is_sequence_item(&self.lines[first][indent..]) {
+            return self.set_in_sequence(start, end, indent, path, value);
+        }
+
+        match self.find_entry(start, end, indent, &path[0]) {
+            Some((line, entry)) => {
+                let children_end = self.children_end(line, indent, end);
+                if path.len() == 1 {
+                    self.replace_value(line, entry.value_col, children_end, value);
+                    return Ok(());
+                }
+
+                let (inline, _) = split_comment(&self.lines[line][entry.value_col..]);
+                if !is_null(inline) {
+                    return Err(unsupported(format!(
+                        "'{}' holds a scalar or flow value and cannot contain nested keys",
+                        path[0]
+                    )));
+                }
+                if !inline.is_empty() {
+                    self.clear_value(line, entry.value_col);
+                }
+                self.set_in(line + 1, children_end, Some(indent), &path[1..], value)
+            }
+            None => {
+                let at = self.last_content(start, end).map_or(start, |line| line + 1);
+                self.insert_new(at, indent, path, value);
+                Ok(())
+            }
+        }
+    }
+
+    fn set_in_sequence(
+        &mut self,
+        start: usize,
+        end: usize,
+        indent: usize,
+        path: &[String],
+        value: &str,
+    ) -> Result<()> {
+        let index: usize = path[0]
+            .parse()
+            .map_err(|_| unsupported(format!("'{}' is not a valid sequence index", path[0])))?;
+        let items = self.sequence_items(start, end, indent);
+
+        if path.len() > 1 {
+            return Err(unsupported(
+                "nested values inside sequence items cannot be written".to_string(),
+            ));
+        }
+
+        match items.get(index) {
+            Some(&line) => {
+                if parse_entry(&self.lines[line], indent + 2).is_some() {
+                    return Err(unsupported(
+                        "sequence items that are mappings cannot be written".to_string(),
+                    ));
+                }
+                let children_end = self.children_end(line, indent, end);
+                self.replace_value(line, indent + 1, children_end, value);
+                Ok(())
+            }
+            None if index == items.len() => {
+                let at = self.last_content(start, end).map_or(start, |line| line + 1);
+                let line = format!("{}- {}", " ".repeat(indent), format_scalar(value));
+                self.lines.insert(at, line);
+                Ok(())
+            }
+            None => Err(unsupported(format!(
+                "sequence index {} is out of range (length {})",
+                index,
+                items.len()
+            ))),
+        }
+    }
+
+    fn delete_in(&mut self, start: usize, end: usize, path: &[String]) -> Result<bool> {
+        let Some(first) = self.first_content(start, end) else {
+            return Ok(false);
+        };
+
+        let indent = indent_of(&self.lines[first]);
+        if is_sequence_item(&self.lines[first][indent..]) {
+            let Ok(index) = path[0].parse::<usize>() else {
+                return Ok(false);
+            };
+            if path.len() > 1 {
+                return Err(unsupported(
+                    "nested values inside sequence items cannot be deleted".to_string(),
+                ));
+            }
+            let Some(&line) = self.sequence_items(start, end, indent).get(index) else {
+                return Ok(false);
+            };
+            let children_end = self.children_end(line, indent, end);
+            self.lines.drain(line..children_end);
+            return Ok(true);
+        }
+
+        let Some((line, entry)) = self.find_entry(start, end, indent, &path[0]) else {
+            return Ok(false);
+        };
+        let children_end = self.children_end(line, indent, end);
+
+        if path.len() == 1 {
+            self.lines.drain(line..children_end);
+            return Ok(true);
+        }
+
+        let (inline, _) = split_comment(&self.lines[line][entry.value_col..]);
+        if !inline.is_empty() {
+            return Ok(false);
+        }
+
+        let before = self.lines.len();
+        if !self.delete_in(line + 1, children_end, &path[1..])? {
+            return Ok(false);
+        }
+
+        let children_end = children_end - (before - self.lines.len());
+        if self.first_content(line + 1, children_end).is_none() {
+            self.lines.drain(line..children_end);
+        }
+        Ok(true)
+    }
+
+    /// Finds the mapping entry with the given key at `indent` in a block.
+    fn find_entry(
+        &self,
+        start: usize,
+        end: usize,
+        indent: usize,
+        key: &str,
+    ) -> Option<(usize, Entry)> {
+        (start..end)
+            .filter(|&line| !is_ignorable(&self.lines[line]))
+            .filter(|&line| indent_of(&self.lines[line]) == indent)
+            .filter_map(|line| parse_entry(&self.lines[line], indent).map(|entry| (line, entry)))
+            .find(|(_, entry)| entry.key == key)
+    }
+
+    /// Returns the lines that start items of the sequence at `indent`.
+    fn sequence_items(&self, start: usize, end: usize, indent: usize) -> Vec<usize> {
+        (start..end)
+            .filter(|&line| !is_ignorable(&self.lines[line]))
+            .filter(|&line| indent_of(&self.lines[line]) == indent)
+            .filter(|&line| is_sequence_item(&self.lines[line][indent..]))
+            .collect()
+    }
+
+    /// Returns the index just past the last line nested under `line`.
+    ///
+    /// A block sequence may sit at the same indentation as the key that owns
+    /// it, so sequence items at `indent` count as children of a key line.
+    fn children_end(&self, line: usize, indent: usize, end: usize) -> usize {
+        let owns_sequence = !is_sequence_item(&self.lines[line][indent..]);
+        let mut last = line;
+
+        for next in line + 1..end {
+            let text = &self.lines[next];
+            if is_ignorable(text) {
+                continue;
+            }
+            let next_indent = indent_of(text);
+            if next_indent > indent
+                || (owns_sequence && next_indent == indent && is_sequence_item(&text[indent..]))
+            {
+                last = next;
+            } else {
+                break;
+            }
+        }
+
+        last + 1
+    }
+
+    fn first_content(&self, start: usize, end: usize) -> Option<usize> {
+        (start..end).find(|&line| !is_ignorable(&self.lines[line]))
+    }
+
+    fn last_content(&self, start: usize, end: usize) -> Option<usize> {
+        (start..end)
+            .rev()
+            .find(|&line| !is_ignorable(&self.lines[line]))
+    }
+
+    /// Replaces the value after `value_col`, dropping any nested lines but
+    /// keeping a trailing comment.
+    fn replace_value(&mut self, line: usize, value_col: usize, children_end: usize, value: &str) {
+        self.lines.drain(line + 1..children_end);
+
+        let text = &self.lines[line];
+        let (_, comment) = split_comment(&text[value_col..]);
+        let updated = format!("{} {}{}", &text[..value_col], format_scalar(value), comment);
+        self.lines[line] = updated;
+    }
+
+    /// Removes an inline value such as `~`, keeping a trailing comment.
+    fn clear_value(&mut self, line: usize, value_col: usize) {
+        let text = &self.lines[line];
+        let (_, comment) = split_comment(&text[value_col..]);
+        let updated = format!("{}{}", &text[..value_col], comment);
+        self.lines[line] = updated;
+    }
+
+    /// Inserts nested mappings for `path` ending in `value`.
+    fn insert_new(&mut self, at: usize, indent: usize, path: &[String], value: &str) {
+        let last = path.len() - 1;
+        let new_lines = path.iter().enumerate().map(|(depth, segment)| {
+            let padding = " ".repeat(indent + depth * self.indent_unit);
+            if depth == last {
+                format!(
+                    "{}{}: {}",
+                    padding,
+                    format_key(segment),
+                    format_scalar(value)
+                )
+            } else {
+                format!("{}{}:", padding, format_key(segment))
+            }
+        });
+        let new_lines: Vec<String> = new_lines.collect();
+        self.lines.splice(at..at, new_lines);
+    }
+}
+
+impl std::fmt::Display for YamlDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.lines.join(self.line_ending))?;
+        if self.trailing_newline && !self.lines.is_empty() {
+            f.write_str(self.line_ending)?;
+        }
+        Ok(())
+    }
+}
+
+fn unsupported(message: String) -> ConfigError {
+    ConfigError::SourceError {
+        source_name: "yaml-file".to_string(),
+        message: format!("Cannot update YAML document: {}", message),
+        source: None,
+    }
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Returns `true` for blank lines, comments and document markers.
+fn is_ignorable(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---" || trimmed == "..."
+}
+
+fn is_sequence_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+fn is_null(value: &str) -> bool {
+    matches!(value, "" | "~" | "null" | "Null" | "NULL")
+}
+
+/// Parses a `key: value` entry whose key starts at byte offset `col`.
+fn parse_entry(line: &str, col: usize) -> Option<Entry> {
+    let rest = line.get(col..)?;
+
+    let (key, key_len) = match rest.chars().next()? {
+        '\'' => {
+            let close = find_single_quote_end(rest)?;
+            (rest[1..close].replace("''", "'"), close + 1)
+        }
+        '"' => {
+            let close = find_double_quote_end(rest)?;
+            let key = serde_yaml::from_str::<String>(&rest[..=close])
+                .unwrap_or_else(|_| rest[1..close].to_string());
+            (key, close + 1)
+        }
+        '-' if is_sequence_item(rest) => return None,
+        '#' | '?' | '{' | '[' => return None,
+        _ => {
+            let colon = find_plain_key_end(rest)?;
+            (rest[..colon].trim_end().to_string(), colon)
+        }
+    };
+
+    let after = &rest[key_len..];
+    let after_key = after.trim_start_matches(' ');
+    let separator = key_len + (after.len() - after_key.len());
+    let next = after_key.strip_prefix(':')?;
+    if !(next.is_empty() || next.starts_with(' ')) {
+        return None;
+    }
+
+    Some(Entry {
+        key,
+        value_col: col + separator + 1,
+    })
+}
+
+/// Finds the `:` that ends a plain key, or `None` if the line has no key.
+fn find_plain_key_end(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    for (i, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'#' if i > 0 && bytes[i - 1] == b' ' => return None,
+            b':' if i + 1 == bytes.len() || bytes[i + 1] == b' ' => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn find_single_quote_end(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        if bytes[i] == b'\'' {
+            if bytes.get(i + 1) == Some(&b'\'') {
+                i += 2;
+                continue;
+            }
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+fn find_double_quote_end(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut i = 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Splits the text after a `:` into the value and a trailing comment.
+///
+/// The comment keeps its leading whitespace so it can be reattached as-is.
+fn split_comment(text: &str) -> (&str, &str) {
+    let trimmed = text.trim_start();
+    let offset = text.len() - trimmed.len();
+
+    let scan_from = match trimmed.chars().next() {
+        Some('\'') => find_single_quote_end(trimmed).map_or(trimmed.len(), |end| end + 1),
+        Some('"') => find_double_quote_end(trimmed).map_or(trimmed.len(), |end| end + 1),
+        _ => 0,
+    };
+
+    let bytes = trimmed.as_bytes();
+    let comment = (scan_from..bytes.len())
+        .find(|&i| bytes[i] == b'#' && (i == 0 || bytes[i - 1] == b' ' || bytes[i - 1] == b'\t'));
+
+    match comment {
+        Some(i) => {
+            let value = trimmed[..i].trim_end();
+            let comment_start = offset + value.len();
+            (value, &text[comment_start..])
+        }
+        None => (trimmed.trim_end(), ""),
+    }
+}
+
+/// Formats a value so that it parses back as the same string.
+///
+/// Numbers and booleans are written plain so hand-edited files keep their
+/// style; anything YAML would read differently is quoted.
+fn format_scalar(value: &str) -> String {
+    if value.chars().any(char::is_control) {
+        return double_quote(value);
+    }
+
+    match serde_yaml::from_str::<serde_yaml::Value>(value) {
+        Ok(serde_yaml::Value::Number(n)) if n.to_string() == value => value.to_string(),
+        Ok(serde_yaml::Value::Bool(b)) if b.to_string() == value => value.to_string(),
+        Ok(serde_yaml::Value::String(s)) if s == value && !value.starts_with(['&', '*', '!']) => {
+            value.to_string()
+        }
+        _ => single_quote(value),
+    }
+}
+
+/// Formats a mapping key so that it parses back as the same string key.
+fn format_key(key: &str) -> String {
+    match serde_yaml::from_str::<serde_yaml::Value>(key) {
+        Ok(serde_yaml::Value::String(s))
+            if s == key && !key.contains(": ") && !key.starts_with(['&', '*', '!']) =>
+        {
+            key.to_string()
+        }
+        _ if key.chars().any(char::is_control) => double_quote(key),
+        _ => single_quote(key),
+    }
+}
+
+fn single_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn double_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(key: &str) -> Vec<String> {
+        key.split('.').map(str::to_string).collect()
+    }
+
+    fn set(content: &str, key: &str, value: &str) -> String {
+        let mut document = YamlDocument::parse(content);
+        document.set(&path(key), value).unwrap();
+        document.to_string()
+    }
+
+    fn delete(content: &str, key: &str) -> String {
+        let mut document = YamlDocument::parse(content);
+        document.delete(&path(key)).unwrap();
+        document.to_string()
+    }
+
+    #[test]
+    fn test_set_existing_value_keeps_comments() {
+        let content = "\
+# Database settings
+database:
+  host: localhost  # primary
+  port: 5432
+
+# Logging
+log_level: info
+";
+        let expected = "\
+# Database settings
+database:
+  host: db.internal  # primary
+  port: 5432
+
+# Logging
+log_level: info
+";
+        assert_eq!(set(content, "database.host", "db.internal"), expected);
+    }
+
+    #[test]
+    fn test_set_creates_missing_maps() {
+        let content = "app:\n    name: demo\nother: x\n";
+        assert_eq!(
+            set(content, "app.cache.ttl", "30"),
+            "app:\n    name: demo\n    cache:\n        ttl: 30\nother: x\n"
+        );
+        assert_eq!(set("", "a.b", "c"), "a:\n  b: c\n");
+        assert_eq!(set("a: ~\n", "a.b", "c"), "a:\n  b: c\n");
+    }
+
+    #[test]
+    fn test_set_quotes_ambiguous_values() {
+        assert_eq!(set("a: 1\n", "a", "yes please"), "a: yes please\n");
+        assert_eq!(set("a: 1\n", "a", "null"), "a: 'null'\n");
+        assert_eq!(set("a: 1\n", "a", "it's: here"), "a: 'it''s: here'\n");
+        assert_eq!(set("a: 1\n", "a", "two\nlines"), "a: \"two\\nlines\"\n");
+        assert_eq!(set("a: 1\n", "a", ""), "a: ''\n");
+        assert_eq!(set("a: 1\n", "a", "true"), "a: true\n");
+    }
+
+    #[test]
+    fn test_set_replaces_nested_block() {
+        let content = "a:\n  b: 1\n  c: |\n    text\n    more\nd: 2\n";
+        assert_eq!(
+            set(content, "a.c", "short"),
+            "a:\n  b: 1\n  c: short\nd: 2\n"
+        );
+    }
+
+    #[test]
+    fn test_set_sequence_items() {
+        let content = "servers:\n- one\n- two  # second\nport: 80\n";
+        assert_eq!(
+            set(content, "servers.1", "deux"),
+            "servers:\n- one\n- deux  # second\nport: 80\n"
+        );
+        assert_eq!(
+            set(content, "servers.2", "three"),
+            "servers:\n- one\n- two  # second\n- three\nport: 80\n"
+        );
+
+        let mut document = YamlDocument::parse(content);
+        assert!(document.set(&path("servers.5"), "x").is_err());
+        assert!(document.set(&path("servers.0.name"), "x").is_err());
+    }
+
+    #[test]
+    fn test_set_rejects_flow_collections() {
+        let mut document = YamlDocument::parse("a: {b: 1}\n");
+        assert!(document.set(&path("a.b"), "2").is_err());
+    }
+
+    #[test]
+    fn test_delete_removes_empty_parents() {
+        let content = "a:\n  b:\n    c: 1\nd: 2\n";
+        assert_eq!(delete(content, "a.b.c"), "d: 2\n");
+        assert_eq!(delete("a:\n  b: 1\n  c: 2\n", "a.b"), "a:\n  c: 2\n");
+
+        let mut document = YamlDocument::parse(content);
+        assert!(!document.delete(&path("a.x")).unwrap());
+        assert!(!document.delete(&path("d.x")).unwrap());
+    }
+
+    #[test]
+    fn test_quoted_keys_and_crlf() {
+        let content = "'example.com': 1\r\n\"port\": 2\r\n";
+        let mut document = YamlDocument::parse(content);
+        document.set(&["example.com".to_string()], "3").unwrap();
+        document.set(&path("port"), "4").unwrap();
+        assert_eq!(document.to_string(), "'example.com': 3\r\n\"port\": 4\r\n");
+
+        assert_eq!(set("", "0", "x"), "'0': x\n");
+    }
+}
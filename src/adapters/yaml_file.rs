@@ -2,13 +2,17 @@
 
 //! YAML file configuration source adapter.
 //!
-//! This module provides an adapter that reads configuration values from YAML files.
+//! This module provides an adapter that reads configuration values from YAML files
+//! and can write updated values back to them.
 
+use crate::adapters::yaml_editor::YamlDocument;
+use crate::domain::config_key::escape_segment;
 use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result};
-use crate::ports::{ConfigParser, ConfigSource};
+use crate::ports::{ConfigChange, ConfigParser, ConfigSink, ConfigSource};
 use directories::ProjectDirs;
 use std::collections::HashMap;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 /// Maximum allowed file size for YAML configuration files (10MB)
@@ -32,33 +36,69 @@ const MAX_YAML_FILE_SIZE: u64 = 10 * 1024 * 1024;
 /// assert_eq!(result.get("database.host"), Some(&"localhost".to_string()));
 /// ```
 #[derive(Debug, Clone)]
-pub struct YamlParser;
+pub struct YamlParser {
+    /// Whether to escape dots inside mapping keys
+    escape_dots_in_keys: bool,
+}
 
 impl YamlParser {
     /// Creates a new YAML parser.
     pub fn new() -> Self {
-        YamlParser
+        YamlParser {
+            escape_dots_in_keys: false,
+        }
+    }
+
+    /// Sets whether dots inside YAML mapping keys are escaped (default `false`).
+    ///
+    /// By default a mapping key such as `"example.com"` is joined as-is, so it
+    /// cannot be told apart from nested `example: { com: ... }` mappings. When
+    /// enabled, the dot is escaped and the key is kept as a single segment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::YamlParser;
+    /// use hexcfg::ports::ConfigParser;
+    ///
+    /// let parser = YamlParser::new().escape_dots_in_keys(true);
+    /// let result = parser.parse("hosts:\n  example.com: 443").unwrap();
+    /// assert_eq!(result.get("hosts.example\\.com"), Some(&"443".to_string()));
+    /// ```
+    pub fn escape_dots_in_keys(mut self, enabled: bool) -> Self {
+        self.escape_dots_in_keys = enabled;
+        self
     }
 
     /// Flattens a YAML value into a flat map with dot notation keys.
-    fn flatten_yaml(value: &serde_yaml::Value, prefix: &str, result: &mut HashMap<String, String>) {
+    fn flatten_yaml(
+        &self,
+        value: &serde_yaml::Value,
+        prefix: &str,
+        result: &mut HashMap<String, String>,
+    ) {
         match value {
             serde_yaml::Value::Mapping(map) => {
                 for (key, val) in map {
                     if let Some(key_str) = key.as_str() {
-                        let new_prefix = if prefix.is_empty() {
+                        let segment = if self.escape_dots_in_keys {
+                            escape_segment(key_str)
+                        } else {
                             key_str.to_string()
+                        };
+                        let new_prefix = if prefix.is_empty() {
+                            segment
                         } else {
-                            format!("{}.{}", prefix, key_str)
+                            format!("{}.{}", prefix, segment)
                         };
-                        Self::flatten_yaml(val, &new_prefix, result);
+                        self.flatten_yaml(val, &new_prefix, result);
                     }
                 }
             }
             serde_yaml::Value::Sequence(seq) => {
                 for (i, val) in seq.iter().enumerate() {
                     let new_prefix = format!("{}.{}", prefix, i);
-                    Self::flatten_yaml(val, &new_prefix, result);
+                    self.flatten_yaml(val, &new_prefix, result);
                 }
             }
             serde_yaml::Value::String(s) => {
@@ -73,6 +113,18 @@ impl YamlParser {
             serde_yaml::Value::Null => {
                 result.insert(prefix.to_string(), String::new());
             }
+            // `!encrypted <scheme>,<payload>` is kept in the `ENC[...]` form a
+            // `DecryptingSource` recognizes
+            serde_yaml::Value::Tagged(tagged) if tagged.tag == "encrypted" => {
+                if let Some(payload) = tagged.value.as_str() {
+                    let value = if payload.starts_with("ENC[") {
+                        payload.to_string()
+                    } else {
+                        format!("ENC[{}]", payload)
+                    };
+                    result.insert(prefix.to_string(), value);
+                }
+            }
             _ => {}
         }
     }
@@ -93,7 +145,7 @@ impl ConfigParser for YamlParser {
             })?;
 
         let mut result = HashMap::new();
-        Self::flatten_yaml(&value, "", &mut result);
+        self.flatten_yaml(&value, "", &mut result);
         Ok(result)
     }
 
@@ -110,8 +162,20 @@ impl ConfigParser for YamlParser {
 ///
 /// # Priority
 ///
-/// YAML files have a priority of 1, which means they are overridden by both
-/// environment variables (priority 2) and command-line arguments (priority 3).
+/// YAML files have a default priority of 1, which means they are overridden by
+/// both environment variables (priority 2) and command-line arguments
+/// (priority 3). Use [`with_priority`](Self::with_priority) to let a site
+/// file override environment variables.
+///
+/// # Writing
+///
+/// The adapter is a [`ConfigSink`]. Updated keys are written back into the
+/// nested structure of the file, creating missing maps, while comments, key
+/// order and the formatting of untouched sections are preserved. The file is
+/// replaced atomically via a temporary file and a rename, so a `FileWatcher`
+/// sees a single change. Block-style mappings and sequences of scalars can be
+/// written; flow collections (`{...}`, `[...]`) and sequences of mappings
+/// return an error rather than being reformatted.
 ///
 /// # Examples
 ///
@@ -133,6 +197,8 @@ pub struct YamlFileAdapter {
     values: HashMap<String, String>,
     /// YAML parser
     parser: YamlParser,
+    /// Priority for this source
+    priority: u8,
 }
 
 impl YamlFileAdapter {
@@ -150,8 +216,28 @@ impl YamlFileAdapter {
     /// let adapter = YamlFileAdapter::from_file("/etc/myapp/config.yaml").unwrap();
     /// ```
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_file_with_parser(path, YamlParser::new())
+    }
+
+    /// Creates a new YAML file adapter that uses a configured parser.
+    ///
+    /// The parser is kept for subsequent reloads.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the YAML file
+    /// * `parser` - The YAML parser to use
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{YamlFileAdapter, YamlParser};
+    ///
+    /// let parser = YamlParser::new().escape_dots_in_keys(true);
+    /// let adapter = YamlFileAdapter::from_file_with_parser("/etc/myapp/config.yaml", parser).unwrap();
+    /// ```
+    pub fn from_file_with_parser<P: AsRef<Path>>(path: P, parser: YamlParser) -> Result<Self> {
         let file_path = path.as_ref().to_path_buf();
-        let parser = YamlParser::new();
 
         // Canonicalize path to prevent directory traversal attacks
         let canonical_path = file_path
@@ -213,6 +299,7 @@ impl YamlFileAdapter {
             file_path: canonical_path,
             values,
             parser,
+            priority: 1,
         })
     }
 
@@ -276,10 +363,115 @@ impl YamlFileAdapter {
         Self::from_file(config_file)
     }
 
+    /// Sets the priority of this source (default 1).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::YamlFileAdapter;
+    ///
+    /// // Site settings that override environment variables
+    /// let adapter = YamlFileAdapter::from_file("/etc/myapp/site.yaml")
+    ///     .unwrap()
+    ///     .with_priority(3);
+    /// ```
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Returns the path to the configuration file.
     pub fn file_path(&self) -> &Path {
         &self.file_path
     }
+
+    /// Reads the configuration file, enforcing the size limit.
+    fn read_content(&self) -> Result<String> {
+        let metadata = fs::metadata(&self.file_path).map_err(|e| ConfigError::SourceError {
+            source_name: "yaml-file".to_string(),
+            message: format!(
+                "Failed to read file metadata: {}",
+                self.file_path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("<unknown>")
+            ),
+            source: Some(Box::new(e)),
+        })?;
+
+        if metadata.len() > MAX_YAML_FILE_SIZE {
+            return Err(ConfigError::SourceError {
+                source_name: "yaml-file".to_string(),
+                message: format!(
+                    "Configuration file too large: {} bytes (max {} bytes)",
+                    metadata.len(),
+                    MAX_YAML_FILE_SIZE
+                ),
+                source: None,
+            });
+        }
+
+        fs::read_to_string(&self.file_path).map_err(|e| ConfigError::SourceError {
+            source_name: "yaml-file".to_string(),
+            message: format!(
+                "Failed to read configuration file: {}",
+                self.file_path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("<unknown>")
+            ),
+            source: Some(Box::new(e)),
+        })
+    }
+
+    /// Replaces the configuration file with `content` via a temporary file in
+    /// the same directory, keeping the original permissions.
+    fn write_content(&self, content: &str) -> Result<()> {
+        let file_name = self
+            .file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("config.yaml");
+        let temp_path =
+            self.file_path
+                .with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+        let write = || -> std::io::Result<()> {
+            let permissions = fs::metadata(&self.file_path)?.permissions();
+
+            // Created with the original mode, so the content is never readable
+            // by more users than the file it replaces
+            let mut options = fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+                options.mode(permissions.mode());
+            }
+            let _ = fs::remove_file(&temp_path);
+            let mut file = options.open(&temp_path)?;
+            file.write_all(content.as_bytes())?;
+            file.sync_all()?;
+
+            // The mode given at creation is reduced by the umask
+            fs::set_permissions(&temp_path, permissions)?;
+            fs::rename(&temp_path, &self.file_path)
+        };
+
+        write().map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            ConfigError::SourceError {
+                source_name: "yaml-file".to_string(),
+                message: format!("Failed to write configuration file: {}", file_name),
+                source: Some(Box::new(e)),
+            }
+        })
+    }
+
+    /// Splits a key into the mapping keys of the YAML document.
+    fn key_path(key: &ConfigKey) -> Vec<String> {
+        key.segments().iter().map(|s| s.as_unescaped()).collect()
+    }
 }
 
 impl ConfigSource for YamlFileAdapter {
@@ -288,7 +480,7 @@ impl ConfigSource for YamlFileAdapter {
     }
 
     fn priority(&self) -> u8 {
-        1
+        self.priority
     }
 
     fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
@@ -307,45 +499,76 @@ impl ConfigSource for YamlFileAdapter {
     }
 
     fn reload(&mut self) -> Result<()> {
-        // Check file size before reading
-        let metadata = fs::metadata(&self.file_path).map_err(|e| ConfigError::SourceError {
-            source_name: "yaml-file".to_string(),
-            message: format!(
-                "Failed to read file metadata: {}",
-                self.file_path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("<unknown>")
-            ),
-            source: Some(Box::new(e)),
-        })?;
+        let content = self.read_content()?;
+        self.values = self.parser.parse(&content)?;
+        Ok(())
+    }
 
-        if metadata.len() > MAX_YAML_FILE_SIZE {
-            return Err(ConfigError::SourceError {
-                source_name: "yaml-file".to_string(),
-                message: format!(
-                    "Configuration file too large: {} bytes (max {} bytes)",
-                    metadata.len(),
-                    MAX_YAML_FILE_SIZE
-                ),
-                source: None,
-            });
+    fn as_sink(&mut self) -> Option<&mut dyn ConfigSink> {
+        Some(self)
+    }
+}
+
+impl ConfigSink for YamlFileAdapter {
+    fn set(&mut self, key: &ConfigKey, value: &ConfigValue) -> Result<()> {
+        self.apply(&[ConfigChange::Set {
+            key: key.clone(),
+            value: value.clone(),
+        }])
+    }
+
+    fn delete(&mut self, key: &ConfigKey) -> Result<()> {
+        self.apply(&[ConfigChange::Delete { key: key.clone() }])
+    }
+
+    /// Applies all changes to the file in a single write.
+    ///
+    /// The updated document is parsed again before it is written, and nothing
+    /// is written if any change fails or does not read back as expected.
+    fn apply(&mut self, changes: &[ConfigChange]) -> Result<()> {
+        let content = self.read_content()?;
+        let mut document = YamlDocument::parse(&content);
+
+        for change in changes {
+            match change {
+                ConfigChange::Set { key, value } => {
+                    document.set(&Self::key_path(key), value.as_str())?;
+                }
+                ConfigChange::Delete { key } => {
+                    document.delete(&Self::key_path(key))?;
+                }
+            }
         }
 
-        let content =
-            fs::read_to_string(&self.file_path).map_err(|e| ConfigError::SourceError {
-                source_name: "yaml-file".to_string(),
-                message: format!(
-                    "Failed to read configuration file: {}",
-                    self.file_path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("<unknown>")
-                ),
-                source: Some(Box::new(e)),
-            })?;
+        let updated = document.to_string();
+        let values = self.parser.parse(&updated)?;
 
-        self.values = self.parser.parse(&content)?;
+        let mut expected: HashMap<&str, Option<&str>> = HashMap::new();
+        for change in changes {
+            match change {
+                ConfigChange::Set { key, value } => {
+                    expected.insert(key.as_str(), Some(value.as_str()));
+                }
+                ConfigChange::Delete { key } => {
+                    expected.insert(key.as_str(), None);
+                }
+            }
+        }
+        for (key, value) in expected {
+            if values.get(key).map(String::as_str) != value {
+                return Err(ConfigError::SourceError {
+                    source_name: "yaml-file".to_string(),
+                    message: format!(
+                        "Cannot update YAML document: key '{}' does not read back as written",
+                        key
+                    ),
+                    source: None,
+                });
+            }
+        }
+
+        self.write_content(&updated)?;
+        self.values = values;
         Ok(())
     }
 }
@@ -353,7 +576,6 @@ impl ConfigSource for YamlFileAdapter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write;
     use tempfile::NamedTempFile;
 
     #[test]
@@ -379,6 +601,26 @@ database:
         assert_eq!(result.get("database.port"), Some(&"5432".to_string()));
     }
 
+    #[test]
+    fn test_yaml_parser_encrypted_tag() {
+        let parser = YamlParser::new();
+        let yaml = r#"
+database:
+  password: !encrypted AES256_GCM,c2VjcmV0
+  token: !encrypted ENC[AGE,dG9rZW4=]
+"#;
+        let result = parser.parse(yaml).unwrap();
+
+        assert_eq!(
+            result.get("database.password"),
+            Some(&"ENC[AES256_GCM,c2VjcmV0]".to_string())
+        );
+        assert_eq!(
+            result.get("database.token"),
+            Some(&"ENC[AGE,dG9rZW4=]".to_string())
+        );
+    }
+
     #[test]
     fn test_yaml_parser_deeply_nested() {
         let parser = YamlParser::new();
@@ -417,6 +659,40 @@ servers:
         assert_eq!(result.get("servers.2"), Some(&"server3".to_string()));
     }
 
+    #[test]
+    fn test_yaml_parser_dotted_keys_unescaped_by_default() {
+        let parser = YamlParser::new();
+        let result = parser.parse("hosts:\n  example.com: 443\n").unwrap();
+
+        assert_eq!(result.get("hosts.example.com"), Some(&"443".to_string()));
+    }
+
+    #[test]
+    fn test_yaml_parser_escape_dots_in_keys() {
+        let parser = YamlParser::new().escape_dots_in_keys(true);
+        let yaml = r#"
+hosts:
+  example.com:
+    port: 443
+  example:
+    com:
+      port: 80
+"#;
+        let result = parser.parse(yaml).unwrap();
+
+        assert_eq!(
+            result.get("hosts.example\\.com.port"),
+            Some(&"443".to_string())
+        );
+        assert_eq!(
+            result.get("hosts.example.com.port"),
+            Some(&"80".to_string())
+        );
+
+        let key = ConfigKey::from("hosts.example\\.com.port");
+        assert_eq!(key.segments().len(), 3);
+    }
+
     #[test]
     fn test_yaml_parser_mixed_types() {
         let parser = YamlParser::new();
@@ -537,4 +813,76 @@ null_value: null
         let parser = YamlParser::default();
         assert_eq!(parser.supported_extensions().len(), 2);
     }
+
+    #[test]
+    fn test_yaml_adapter_write_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        fs::write(
+            &path,
+            "# Service settings\ndatabase:\n  host: localhost  # primary\n  port: 5432\n",
+        )
+        .unwrap();
+
+        let mut adapter = YamlFileAdapter::from_file(&path).unwrap();
+        let sink = adapter.as_sink().unwrap();
+        sink.apply(&[
+            ConfigChange::set("database.host", "db.internal"),
+            ConfigChange::set("cache.ttl", "30"),
+            ConfigChange::delete("database.port"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Service settings\ndatabase:\n  host: db.internal  # primary\ncache:\n  ttl: 30\n"
+        );
+        assert_eq!(
+            adapter.get_str("cache.ttl").unwrap().unwrap().as_str(),
+            "30"
+        );
+        assert!(adapter.get_str("database.port").unwrap().is_none());
+
+        // Only the configuration file remains in the directory
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_yaml_adapter_write_back_keeps_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.yaml");
+        fs::write(&path, "db:\n  password: old\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        let mut adapter = YamlFileAdapter::from_file(&path).unwrap();
+        adapter
+            .as_sink()
+            .unwrap()
+            .set(&ConfigKey::from("db.password"), &ConfigValue::from("new"))
+            .unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_yaml_adapter_write_back_failure_leaves_file() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "servers: [a, b]").unwrap();
+
+        let mut adapter = YamlFileAdapter::from_file(temp_file.path()).unwrap();
+        let result = adapter
+            .as_sink()
+            .unwrap()
+            .set(&ConfigKey::from("servers.0"), &ConfigValue::from("c"));
+
+        assert!(result.is_err());
+        assert_eq!(
+            fs::read_to_string(temp_file.path()).unwrap(),
+            "servers: [a, b]\n"
+        );
+    }
 }
@@ -4,7 +4,12 @@
 //!
 //! This module provides the `ConfigKey` type, which is a newtype wrapper around `String`
 //! that provides type safety for configuration keys and prevents accidental string confusion.
+//!
+//! Keys are dot-separated paths. A literal dot inside a segment name is written as `\.`
+//! and a literal backslash as `\\`; segments made only of digits are array indices.
 
+use crate::domain::key_normalizer::KeyNormalizer;
+use crate::domain::key_pattern::KeyPattern;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 
@@ -70,6 +75,308 @@ impl ConfigKey {
     pub fn into_string(self) -> String {
         self.0
     }
+
+    /// Returns the canonical form of this key under the given normalizer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::config_key::ConfigKey;
+    /// use hexcfg::domain::StandardKeyNormalizer;
+    ///
+    /// let key = ConfigKey::from("Database.Max-Connections");
+    /// let normalized = key.normalized(&StandardKeyNormalizer::new());
+    /// assert_eq!(normalized.as_str(), "database.max_connections");
+    /// ```
+    pub fn normalized(&self, normalizer: &dyn KeyNormalizer) -> ConfigKey {
+        ConfigKey(normalizer.normalize(&self.0))
+    }
+
+    /// Compares two keys using the given normalizer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::config_key::ConfigKey;
+    /// use hexcfg::domain::StandardKeyNormalizer;
+    ///
+    /// let normalizer = StandardKeyNormalizer::new();
+    /// let yaml_key = ConfigKey::from("max_connections");
+    /// let env_key = ConfigKey::from("MAX_CONNECTIONS");
+    /// assert!(yaml_key.eq_with(&env_key, &normalizer));
+    /// assert_ne!(yaml_key, env_key);
+    /// ```
+    pub fn eq_with(&self, other: &ConfigKey, normalizer: &dyn KeyNormalizer) -> bool {
+        normalizer.matches(&self.0, &other.0)
+    }
+
+    /// Builds a key from a sequence of segments, escaping names as needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::{ConfigKey, KeySegment};
+    ///
+    /// let key = ConfigKey::from_segments([
+    ///     KeySegment::from("domains"),
+    ///     KeySegment::from("example.com"),
+    ///     KeySegment::Index(0),
+    /// ]);
+    /// assert_eq!(key.as_str(), "domains.example\\.com.0");
+    /// ```
+    pub fn from_segments<I>(segments: I) -> Self
+    where
+        I: IntoIterator<Item = KeySegment>,
+    {
+        let rendered: Vec<String> = segments.into_iter().map(|s| s.to_string()).collect();
+        ConfigKey(rendered.join("."))
+    }
+
+    /// Returns the path segments of this key.
+    ///
+    /// Escaped dots are kept inside their segment, and segments consisting only
+    /// of digits are returned as [`KeySegment::Index`]. The empty key has no segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::{ConfigKey, KeySegment};
+    ///
+    /// let key = ConfigKey::from("servers.0.host");
+    /// assert_eq!(
+    ///     key.segments(),
+    ///     vec![
+    ///         KeySegment::from("servers"),
+    ///         KeySegment::Index(0),
+    ///         KeySegment::from("host"),
+    ///     ]
+    /// );
+    ///
+    /// let key = ConfigKey::from("hosts.example\\.com");
+    /// assert_eq!(key.segments()[1], KeySegment::from("example.com"));
+    /// ```
+    pub fn segments(&self) -> Vec<KeySegment> {
+        split_segments(&self.0)
+            .into_iter()
+            .map(|raw| KeySegment::from(unescape_segment(raw)))
+            .collect()
+    }
+
+    /// Returns the key with its last segment removed, or `None` for a single-segment key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::ConfigKey;
+    ///
+    /// let key = ConfigKey::from("database.primary.host");
+    /// assert_eq!(key.parent(), Some(ConfigKey::from("database.primary")));
+    /// assert_eq!(ConfigKey::from("database").parent(), None);
+    /// ```
+    pub fn parent(&self) -> Option<ConfigKey> {
+        let end = segment_boundaries(&self.0).pop()?;
+        Some(ConfigKey(self.0[..end].to_string()))
+    }
+
+    /// Returns a new key with the given segment appended.
+    ///
+    /// Dots and backslashes in the segment name are escaped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::{ConfigKey, KeySegment};
+    ///
+    /// let key = ConfigKey::from("database");
+    /// assert_eq!(key.child("host").as_str(), "database.host");
+    /// assert_eq!(key.child(KeySegment::Index(2)).as_str(), "database.2");
+    /// assert_eq!(ConfigKey::from("").child("app").as_str(), "app");
+    /// ```
+    pub fn child(&self, segment: impl Into<KeySegment>) -> ConfigKey {
+        let segment = segment.into();
+        if self.0.is_empty() {
+            ConfigKey(segment.to_string())
+        } else {
+            ConfigKey(format!("{}.{}", self.0, segment))
+        }
+    }
+
+    /// Returns `true` if every segment of this key is a leading segment of `other`.
+    ///
+    /// A key is a prefix of itself, and the empty key is a prefix of every key.
+    /// Matching is done segment by segment, so `db` is not a prefix of `dbx.host`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::ConfigKey;
+    ///
+    /// let db = ConfigKey::from("database");
+    /// assert!(db.is_prefix_of(&ConfigKey::from("database.host")));
+    /// assert!(db.is_prefix_of(&db));
+    /// assert!(!db.is_prefix_of(&ConfigKey::from("databases.host")));
+    /// ```
+    pub fn is_prefix_of(&self, other: &ConfigKey) -> bool {
+        let own = self.segments();
+        let theirs = other.segments();
+        own.len() <= theirs.len() && own.iter().zip(&theirs).all(|(a, b)| a == b)
+    }
+
+    /// Returns `true` if this key matches the given glob pattern.
+    ///
+    /// See [`KeyPattern`] for the pattern syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::{ConfigKey, KeyPattern};
+    ///
+    /// let key = ConfigKey::from("database.primary.host");
+    /// assert!(key.matches(&KeyPattern::new("database.*.host")));
+    /// assert!(!key.matches(&KeyPattern::new("**.password")));
+    /// ```
+    pub fn matches(&self, pattern: &KeyPattern) -> bool {
+        pattern.matches(self)
+    }
+}
+
+/// A single segment of a configuration key path.
+///
+/// # Examples
+///
+/// ```
+/// use hexcfg::domain::KeySegment;
+///
+/// assert_eq!(KeySegment::from("host"), KeySegment::Name("host".to_string()));
+/// assert_eq!(KeySegment::from("3"), KeySegment::Index(3));
+/// assert_eq!(KeySegment::from("example.com").to_string(), "example\\.com");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum KeySegment {
+    /// A named segment, stored unescaped
+    Name(String),
+    /// An array index segment
+    Index(usize),
+}
+
+impl KeySegment {
+    /// Returns the segment as an unescaped string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::KeySegment;
+    ///
+    /// assert_eq!(KeySegment::from("example.com").as_unescaped(), "example.com");
+    /// assert_eq!(KeySegment::Index(1).as_unescaped(), "1");
+    /// ```
+    pub fn as_unescaped(&self) -> String {
+        match self {
+            KeySegment::Name(name) => name.clone(),
+            KeySegment::Index(index) => index.to_string(),
+        }
+    }
+}
+
+impl From<String> for KeySegment {
+    fn from(s: String) -> Self {
+        match parse_index(&s) {
+            Some(index) => KeySegment::Index(index),
+            None => KeySegment::Name(s),
+        }
+    }
+}
+
+impl From<&str> for KeySegment {
+    fn from(s: &str) -> Self {
+        KeySegment::from(s.to_string())
+    }
+}
+
+impl From<usize> for KeySegment {
+    fn from(index: usize) -> Self {
+        KeySegment::Index(index)
+    }
+}
+
+impl fmt::Display for KeySegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeySegment::Name(name) => write!(f, "{}", escape_segment(name)),
+            KeySegment::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+/// Parses a canonical array index (digits only, no leading zeros).
+fn parse_index(s: &str) -> Option<usize> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) || (s.len() > 1 && s.starts_with('0'))
+    {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Escapes dots and backslashes in a segment name.
+pub(crate) fn escape_segment(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c == '.' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Removes escapes from a raw segment; unknown escapes are kept as-is.
+pub(crate) fn unescape_segment(raw: &str) -> String {
+    let mut result = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(&next) = chars.peek() {
+                if next == '.' || next == '\\' {
+                    result.push(next);
+                    chars.next();
+                    continue;
+                }
+            }
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Returns the byte offsets of every unescaped dot in a key.
+fn segment_boundaries(key: &str) -> Vec<usize> {
+    let mut boundaries = Vec::new();
+    let mut escaped = false;
+    for (i, c) in key.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '.' {
+            boundaries.push(i);
+        }
+    }
+    boundaries
+}
+
+/// Splits a key on unescaped dots, leaving escapes in place.
+pub(crate) fn split_segments(key: &str) -> Vec<&str> {
+    if key.is_empty() {
+        return Vec::new();
+    }
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for end in segment_boundaries(key) {
+        segments.push(&key[start..end]);
+        start = end + 1;
+    }
+    segments.push(&key[start..]);
+    segments
 }
 
 impl From<String> for ConfigKey {
@@ -207,6 +514,81 @@ mod tests {
         assert_eq!(key.as_str(), "app_name");
     }
 
+    #[test]
+    fn test_config_key_normalized() {
+        use crate::domain::StandardKeyNormalizer;
+
+        let normalizer = StandardKeyNormalizer::new().nesting_separator("__");
+        let key = ConfigKey::from("APP__MAX-CONNECTIONS");
+        assert_eq!(key.normalized(&normalizer).as_str(), "app.max_connections");
+        assert!(key.eq_with(&ConfigKey::from("app.max_connections"), &normalizer));
+    }
+
+    #[test]
+    fn test_config_key_segments() {
+        let key = ConfigKey::from("servers.10.name");
+        assert_eq!(
+            key.segments(),
+            vec![
+                KeySegment::Name("servers".to_string()),
+                KeySegment::Index(10),
+                KeySegment::Name("name".to_string()),
+            ]
+        );
+        assert!(ConfigKey::from("").segments().is_empty());
+    }
+
+    #[test]
+    fn test_config_key_leading_zero_is_name() {
+        let key = ConfigKey::from("codes.007");
+        assert_eq!(key.segments()[1], KeySegment::Name("007".to_string()));
+    }
+
+    #[test]
+    fn test_config_key_escaped_dots() {
+        let key = ConfigKey::from("hosts.example\\.com.port");
+        assert_eq!(key.segments().len(), 3);
+        assert_eq!(key.segments()[1].as_unescaped(), "example.com");
+        assert_eq!(key.parent(), Some(ConfigKey::from("hosts.example\\.com")));
+    }
+
+    #[test]
+    fn test_config_key_segments_roundtrip() {
+        let key = ConfigKey::from("a.b\\.c.d\\\\e.0");
+        assert_eq!(ConfigKey::from_segments(key.segments()), key);
+    }
+
+    #[test]
+    fn test_config_key_unknown_escape_preserved() {
+        let key = ConfigKey::from("paths.C:\\temp");
+        assert_eq!(key.segments()[1].as_unescaped(), "C:\\temp");
+        assert_eq!(key.parent(), Some(ConfigKey::from("paths")));
+    }
+
+    #[test]
+    fn test_config_key_parent_and_child() {
+        let key = ConfigKey::from("database.host");
+        assert_eq!(
+            key.parent().unwrap().child("port").as_str(),
+            "database.port"
+        );
+        assert_eq!(
+            key.child("example.com").as_str(),
+            "database.host.example\\.com"
+        );
+        assert_eq!(ConfigKey::from("").parent(), None);
+    }
+
+    #[test]
+    fn test_config_key_is_prefix_of() {
+        let key = ConfigKey::from("a.b");
+        assert!(key.is_prefix_of(&ConfigKey::from("a.b.c")));
+        assert!(ConfigKey::from("").is_prefix_of(&key));
+        assert!(!key.is_prefix_of(&ConfigKey::from("a")));
+        assert!(!key.is_prefix_of(&ConfigKey::from("a.bc")));
+        assert!(!ConfigKey::from("a\\.b").is_prefix_of(&ConfigKey::from("a.b.c")));
+    }
+
     #[test]
     fn test_config_key_empty() {
         let key = ConfigKey::from("");
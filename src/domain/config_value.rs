@@ -26,8 +26,31 @@ use std::str::FromStr;
 /// assert_eq!(value.as_str(), "42");
 /// assert_eq!(value.as_i32("test.key").unwrap(), 42);
 /// ```
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ConfigValue(String);
+///
+/// # Secrets
+///
+/// Values created with [`ConfigValue::secret`] are marked as secret and are
+/// shown as `<redacted>` by their `Debug` output, so they do not leak into
+/// logs through `{:?}`. `Display`, [`as_str`](ConfigValue::as_str) and the
+/// conversions still return the actual value. The marker does not affect
+/// equality and is not serialized.
+///
+/// ```
+/// use hexcfg::domain::config_value::ConfigValue;
+///
+/// let password = ConfigValue::secret("hunter2");
+/// assert!(password.is_secret());
+/// assert_eq!(password.as_str(), "hunter2");
+/// assert_eq!(format!("{:?}", password), "ConfigValue(<redacted>)");
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct ConfigValue {
+    /// The value as read from its source
+    value: String,
+    /// Whether the value must not appear in debug output
+    secret: bool,
+}
 
 impl ConfigValue {
     /// Creates a new `ConfigValue` from a `String`.
@@ -41,7 +64,33 @@ impl ConfigValue {
     /// assert_eq!(value.as_str(), "hello");
     /// ```
     pub fn new(value: String) -> Self {
-        ConfigValue(value)
+        ConfigValue {
+            value,
+            secret: false,
+        }
+    }
+
+    /// Creates a `ConfigValue` that is marked as secret.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::config_value::ConfigValue;
+    ///
+    /// let value = ConfigValue::secret("s3cr3t");
+    /// assert!(value.is_secret());
+    /// assert!(!format!("{:?}", value).contains("s3cr3t"));
+    /// ```
+    pub fn secret(value: impl Into<String>) -> Self {
+        ConfigValue {
+            value: value.into(),
+            secret: true,
+        }
+    }
+
+    /// Returns `true` if the value is marked as secret.
+    pub fn is_secret(&self) -> bool {
+        self.secret
     }
 
     /// Returns the value as a string slice.
@@ -55,7 +104,7 @@ impl ConfigValue {
     /// assert_eq!(value.as_str(), "world");
     /// ```
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.value
     }
 
     /// Converts the value into a `String`.
@@ -69,7 +118,7 @@ impl ConfigValue {
     /// assert_eq!(value.as_string(), "test");
     /// ```
     pub fn as_string(&self) -> String {
-        self.0.clone()
+        self.value.clone()
     }
 
     /// Converts the value to a boolean.
@@ -90,11 +139,11 @@ impl ConfigValue {
     /// assert_eq!(value.as_bool("test.key").unwrap(), true);
     /// ```
     pub fn as_bool(&self, key: &str) -> Result<bool> {
-        match self.0.to_lowercase().as_str() {
+        match self.value.to_lowercase().as_str() {
             "true" | "yes" | "1" | "on" => Ok(true),
             "false" | "no" | "0" | "off" => Ok(false),
             _ => self
-                .0
+                .value
                 .parse::<bool>()
                 .map_err(|e| ConfigError::from_parse_bool_error(key.to_string(), e)),
         }
@@ -111,7 +160,7 @@ impl ConfigValue {
     /// assert_eq!(value.as_i32("test.key").unwrap(), 42);
     /// ```
     pub fn as_i32(&self, key: &str) -> Result<i32> {
-        self.0
+        self.value
             .parse::<i32>()
             .map_err(|e| ConfigError::from_parse_int_error(key.to_string(), e))
     }
@@ -127,7 +176,7 @@ impl ConfigValue {
     /// assert_eq!(value.as_i64("test.key").unwrap(), 9223372036854775807);
     /// ```
     pub fn as_i64(&self, key: &str) -> Result<i64> {
-        self.0
+        self.value
             .parse::<i64>()
             .map_err(|e| ConfigError::from_parse_int_error(key.to_string(), e))
     }
@@ -143,7 +192,7 @@ impl ConfigValue {
     /// assert_eq!(value.as_u32("test.key").unwrap(), 42);
     /// ```
     pub fn as_u32(&self, key: &str) -> Result<u32> {
-        self.0
+        self.value
             .parse::<u32>()
             .map_err(|e| ConfigError::from_parse_int_error(key.to_string(), e))
     }
@@ -159,7 +208,7 @@ impl ConfigValue {
     /// assert_eq!(value.as_u64("test.key").unwrap(), 18446744073709551615);
     /// ```
     pub fn as_u64(&self, key: &str) -> Result<u64> {
-        self.0
+        self.value
             .parse::<u64>()
             .map_err(|e| ConfigError::from_parse_int_error(key.to_string(), e))
     }
@@ -175,7 +224,7 @@ impl ConfigValue {
     /// assert_eq!(value.as_f64("test.key").unwrap(), 3.14);
     /// ```
     pub fn as_f64(&self, key: &str) -> Result<f64> {
-        self.0
+        self.value
             .parse::<f64>()
             .map_err(|e| ConfigError::from_parse_float_error(key.to_string(), e))
     }
@@ -197,7 +246,7 @@ impl ConfigValue {
         T: FromStr,
         T::Err: std::error::Error + Send + Sync + 'static,
     {
-        self.0
+        self.value
             .parse::<T>()
             .map_err(|e| ConfigError::TypeConversionError {
                 key: key.to_string(),
@@ -209,31 +258,51 @@ impl ConfigValue {
 
 impl From<String> for ConfigValue {
     fn from(s: String) -> Self {
-        ConfigValue(s)
+        ConfigValue::new(s)
     }
 }
 
 impl From<&str> for ConfigValue {
     fn from(s: &str) -> Self {
-        ConfigValue(s.to_string())
+        ConfigValue::new(s.to_string())
     }
 }
 
 impl From<ConfigValue> for String {
     fn from(value: ConfigValue) -> Self {
-        value.0
+        value.value
     }
 }
 
 impl AsRef<str> for ConfigValue {
     fn as_ref(&self) -> &str {
-        &self.0
+        &self.value
+    }
+}
+
+impl PartialEq for ConfigValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for ConfigValue {}
+
+impl fmt::Debug for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.secret {
+            f.debug_tuple("ConfigValue")
+                .field(&format_args!("<redacted>"))
+                .finish()
+        } else {
+            f.debug_tuple("ConfigValue").field(&self.value).finish()
+        }
     }
 }
 
 impl fmt::Display for ConfigValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.value)
     }
 }
 
@@ -272,6 +341,19 @@ mod tests {
         assert_eq!(format!("{}", value), "test");
     }
 
+    #[test]
+    fn test_config_value_secret_debug_is_redacted() {
+        let value = ConfigValue::secret("hunter2");
+        assert!(value.is_secret());
+        assert_eq!(format!("{:?}", value), "ConfigValue(<redacted>)");
+        assert_eq!(format!("{}", value), "hunter2");
+        assert_eq!(value, ConfigValue::from("hunter2"));
+
+        let value = ConfigValue::from("public");
+        assert!(!value.is_secret());
+        assert_eq!(format!("{:?}", value), "ConfigValue(\"public\")");
+    }
+
     #[test]
     fn test_as_bool_true_variants() {
         let true_values = vec![
@@ -279,9 +361,8 @@ mod tests {
         ];
         for val in true_values {
             let value = ConfigValue::from(val);
-            assert_eq!(
+            assert!(
                 value.as_bool("test.key").unwrap(),
-                true,
                 "Failed for value: {}",
                 val
             );
@@ -295,9 +376,8 @@ mod tests {
         ];
         for val in false_values {
             let value = ConfigValue::from(val);
-            assert_eq!(
-                value.as_bool("test.key").unwrap(),
-                false,
+            assert!(
+                !value.as_bool("test.key").unwrap(),
                 "Failed for value: {}",
                 val
             );
@@ -372,11 +452,11 @@ mod tests {
 
     #[test]
     fn test_as_f64() {
-        let value = ConfigValue::from("3.14");
-        assert_eq!(value.as_f64("test.key").unwrap(), 3.14);
+        let value = ConfigValue::from("2.5");
+        assert_eq!(value.as_f64("test.key").unwrap(), 2.5);
 
-        let value = ConfigValue::from("-3.14");
-        assert_eq!(value.as_f64("test.key").unwrap(), -3.14);
+        let value = ConfigValue::from("-2.5");
+        assert_eq!(value.as_f64("test.key").unwrap(), -2.5);
     }
 
     #[test]
@@ -81,6 +81,17 @@ pub enum ConfigError {
         source: Option<Box<dyn std::error::Error + Send + Sync>>,
     },
 
+    /// Failed to decrypt an encrypted configuration value.
+    ///
+    /// The message never contains the encrypted or decrypted value.
+    #[error("Failed to decrypt configuration value for key '{key}': {message}")]
+    DecryptionError {
+        /// The key whose value could not be decrypted
+        key: String,
+        /// The error message
+        message: String,
+    },
+
     /// An I/O error occurred while reading configuration.
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error),
@@ -180,6 +191,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decryption_error() {
+        let error = ConfigError::DecryptionError {
+            key: "database.password".to_string(),
+            message: "authentication failed".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Failed to decrypt configuration value for key 'database.password': authentication failed"
+        );
+    }
+
     #[test]
     fn test_io_error_conversion() {
         let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
@@ -0,0 +1,275 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Key normalization for tolerant configuration key matching.
+//!
+//! Configuration sources spell keys differently: environment variables are
+//! usually upper-case, while YAML keys keep their case and may use `-` or `_`.
+//! This module provides the `KeyNormalizer` trait, which maps a key to a
+//! canonical form so that keys from different sources can be compared.
+
+/// A trait for mapping configuration keys to a canonical form.
+///
+/// Two keys are considered equal when their normalized forms are equal. The
+/// configuration service applies the normalizer when matching requested keys
+/// against the keys provided by its sources and when caching values. Adapters
+/// keep the keys they read as they are; normalization happens only in the
+/// service, so it covers every source added to it.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::domain::KeyNormalizer;
+///
+/// struct LowercaseNormalizer;
+///
+/// impl KeyNormalizer for LowercaseNormalizer {
+///     fn normalize(&self, key: &str) -> String {
+///         key.to_lowercase()
+///     }
+/// }
+///
+/// let normalizer = LowercaseNormalizer;
+/// assert!(normalizer.matches("Database.Host", "database.host"));
+/// ```
+pub trait KeyNormalizer: Send + Sync {
+    /// Returns the canonical form of a key.
+    fn normalize(&self, key: &str) -> String;
+
+    /// Returns `true` if both keys have the same canonical form.
+    fn matches(&self, a: &str, b: &str) -> bool {
+        self.normalize(a) == self.normalize(b)
+    }
+}
+
+/// The standard key normalizer.
+///
+/// By default this normalizer folds case and treats `-` and `_` as equivalent,
+/// so `max-connections`, `max_connections` and `MAX_CONNECTIONS` all match.
+/// An additional nesting separator can be configured so that, for example,
+/// `DB__MAX_CONNECTIONS` matches `db.max_connections`.
+///
+/// [`EnvVarAdapter::new`](crate::adapters::EnvVarAdapter::new) turns every
+/// underscore into a dot, so `MAX_CONNECTIONS` arrives as `max.connections`.
+/// Enable [`underscores_as_dots`](Self::underscores_as_dots) to match such keys
+/// against `max_connections` from a file.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::domain::{KeyNormalizer, StandardKeyNormalizer};
+///
+/// let normalizer = StandardKeyNormalizer::new().nesting_separator("__");
+///
+/// assert_eq!(normalizer.normalize("DB__MAX-CONNECTIONS"), "db.max_connections");
+/// assert!(normalizer.matches("db.max_connections", "DB__MAX_CONNECTIONS"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StandardKeyNormalizer {
+    /// Whether to fold keys to lowercase
+    case_insensitive: bool,
+    /// Whether to treat `-` and `_` as the same character
+    dashes_as_underscores: bool,
+    /// Optional separator that is treated as a nesting dot
+    nesting_separator: Option<String>,
+    /// Whether to treat `_` (and `-`, when folded) as a nesting dot
+    underscores_as_dots: bool,
+}
+
+impl StandardKeyNormalizer {
+    /// Creates a normalizer that folds case and treats `-` and `_` as equivalent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::domain::{KeyNormalizer, StandardKeyNormalizer};
+    ///
+    /// let normalizer = StandardKeyNormalizer::new();
+    /// assert_eq!(normalizer.normalize("App.Max-Connections"), "app.max_connections");
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            case_insensitive: true,
+            dashes_as_underscores: true,
+            nesting_separator: None,
+            underscores_as_dots: false,
+        }
+    }
+
+    /// Sets whether keys are compared case-insensitively (default `true`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::domain::{KeyNormalizer, StandardKeyNormalizer};
+    ///
+    /// let normalizer = StandardKeyNormalizer::new().case_insensitive(false);
+    /// assert!(!normalizer.matches("App.Name", "app.name"));
+    /// ```
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Sets whether `-` and `_` are treated as equivalent (default `true`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::domain::{KeyNormalizer, StandardKeyNormalizer};
+    ///
+    /// let normalizer = StandardKeyNormalizer::new().dashes_as_underscores(false);
+    /// assert!(!normalizer.matches("max-connections", "max_connections"));
+    /// ```
+    pub fn dashes_as_underscores(mut self, enabled: bool) -> Self {
+        self.dashes_as_underscores = enabled;
+        self
+    }
+
+    /// Sets a separator that is treated as a nesting dot (e.g. `"__"`).
+    ///
+    /// The separator is replaced before any other normalization, so a
+    /// double underscore can mark nesting while single underscores are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::domain::{KeyNormalizer, StandardKeyNormalizer};
+    ///
+    /// let normalizer = StandardKeyNormalizer::new().nesting_separator("__");
+    /// assert_eq!(normalizer.normalize("APP__DB__MAX_CONNECTIONS"), "app.db.max_connections");
+    /// ```
+    pub fn nesting_separator(mut self, separator: impl Into<String>) -> Self {
+        let separator = separator.into();
+        self.nesting_separator = if separator.is_empty() {
+            None
+        } else {
+            Some(separator)
+        };
+        self
+    }
+
+    /// Sets whether `_` is treated as a nesting dot (default `false`).
+    ///
+    /// This matches the keys of an [`EnvVarAdapter`](crate::adapters::EnvVarAdapter)
+    /// that replaces underscores with dots against keys that keep them, at the
+    /// cost of no longer telling `max_connections` and `max.connections` apart.
+    /// With [`dashes_as_underscores`](Self::dashes_as_underscores), `-` is
+    /// treated as a dot as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::domain::{KeyNormalizer, StandardKeyNormalizer};
+    ///
+    /// let normalizer = StandardKeyNormalizer::new().underscores_as_dots(true);
+    /// // `MAX_CONNECTIONS` as read by `EnvVarAdapter::new().lowercase_keys(true)`
+    /// assert!(normalizer.matches("max.connections", "max_connections"));
+    /// ```
+    pub fn underscores_as_dots(mut self, enabled: bool) -> Self {
+        self.underscores_as_dots = enabled;
+        self
+    }
+}
+
+impl Default for StandardKeyNormalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyNormalizer for StandardKeyNormalizer {
+    fn normalize(&self, key: &str) -> String {
+        let mut normalized = match &self.nesting_separator {
+            Some(separator) => key.replace(separator.as_str(), "."),
+            None => key.to_string(),
+        };
+        if self.case_insensitive {
+            normalized = normalized.to_lowercase();
+        }
+        if self.dashes_as_underscores {
+            normalized = normalized.replace('-', "_");
+        }
+        if self.underscores_as_dots {
+            normalized = normalized.replace('_', ".");
+        }
+        normalized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_normalizer_defaults() {
+        let normalizer = StandardKeyNormalizer::new();
+        assert_eq!(normalizer.normalize("Database.Host"), "database.host");
+        assert_eq!(
+            normalizer.normalize("max-connections"),
+            normalizer.normalize("MAX_CONNECTIONS")
+        );
+    }
+
+    #[test]
+    fn test_standard_normalizer_keeps_dots() {
+        let normalizer = StandardKeyNormalizer::new();
+        assert!(!normalizer.matches("max.connections", "max_connections"));
+    }
+
+    #[test]
+    fn test_standard_normalizer_underscores_as_dots() {
+        let normalizer = StandardKeyNormalizer::new().underscores_as_dots(true);
+        assert!(normalizer.matches("max.connections", "max_connections"));
+        assert!(normalizer.matches("db.max-connections", "DB_MAX_CONNECTIONS"));
+
+        let normalizer = normalizer.dashes_as_underscores(false);
+        assert_eq!(normalizer.normalize("Max-Connections"), "max-connections");
+    }
+
+    #[test]
+    fn test_standard_normalizer_case_sensitive() {
+        let normalizer = StandardKeyNormalizer::new().case_insensitive(false);
+        assert_eq!(normalizer.normalize("App-Name"), "App_Name");
+    }
+
+    #[test]
+    fn test_standard_normalizer_without_dash_folding() {
+        let normalizer = StandardKeyNormalizer::new().dashes_as_underscores(false);
+        assert_eq!(normalizer.normalize("Max-Connections"), "max-connections");
+    }
+
+    #[test]
+    fn test_standard_normalizer_nesting_separator() {
+        let normalizer = StandardKeyNormalizer::new().nesting_separator("__");
+        assert_eq!(
+            normalizer.normalize("APP__DB__MAX_CONNECTIONS"),
+            "app.db.max_connections"
+        );
+        assert!(normalizer.matches("app.db.max-connections", "APP__DB__MAX_CONNECTIONS"));
+    }
+
+    #[test]
+    fn test_standard_normalizer_empty_separator_ignored() {
+        let normalizer = StandardKeyNormalizer::new().nesting_separator("");
+        assert_eq!(normalizer, StandardKeyNormalizer::new());
+    }
+
+    #[test]
+    fn test_custom_normalizer_matches() {
+        struct Identity;
+        impl KeyNormalizer for Identity {
+            fn normalize(&self, key: &str) -> String {
+                key.to_string()
+            }
+        }
+
+        assert!(Identity.matches("a.b", "a.b"));
+        assert!(!Identity.matches("a.b", "A.B"));
+    }
+
+    #[test]
+    fn test_normalizer_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+        assert_send_sync::<dyn KeyNormalizer>();
+    }
+}
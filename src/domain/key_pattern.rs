@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Glob patterns over configuration keys.
+//!
+//! This module provides the `KeyPattern` type, which matches configuration keys
+//! segment by segment. It is the shared matching primitive for anything that
+//! selects a group of keys, such as watch filters or secret redaction.
+
+use crate::domain::config_key::{split_segments, ConfigKey};
+use std::fmt;
+
+/// A glob pattern that matches configuration keys segment by segment.
+///
+/// The pattern syntax mirrors the key syntax:
+///
+/// - `*` as a whole segment matches exactly one segment
+/// - `**` as a whole segment matches zero or more segments
+/// - `*` inside a segment matches any run of characters within that segment
+/// - `\.`, `\\` and `\*` match a literal dot, backslash and asterisk
+///
+/// # Examples
+///
+/// ```
+/// use hexcfg::domain::{ConfigKey, KeyPattern};
+///
+/// let pattern = KeyPattern::new("database.*.host");
+/// assert!(pattern.matches(&ConfigKey::from("database.primary.host")));
+/// assert!(!pattern.matches(&ConfigKey::from("database.host")));
+///
+/// let secrets = KeyPattern::new("**.password");
+/// assert!(secrets.matches(&ConfigKey::from("password")));
+/// assert!(secrets.matches(&ConfigKey::from("services.0.auth.password")));
+///
+/// let suffix = KeyPattern::new("**.*_token");
+/// assert!(suffix.matches(&ConfigKey::from("github.api_token")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyPattern {
+    /// The pattern as written
+    raw: String,
+    /// Parsed pattern segments
+    segments: Vec<PatternSegment>,
+}
+
+/// A parsed segment of a key pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PatternSegment {
+    /// `*`: exactly one segment
+    Any,
+    /// `**`: zero or more segments
+    AnyDepth,
+    /// A segment name, split on unescaped `*` wildcards
+    Glob(Vec<String>),
+}
+
+impl KeyPattern {
+    /// Creates a pattern from its string form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::KeyPattern;
+    ///
+    /// let pattern = KeyPattern::new("**.password");
+    /// assert_eq!(pattern.as_str(), "**.password");
+    /// ```
+    pub fn new(pattern: impl Into<String>) -> Self {
+        let raw = pattern.into();
+        let segments = split_segments(&raw)
+            .into_iter()
+            .map(PatternSegment::parse)
+            .collect();
+        Self { raw, segments }
+    }
+
+    /// Returns the pattern as a string slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::KeyPattern;
+    ///
+    /// let pattern = KeyPattern::new("database.*");
+    /// assert_eq!(pattern.as_str(), "database.*");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns `true` if the key matches this pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::{ConfigKey, KeyPattern};
+    ///
+    /// let pattern = KeyPattern::new("servers.*.port");
+    /// assert!(pattern.matches(&ConfigKey::from("servers.0.port")));
+    /// assert!(!pattern.matches(&ConfigKey::from("servers.0.tls.port")));
+    /// ```
+    pub fn matches(&self, key: &ConfigKey) -> bool {
+        let segments: Vec<String> = key.segments().iter().map(|s| s.as_unescaped()).collect();
+        match_segments(&self.segments, &segments)
+    }
+}
+
+impl PatternSegment {
+    /// Parses one raw (still escaped) pattern segment.
+    fn parse(raw: &str) -> Self {
+        match raw {
+            "*" => return PatternSegment::Any,
+            "**" => return PatternSegment::AnyDepth,
+            _ => {}
+        }
+
+        let mut parts = vec![String::new()];
+        let mut chars = raw.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.peek() {
+                    Some(&next) if matches!(next, '.' | '\\' | '*') => {
+                        parts.last_mut().unwrap().push(next);
+                        chars.next();
+                    }
+                    _ => parts.last_mut().unwrap().push(c),
+                },
+                '*' => parts.push(String::new()),
+                _ => parts.last_mut().unwrap().push(c),
+            }
+        }
+        PatternSegment::Glob(parts)
+    }
+
+    /// Returns `true` if this single-segment pattern matches the segment.
+    fn matches(&self, segment: &str) -> bool {
+        match self {
+            PatternSegment::Any | PatternSegment::AnyDepth => true,
+            PatternSegment::Glob(parts) => glob_match(parts, segment),
+        }
+    }
+}
+
+/// Matches pattern segments against key segments, backtracking on `**`.
+fn match_segments(pattern: &[PatternSegment], key: &[String]) -> bool {
+    match pattern.split_first() {
+        None => key.is_empty(),
+        Some((PatternSegment::AnyDepth, rest)) => {
+            (0..=key.len()).any(|skip| match_segments(rest, &key[skip..]))
+        }
+        Some((segment, rest)) => match key.split_first() {
+            Some((first, key_rest)) => segment.matches(first) && match_segments(rest, key_rest),
+            None => false,
+        },
+    }
+}
+
+/// Matches a segment against literal parts separated by `*` wildcards.
+fn glob_match(parts: &[String], segment: &str) -> bool {
+    let (first, rest) = match parts.split_first() {
+        Some(split) => split,
+        None => return segment.is_empty(),
+    };
+    let Some(mut remaining) = segment.strip_prefix(first.as_str()) else {
+        return false;
+    };
+    let Some((last, middle)) = rest.split_last() else {
+        return remaining.is_empty();
+    };
+
+    for part in middle {
+        match remaining.find(part.as_str()) {
+            Some(pos) => remaining = &remaining[pos + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.ends_with(last.as_str())
+}
+
+impl From<&str> for KeyPattern {
+    fn from(s: &str) -> Self {
+        KeyPattern::new(s)
+    }
+}
+
+impl From<String> for KeyPattern {
+    fn from(s: String) -> Self {
+        KeyPattern::new(s)
+    }
+}
+
+impl fmt::Display for KeyPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, key: &str) -> bool {
+        KeyPattern::new(pattern).matches(&ConfigKey::from(key))
+    }
+
+    #[test]
+    fn test_pattern_literal() {
+        assert!(matches("database.host", "database.host"));
+        assert!(!matches("database.host", "database.port"));
+        assert!(!matches("database", "database.host"));
+    }
+
+    #[test]
+    fn test_pattern_single_segment_wildcard() {
+        assert!(matches("database.*.host", "database.replica.host"));
+        assert!(!matches("database.*.host", "database.host"));
+        assert!(!matches("database.*.host", "database.a.b.host"));
+    }
+
+    #[test]
+    fn test_pattern_any_depth() {
+        assert!(matches("**.password", "password"));
+        assert!(matches("**.password", "a.b.c.password"));
+        assert!(!matches("**.password", "a.password.hash"));
+        assert!(matches("database.**", "database"));
+        assert!(matches("database.**", "database.pool.size"));
+        assert!(matches("a.**.z", "a.b.c.z"));
+    }
+
+    #[test]
+    fn test_pattern_in_segment_glob() {
+        assert!(matches("*_token", "api_token"));
+        assert!(matches("db_*_url", "db_primary_url"));
+        assert!(matches("a*b*c", "abc"));
+        assert!(!matches("a*b*c", "acb"));
+        assert!(!matches("*_token", "api.token"));
+    }
+
+    #[test]
+    fn test_pattern_escapes() {
+        assert!(matches("hosts.example\\.com", "hosts.example\\.com"));
+        assert!(!matches("hosts.example\\.com", "hosts.example.com"));
+        assert!(matches("literal\\*", "literal*"));
+        assert!(!matches("literal\\*", "literally"));
+    }
+
+    #[test]
+    fn test_pattern_indices() {
+        assert!(matches("servers.*.port", "servers.3.port"));
+        assert!(matches("servers.0.port", "servers.0.port"));
+    }
+
+    #[test]
+    fn test_pattern_display() {
+        let pattern = KeyPattern::from("**.secret");
+        assert_eq!(pattern.to_string(), "**.secret");
+    }
+}
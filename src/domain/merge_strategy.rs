@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Merge strategies for structured values.
+//!
+//! This module defines how the values several configuration sources provide
+//! below a key prefix are combined, for maps and lists that sources store as
+//! flattened keys.
+
+use std::fmt;
+
+/// How values from several sources below a key prefix are combined.
+///
+/// Sources store structured values as flattened keys, so a YAML list
+/// `servers: [a, b]` becomes `servers.0` and `servers.1`. Without a strategy,
+/// precedence applies to each flattened key on its own, which is
+/// [`DeepMerge`](Self::DeepMerge): a higher-priority `servers: [c]` overrides
+/// `servers.0` but leaves `servers.1` from the lower-priority source visible.
+/// The other strategies treat the subtree below the prefix as one value.
+///
+/// Lists are read as indexed children `prefix.0`, `prefix.1` and so on, or as
+/// a comma-separated value at `prefix` itself, as for
+/// [`DefaultConfigService::get_list`](crate::service::DefaultConfigService::get_list).
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::domain::MergeStrategy;
+///
+/// assert_eq!(MergeStrategy::default(), MergeStrategy::DeepMerge);
+/// assert_eq!(MergeStrategy::UniqueUnion.to_string(), "unique-union");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MergeStrategy {
+    /// The highest-priority source that provides any key below the prefix
+    /// supplies the whole subtree
+    Replace,
+    /// Each key is taken from the highest-priority source that provides it
+    #[default]
+    DeepMerge,
+    /// The lists of all sources are concatenated, lowest priority first
+    Append,
+    /// Like [`Append`](Self::Append), but items equal to an earlier item are
+    /// dropped
+    UniqueUnion,
+}
+
+impl fmt::Display for MergeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MergeStrategy::Replace => "replace",
+            MergeStrategy::DeepMerge => "deep-merge",
+            MergeStrategy::Append => "append",
+            MergeStrategy::UniqueUnion => "unique-union",
+        };
+        f.write_str(name)
+    }
+}
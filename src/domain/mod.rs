@@ -9,10 +9,18 @@
 pub mod config_key;
 pub mod config_value;
 pub mod errors;
+pub mod key_normalizer;
+pub mod key_pattern;
+pub mod merge_strategy;
 pub mod service;
+pub mod typed_key;
 
 // Re-export commonly used types
-pub use config_key::ConfigKey;
+pub use config_key::{ConfigKey, KeySegment};
 pub use config_value::ConfigValue;
 pub use errors::{ConfigError, Result};
+pub use key_normalizer::{KeyNormalizer, StandardKeyNormalizer};
+pub use key_pattern::KeyPattern;
+pub use merge_strategy::MergeStrategy;
 pub use service::ConfigurationService;
+pub use typed_key::{KeyInfo, TypedKey};
@@ -6,8 +6,9 @@
 //! for interacting with the configuration system. It provides methods for retrieving
 //! configuration values, checking for key existence, and managing configuration reloading.
 
-use crate::domain::{ConfigKey, ConfigValue, Result};
+use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result, TypedKey};
 use crate::ports::ConfigWatcher;
+use std::str::FromStr;
 
 /// The main configuration service trait.
 ///
@@ -282,6 +283,59 @@ pub trait ConfigurationService {
         self.has(&ConfigKey::from(key))
     }
 
+    /// Retrieves and parses a configuration value for a typed key.
+    ///
+    /// If no source provides the key, the key's default is returned when it has
+    /// one. A value that fails to parse is an error even when a default exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The typed key to retrieve
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` - The parsed value or the key's default
+    /// * `Err(ConfigError)` - The key was not found and has no default, or the value failed to parse
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use hexcfg::domain::{ConfigurationService, ConfigError, ConfigKey, ConfigValue, Result, TypedKey};
+    /// # use hexcfg::ports::ConfigWatcher;
+    /// # struct MyConfigService;
+    /// # impl ConfigurationService for MyConfigService {
+    /// #     fn get(&self, key: &ConfigKey) -> Result<ConfigValue> {
+    /// #         Err(ConfigError::ConfigKeyNotFound { key: key.to_string() })
+    /// #     }
+    /// #     fn get_or_default(&self, key: &ConfigKey, default: &str) -> ConfigValue {
+    /// #         self.get(key).unwrap_or_else(|_| ConfigValue::from(default))
+    /// #     }
+    /// #     fn has(&self, key: &ConfigKey) -> bool { false }
+    /// #     fn reload(&mut self) -> Result<()> { Ok(()) }
+    /// #     fn register_watcher(&mut self, watcher: Box<dyn ConfigWatcher>) -> Result<()> { Ok(()) }
+    /// # }
+    /// const DB_PORT: TypedKey<u16> = TypedKey::new("db.port").default(5432);
+    ///
+    /// let service = MyConfigService;
+    /// let port: u16 = service.get_typed(&DB_PORT).unwrap();
+    /// assert_eq!(port, 5432);
+    /// ```
+    fn get_typed<T>(&self, key: &TypedKey<T>) -> Result<T>
+    where
+        Self: Sized,
+        T: FromStr + Clone,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        match self.get(&key.key()) {
+            Ok(value) => value.parse(key.name()),
+            Err(ConfigError::ConfigKeyNotFound { key: missing }) => match key.default_value() {
+                Some(default) => Ok(default.clone()),
+                None => Err(ConfigError::ConfigKeyNotFound { key: missing }),
+            },
+            Err(e) => Err(e),
+        }
+    }
+
     /// Reloads configuration from all sources.
     ///
     /// This method triggers a reload of all configuration sources that support
@@ -412,6 +466,21 @@ mod tests {
         assert!(service.has(&key));
     }
 
+    #[test]
+    fn test_service_get_typed() {
+        const PORT: TypedKey<u16> = TypedKey::new("server.port").default(80);
+
+        // TestConfigService returns "test_value" for every key, which is not a u16
+        let service = TestConfigService;
+        assert!(matches!(
+            service.get_typed(&PORT),
+            Err(ConfigError::TypeConversionError { .. })
+        ));
+
+        let name: TypedKey<String> = TypedKey::new("app.name");
+        assert_eq!(service.get_typed(&name).unwrap(), "test_value");
+    }
+
     #[test]
     fn test_service_reload() {
         let mut service = TestConfigService;
@@ -0,0 +1,246 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Typed configuration keys.
+//!
+//! This module provides `TypedKey<T>`, a configuration key that carries its value
+//! type, an optional default and an optional description. Typed keys are usually
+//! declared as constants so they can be shared between crates and listed for
+//! documentation or schema generation.
+
+use crate::domain::ConfigKey;
+use std::fmt;
+use std::mem;
+
+/// A configuration key with a compile-time value type.
+///
+/// # Examples
+///
+/// ```
+/// use hexcfg::domain::TypedKey;
+///
+/// const DB_PORT: TypedKey<u16> = TypedKey::new("db.port")
+///     .default(5432)
+///     .describe("Port of the database server");
+///
+/// assert_eq!(DB_PORT.name(), "db.port");
+/// assert_eq!(DB_PORT.default_value(), Some(&5432));
+/// assert_eq!(DB_PORT.description(), Some("Port of the database server"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedKey<T> {
+    /// The key path
+    name: &'static str,
+    /// Value used when no source provides the key
+    default: Option<T>,
+    /// Human-readable description of the key
+    description: Option<&'static str>,
+}
+
+impl<T> TypedKey<T> {
+    /// Creates a typed key without a default or description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::TypedKey;
+    ///
+    /// const APP_NAME: TypedKey<String> = TypedKey::new("app.name");
+    /// assert!(APP_NAME.default_value().is_none());
+    /// ```
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            default: None,
+            description: None,
+        }
+    }
+
+    /// Sets the value returned when no source provides the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a default is already set, which is a compile error for
+    /// constants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::TypedKey;
+    ///
+    /// const WORKERS: TypedKey<u32> = TypedKey::new("server.workers").default(4);
+    /// assert_eq!(WORKERS.default_value(), Some(&4));
+    /// ```
+    pub const fn default(self, value: T) -> Self {
+        assert!(self.default.is_none(), "TypedKey default is already set");
+        let key = Self {
+            name: self.name,
+            default: Some(value),
+            description: self.description,
+        };
+        // Dropping `self` is not allowed in a const fn; it holds no value to leak
+        mem::forget(self);
+        key
+    }
+
+    /// Sets a human-readable description of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::TypedKey;
+    ///
+    /// const DEBUG: TypedKey<bool> = TypedKey::new("app.debug").describe("Enable debug logging");
+    /// assert_eq!(DEBUG.description(), Some("Enable debug logging"));
+    /// ```
+    pub const fn describe(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Returns the key path as a string slice.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the key as a `ConfigKey`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::{ConfigKey, TypedKey};
+    ///
+    /// const DB_HOST: TypedKey<String> = TypedKey::new("db.host");
+    /// assert_eq!(DB_HOST.key(), ConfigKey::from("db.host"));
+    /// ```
+    pub fn key(&self) -> ConfigKey {
+        ConfigKey::from(self.name)
+    }
+
+    /// Returns the default value, if one is set.
+    pub const fn default_value(&self) -> Option<&T> {
+        self.default.as_ref()
+    }
+
+    /// Returns the description, if one is set.
+    pub const fn description(&self) -> Option<&'static str> {
+        self.description
+    }
+}
+
+impl<T: fmt::Display> TypedKey<T> {
+    /// Returns a type-erased description of the key for documentation and schemas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hexcfg::domain::TypedKey;
+    ///
+    /// const DB_PORT: TypedKey<u16> = TypedKey::new("db.port").default(5432);
+    ///
+    /// let info = DB_PORT.info();
+    /// assert_eq!(info.key, "db.port");
+    /// assert_eq!(info.value_type, "u16");
+    /// assert_eq!(info.default.as_deref(), Some("5432"));
+    /// ```
+    pub fn info(&self) -> KeyInfo {
+        KeyInfo {
+            key: self.name,
+            value_type: std::any::type_name::<T>(),
+            default: self.default.as_ref().map(|d| d.to_string()),
+            description: self.description,
+        }
+    }
+}
+
+impl<T> fmt::Display for TypedKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<T> From<&TypedKey<T>> for ConfigKey {
+    fn from(key: &TypedKey<T>) -> Self {
+        key.key()
+    }
+}
+
+/// A type-erased description of a typed key.
+///
+/// Collect these from a crate's typed key constants to document its settings.
+///
+/// # Examples
+///
+/// ```
+/// use hexcfg::domain::{KeyInfo, TypedKey};
+///
+/// const DB_HOST: TypedKey<String> = TypedKey::new("db.host").describe("Database host name");
+/// const DB_PORT: TypedKey<u16> = TypedKey::new("db.port").default(5432);
+///
+/// let schema: Vec<KeyInfo> = vec![DB_HOST.info(), DB_PORT.info()];
+/// for info in &schema {
+///     println!("{}: {} (default: {:?})", info.key, info.value_type, info.default);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyInfo {
+    /// The key path
+    pub key: &'static str,
+    /// The Rust type name of the value
+    pub value_type: &'static str,
+    /// The default value rendered as a string, if any
+    pub default: Option<String>,
+    /// The description, if any
+    pub description: Option<&'static str>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORT: TypedKey<u16> = TypedKey::new("server.port")
+        .default(8080)
+        .describe("Listen port");
+
+    #[test]
+    fn test_typed_key_const() {
+        assert_eq!(PORT.name(), "server.port");
+        assert_eq!(PORT.default_value(), Some(&8080));
+        assert_eq!(PORT.description(), Some("Listen port"));
+        assert_eq!(PORT.key(), ConfigKey::from("server.port"));
+    }
+
+    #[test]
+    fn test_typed_key_without_default() {
+        let key: TypedKey<String> = TypedKey::new("app.name");
+        assert!(key.default_value().is_none());
+        assert!(key.description().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "TypedKey default is already set")]
+    fn test_typed_key_default_set_twice() {
+        let _ = TypedKey::new("retries")
+            .default(String::from("1"))
+            .default(String::from("3"));
+    }
+
+    #[test]
+    fn test_typed_key_info() {
+        let info = PORT.info();
+        assert_eq!(
+            info,
+            KeyInfo {
+                key: "server.port",
+                value_type: "u16",
+                default: Some("8080".to_string()),
+                description: Some("Listen port"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_typed_key_display() {
+        assert_eq!(PORT.to_string(), "server.port");
+        assert_eq!(ConfigKey::from(&PORT).as_str(), "server.port");
+    }
+}
@@ -4,7 +4,7 @@
 //!
 //! This crate provides a flexible, type-safe configuration management system that can
 //! read configuration from multiple sources including environment variables, YAML files,
-//! command-line arguments, and remote services like etcd and Redis.
+//! command-line arguments, and remote services like etcd, Redis and Consul.
 //!
 //! # Architecture
 //!
@@ -17,7 +17,8 @@
 //!
 //! # Features
 //!
-//! - **Multiple Sources**: Environment variables, YAML files, CLI arguments, etcd, Redis
+//! - **Multiple Sources**: Environment variables, YAML files, CLI arguments, mounted
+//!   key-per-file directories, etcd, Redis, Consul
 //! - **Type Safety**: Type-safe conversions from string values to Rust types
 //! - **Precedence**: Configurable precedence order (CLI > env > files by default)
 //! - **Dynamic Reloading**: Watch for configuration changes and reload automatically
@@ -28,10 +29,19 @@
 //! - `yaml`: Enable YAML file support (default)
 //! - `env`: Enable environment variable support (default)
 //! - `cli`: Enable command-line argument support (default)
+//! - `key-per-file`: Enable directories with one file per key (Kubernetes volumes,
+//!   Docker secrets, systemd credentials)
+//! - `encryption`: Enable decryption of `ENC[...]` values with AES-256-GCM keys
+//!   or age identities
+//! - `secrets`: Enable resolution of `secret://...` and `${secret:...}` references
+//!   through secret resolvers
 //! - `reload`: Enable dynamic reloading with file watching
 //! - `etcd`: Enable etcd remote configuration support
 //! - `redis`: Enable Redis remote configuration support
-//! - `remote`: Enable all remote sources (etcd + redis)
+//! - `consul`: Enable Consul KV remote configuration support
+//! - `http`: Enable configuration documents served over HTTP(S)
+//! - `vault`: Enable secrets read from HashiCorp Vault's KV v2 engine
+//! - `remote`: Enable all remote sources (etcd + redis + consul + http + vault)
 //! - `full`: Enable all features
 //!
 //! # Quick Start
@@ -91,21 +101,39 @@ pub mod service;
 ///
 /// This module re-exports the most commonly used types and traits for convenient access.
 pub mod prelude {
-    pub use crate::domain::{ConfigError, ConfigKey, ConfigValue, ConfigurationService, Result};
-    pub use crate::ports::{ConfigParser, ConfigSource, ConfigWatcher};
+    pub use crate::domain::{
+        ConfigError, ConfigKey, ConfigValue, ConfigurationService, Result, TypedKey,
+    };
+    pub use crate::ports::{
+        ConfigParser, ConfigSource, ConfigWatcher, SecretDecryptor, SecretResolver,
+    };
     pub use crate::service::{ConfigurationServiceBuilder, DefaultConfigService};
 
     // Re-export adapters based on feature flags
-    #[cfg(feature = "cli")]
-    pub use crate::adapters::CommandLineAdapter;
     #[cfg(feature = "env")]
     pub use crate::adapters::EnvVarAdapter;
-    #[cfg(feature = "etcd")]
-    pub use crate::adapters::EtcdAdapter;
     #[cfg(feature = "reload")]
     pub use crate::adapters::FileWatcher;
+    #[cfg(feature = "key-per-file")]
+    pub use crate::adapters::KeyPerFileAdapter;
+    #[cfg(any(feature = "etcd", feature = "redis", feature = "consul"))]
+    pub use crate::adapters::KeySeparator;
+    #[cfg(feature = "encryption")]
+    pub use crate::adapters::{AesGcmDecryptor, AgeDecryptor, DecryptingSource};
+    #[cfg(feature = "cli")]
+    pub use crate::adapters::{CliSchema, CommandLineAdapter};
+    #[cfg(feature = "consul")]
+    pub use crate::adapters::{ConsulAdapter, ConsulConnectOptions};
+    #[cfg(feature = "etcd")]
+    pub use crate::adapters::{EtcdAdapter, EtcdConnectOptions};
+    #[cfg(feature = "secrets")]
+    pub use crate::adapters::{FileSecretResolver, ResolvingSource};
+    #[cfg(feature = "http")]
+    pub use crate::adapters::{HttpAdapter, HttpOptions};
     #[cfg(feature = "redis")]
-    pub use crate::adapters::{RedisAdapter, RedisStorageMode};
+    pub use crate::adapters::{RedisAdapter, RedisConnectOptions, RedisStorageMode};
+    #[cfg(feature = "vault")]
+    pub use crate::adapters::{VaultOptions, VaultResolver};
     #[cfg(feature = "yaml")]
     pub use crate::adapters::{YamlFileAdapter, YamlParser};
 }
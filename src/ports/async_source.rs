@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Asynchronous configuration source trait definition.
+//!
+//! This module defines the `AsyncConfigSource` trait, the async counterpart of
+//! `ConfigSource`. It lets sources that talk to remote services reload (and,
+//! if they choose, fetch values lazily) without blocking an async runtime.
+
+use crate::domain::{ConfigKey, ConfigValue, Result};
+use std::future::Future;
+use std::pin::Pin;
+
+/// A boxed, `Send` future returned by `AsyncConfigSource` methods.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A trait for asynchronous configuration sources.
+///
+/// The methods mirror [`ConfigSource`](crate::ports::ConfigSource), but `get`,
+/// `all_keys` and `reload` return futures. Sources that keep an in-memory cache
+/// can return ready futures from `get` and only do I/O in `reload`.
+///
+/// # Thread Safety
+///
+/// Implementations must be `Send + Sync` and return `Send` futures so they can
+/// be used from multi-threaded runtimes.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::ports::{AsyncConfigSource, BoxFuture};
+/// use hexcfg::domain::{ConfigKey, ConfigValue, Result};
+///
+/// struct MySource;
+///
+/// impl AsyncConfigSource for MySource {
+///     fn name(&self) -> &str {
+///         "my-source"
+///     }
+///
+///     fn priority(&self) -> u8 {
+///         1
+///     }
+///
+///     fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+///         Box::pin(async move {
+///             // Fetch the value here
+///             Ok(None)
+///         })
+///     }
+///
+///     fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> {
+///         Box::pin(async { Ok(vec![]) })
+///     }
+///
+///     fn reload(&mut self) -> BoxFuture<'_, Result<()>> {
+///         Box::pin(async { Ok(()) })
+///     }
+/// }
+/// ```
+pub trait AsyncConfigSource: Send + Sync {
+    /// Returns the name of this configuration source.
+    fn name(&self) -> &str;
+
+    /// Returns the priority of this configuration source.
+    ///
+    /// Higher values take precedence over lower values, as for `ConfigSource`.
+    fn priority(&self) -> u8;
+
+    /// Retrieves a configuration value for the given key.
+    ///
+    /// Resolves to `Ok(Some(value))` if the key exists, `Ok(None)` if it does
+    /// not, or `Err` if the value could not be retrieved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use hexcfg::ports::{AsyncConfigSource, BoxFuture};
+    /// # use hexcfg::domain::{ConfigKey, ConfigValue, Result};
+    /// # struct MySource;
+    /// # impl AsyncConfigSource for MySource {
+    /// #     fn name(&self) -> &str { "my-source" }
+    /// #     fn priority(&self) -> u8 { 1 }
+    /// #     fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+    /// #         Box::pin(async move { Ok(Some(ConfigValue::from("MyApp"))) })
+    /// #     }
+    /// #     fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> { Box::pin(async { Ok(vec![]) }) }
+    /// #     fn reload(&mut self) -> BoxFuture<'_, Result<()>> { Box::pin(async { Ok(()) }) }
+    /// # }
+    /// # tokio_test::block_on(async {
+    /// let source = MySource;
+    /// let value = source.get(&ConfigKey::from("app.name")).await.unwrap();
+    /// assert!(value.is_some());
+    /// # });
+    /// ```
+    fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>>;
+
+    /// Returns all configuration keys available in this source.
+    fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>>;
+
+    /// Reloads the configuration from the source.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use hexcfg::ports::{AsyncConfigSource, BoxFuture};
+    /// # use hexcfg::domain::{ConfigKey, ConfigValue, Result};
+    /// # struct MySource;
+    /// # impl AsyncConfigSource for MySource {
+    /// #     fn name(&self) -> &str { "my-source" }
+    /// #     fn priority(&self) -> u8 { 1 }
+    /// #     fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+    /// #         Box::pin(async { Ok(None) })
+    /// #     }
+    /// #     fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> { Box::pin(async { Ok(vec![]) }) }
+    /// #     fn reload(&mut self) -> BoxFuture<'_, Result<()>> { Box::pin(async { Ok(()) }) }
+    /// # }
+    /// # tokio_test::block_on(async {
+    /// let mut source = MySource;
+    /// source.reload().await.unwrap();
+    /// # });
+    /// ```
+    fn reload(&mut self) -> BoxFuture<'_, Result<()>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StaticSource;
+
+    impl AsyncConfigSource for StaticSource {
+        fn name(&self) -> &str {
+            "static"
+        }
+
+        fn priority(&self) -> u8 {
+            1
+        }
+
+        fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+            Box::pin(
+                async move { Ok((key.as_str() == "app.name").then(|| ConfigValue::from("demo"))) },
+            )
+        }
+
+        fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> {
+            Box::pin(async { Ok(vec![ConfigKey::from("app.name")]) })
+        }
+
+        fn reload(&mut self) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[test]
+    fn test_async_source_as_trait_object() {
+        let mut source: Box<dyn AsyncConfigSource> = Box::new(StaticSource);
+
+        tokio_test::block_on(async {
+            let value = source.get(&ConfigKey::from("app.name")).await.unwrap();
+            assert_eq!(value.unwrap().as_str(), "demo");
+            assert_eq!(source.all_keys().await.unwrap().len(), 1);
+            assert!(source.reload().await.is_ok());
+        });
+    }
+}
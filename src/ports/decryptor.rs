@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Secret decryptor trait definition.
+//!
+//! This module defines the `SecretDecryptor` trait, which provides an interface
+//! for decrypting configuration values that are stored encrypted, so that files
+//! holding secrets can be committed to version control.
+
+use crate::domain::{ConfigKey, Result};
+
+/// A trait for decrypting encrypted configuration values.
+///
+/// Encrypted values are written as `ENC[<scheme>,<payload>]`, for example
+/// `ENC[AES256_GCM,bm9uY2UgYW5kIGNpcGhlcnRleHQ=]`. In YAML files they can also
+/// be tagged as `!encrypted <scheme>,<payload>`. A decryptor handles one or
+/// more schemes and turns the payload back into the plaintext value.
+///
+/// # Errors
+///
+/// Failures should be reported as
+/// [`ConfigError::DecryptionError`](crate::domain::ConfigError::DecryptionError)
+/// naming `key`. Error messages must never contain the payload or the
+/// plaintext.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::domain::{ConfigError, ConfigKey, Result};
+/// use hexcfg::ports::SecretDecryptor;
+///
+/// /// Decodes `ENC[REVERSE,...]` values, for illustration only.
+/// struct ReverseDecryptor;
+///
+/// impl SecretDecryptor for ReverseDecryptor {
+///     fn schemes(&self) -> &[&str] {
+///         &["REVERSE"]
+///     }
+///
+///     fn decrypt(&self, key: &ConfigKey, _scheme: &str, payload: &str) -> Result<String> {
+///         if payload.is_empty() {
+///             return Err(ConfigError::DecryptionError {
+///                 key: key.to_string(),
+///                 message: "empty payload".to_string(),
+///             });
+///         }
+///         Ok(payload.chars().rev().collect())
+///     }
+/// }
+/// ```
+pub trait SecretDecryptor: Send + Sync {
+    /// Returns the schemes this decryptor handles, such as `"AES256_GCM"`.
+    fn schemes(&self) -> &[&str];
+
+    /// Decrypts the payload of an encrypted value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key the value belongs to, for error messages
+    /// * `scheme` - The scheme named in the value, one of [`schemes`](Self::schemes)
+    /// * `payload` - Everything after the first comma inside `ENC[...]`
+    fn decrypt(&self, key: &ConfigKey, scheme: &str, payload: &str) -> Result<String>;
+}
@@ -6,11 +6,21 @@
 //! for various components of the configuration system. These traits are implemented
 //! by adapters in the adapters layer.
 
+#[cfg(feature = "async")]
+pub mod async_source;
+pub mod decryptor;
 pub mod parser;
+pub mod secret_resolver;
+pub mod sink;
 pub mod source;
 pub mod watcher;
 
 // Re-export commonly used types
+#[cfg(feature = "async")]
+pub use async_source::{AsyncConfigSource, BoxFuture};
+pub use decryptor::SecretDecryptor;
 pub use parser::ConfigParser;
-pub use source::ConfigSource;
+pub use secret_resolver::{ResolvedSecret, SecretReference, SecretResolver};
+pub use sink::{ConfigChange, ConfigSink};
+pub use source::{ConfigSource, SourceHealth, SourceStatus};
 pub use watcher::{ChangeCallback, ConfigWatcher};
@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Secret resolver trait definition.
+//!
+//! This module defines the `SecretResolver` trait, which provides an interface
+//! for looking up secrets that configuration values refer to by name, so that
+//! the secrets themselves are kept in a dedicated secret store.
+
+use crate::domain::Result;
+use std::fmt;
+use std::time::Duration;
+
+/// A reference to a secret held in a secret store.
+///
+/// Configuration values refer to secrets in one of two forms:
+///
+/// - `secret://<store>/<path>#<field>` as the whole value, for example
+///   `secret://vault/kv/db#password`
+/// - `${secret:<path>#<field>}` anywhere in a value, for example
+///   `postgres://app:${secret:db#password}@db.internal/app`, which is resolved
+///   by the first resolver registered
+///
+/// The `#<field>` part is optional in both forms.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::ports::SecretReference;
+///
+/// let reference = SecretReference::parse("secret://vault/kv/db#password").unwrap();
+/// assert_eq!(reference.store(), Some("vault"));
+/// assert_eq!(reference.path(), "kv/db");
+/// assert_eq!(reference.field(), Some("password"));
+///
+/// assert!(SecretReference::parse("plain value").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SecretReference {
+    /// Name of the resolver to use; the first resolver when not set
+    store: Option<String>,
+    /// Path of the secret within the store
+    path: String,
+    /// Field of the secret to read, for stores holding several per path
+    field: Option<String>,
+}
+
+impl SecretReference {
+    /// Prefix of references naming their store.
+    pub const URL_PREFIX: &'static str = "secret://";
+
+    /// Creates a reference to `path` in the named store, or in the first
+    /// resolver's store when `store` is `None`.
+    pub fn new(store: Option<&str>, path: impl Into<String>, field: Option<&str>) -> Self {
+        Self {
+            store: store.map(String::from),
+            path: path.into(),
+            field: field.map(String::from),
+        }
+    }
+
+    /// Parses a whole value of the form `secret://<store>/<path>[#<field>]`.
+    ///
+    /// Returns `None` if the value is not a secret reference.
+    pub fn parse(value: &str) -> Option<Self> {
+        let rest = value.trim().strip_prefix(Self::URL_PREFIX)?;
+        let (store, rest) = rest.split_once('/')?;
+        if store.is_empty() {
+            return None;
+        }
+        let mut reference = Self::parse_path(rest)?;
+        reference.store = Some(store.to_string());
+        Some(reference)
+    }
+
+    /// Parses the inside of a `${secret:<path>[#<field>]}` placeholder.
+    pub(crate) fn parse_path(value: &str) -> Option<Self> {
+        let (path, field) = match value.split_once('#') {
+            Some((path, field)) => (path, Some(field)),
+            None => (value, None),
+        };
+        let path = path.trim_matches('/');
+        if path.is_empty() || field == Some("") {
+            return None;
+        }
+        Some(Self::new(None, path, field))
+    }
+
+    /// Returns the name of the resolver to use, if the reference names one.
+    pub fn store(&self) -> Option<&str> {
+        self.store.as_deref()
+    }
+
+    /// Returns the path of the secret within the store.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the field of the secret to read, if any.
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+}
+
+impl fmt::Display for SecretReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.store {
+            Some(store) => write!(f, "{}{}/{}", Self::URL_PREFIX, store, self.path)?,
+            None => write!(f, "${{secret:{}", self.path)?,
+        }
+        if let Some(field) = &self.field {
+            write!(f, "#{}", field)?;
+        }
+        if self.store.is_none() {
+            write!(f, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A secret returned by a [`SecretResolver`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct ResolvedSecret {
+    /// The secret value
+    pub value: String,
+    /// How long the value may be cached before it is resolved again; `None`
+    /// caches it until the source is reloaded
+    pub ttl: Option<Duration>,
+}
+
+impl ResolvedSecret {
+    /// Creates a secret that is cached until the source is reloaded.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            ttl: None,
+        }
+    }
+
+    /// Sets how long the secret may be cached, such as the lease duration
+    /// reported by the store.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+impl fmt::Debug for ResolvedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolvedSecret")
+            .field("value", &"<redacted>")
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+/// A trait for looking up secrets referenced by configuration values.
+///
+/// Each resolver serves one secret store, named by [`name`](Self::name), which
+/// `secret://<store>/...` references select it by. References without a store
+/// go to the first resolver registered.
+///
+/// # Errors
+///
+/// Failures should be reported as
+/// [`ConfigError::SourceError`](crate::domain::ConfigError::SourceError).
+/// Error messages may name the reference but must never contain the secret.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::domain::{ConfigError, Result};
+/// use hexcfg::ports::{ResolvedSecret, SecretReference, SecretResolver};
+/// use std::collections::HashMap;
+///
+/// /// Serves secrets from memory, for illustration only.
+/// struct MapResolver(HashMap<String, String>);
+///
+/// impl SecretResolver for MapResolver {
+///     fn name(&self) -> &str {
+///         "map"
+///     }
+///
+///     fn resolve(&self, reference: &SecretReference) -> Result<ResolvedSecret> {
+///         self.0
+///             .get(reference.path())
+///             .map(ResolvedSecret::new)
+///             .ok_or_else(|| ConfigError::SourceError {
+///                 source_name: "map".to_string(),
+///                 message: format!("Secret not found: {}", reference),
+///                 source: None,
+///             })
+///     }
+/// }
+/// ```
+pub trait SecretResolver: Send + Sync {
+    /// Returns the name references use to select this resolver, such as
+    /// `"vault"`.
+    fn name(&self) -> &str;
+
+    /// Looks up the secret `reference` points to.
+    fn resolve(&self, reference: &SecretReference) -> Result<ResolvedSecret>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_secret_reference() {
+        let reference = SecretReference::parse(" secret://vault/kv/db#password ").unwrap();
+        assert_eq!(reference.store(), Some("vault"));
+        assert_eq!(reference.path(), "kv/db");
+        assert_eq!(reference.field(), Some("password"));
+        assert_eq!(reference.to_string(), "secret://vault/kv/db#password");
+
+        let reference = SecretReference::parse("secret://file/api_token").unwrap();
+        assert_eq!(reference.path(), "api_token");
+        assert_eq!(reference.field(), None);
+
+        assert!(SecretReference::parse("secret://vault").is_none());
+        assert!(SecretReference::parse("secret:///kv/db").is_none());
+        assert!(SecretReference::parse("secret://vault/kv/db#").is_none());
+        assert!(SecretReference::parse("https://vault/kv/db").is_none());
+    }
+
+    #[test]
+    fn test_resolved_secret_debug_is_redacted() {
+        let secret = ResolvedSecret::new("hunter2").with_ttl(Duration::from_secs(60));
+        let debug = format!("{:?}", secret);
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains("60s"));
+    }
+}
@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Configuration sink trait definition.
+//!
+//! This module defines the `ConfigSink` trait, the write-side port for
+//! configuration sources. Sources that can persist changes expose a sink through
+//! `ConfigSource::as_sink`, so writes go through the same key mapping rules the
+//! source uses when reading.
+
+use crate::domain::{ConfigKey, ConfigValue, Result};
+
+/// A single change to apply to a configuration sink.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::ports::ConfigChange;
+///
+/// let changes = vec![
+///     ConfigChange::set("database.host", "db.internal"),
+///     ConfigChange::delete("database.legacy_host"),
+/// ];
+/// assert_eq!(changes[1].key().as_str(), "database.legacy_host");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigChange {
+    /// Set a key to a value, creating it if needed
+    Set {
+        /// The key to set
+        key: ConfigKey,
+        /// The new value
+        value: ConfigValue,
+    },
+    /// Delete a key; deleting a missing key is not an error
+    Delete {
+        /// The key to delete
+        key: ConfigKey,
+    },
+}
+
+impl ConfigChange {
+    /// Creates a `Set` change.
+    pub fn set(key: impl Into<ConfigKey>, value: impl Into<ConfigValue>) -> Self {
+        ConfigChange::Set {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Creates a `Delete` change.
+    pub fn delete(key: impl Into<ConfigKey>) -> Self {
+        ConfigChange::Delete { key: key.into() }
+    }
+
+    /// Returns the key this change applies to.
+    pub fn key(&self) -> &ConfigKey {
+        match self {
+            ConfigChange::Set { key, .. } | ConfigChange::Delete { key } => key,
+        }
+    }
+}
+
+/// A trait for configuration sources that can be written to.
+///
+/// Implementations translate configuration keys back to the storage layout of
+/// the source (for example `.` to `/` for etcd) and keep any cached values in
+/// sync with what they write.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::ports::{ConfigChange, ConfigSink};
+/// use hexcfg::domain::{ConfigKey, ConfigValue, Result};
+/// use std::collections::HashMap;
+///
+/// struct MapSink(HashMap<String, String>);
+///
+/// impl ConfigSink for MapSink {
+///     fn set(&mut self, key: &ConfigKey, value: &ConfigValue) -> Result<()> {
+///         self.0.insert(key.to_string(), value.to_string());
+///         Ok(())
+///     }
+///
+///     fn delete(&mut self, key: &ConfigKey) -> Result<()> {
+///         self.0.remove(key.as_str());
+///         Ok(())
+///     }
+/// }
+///
+/// let mut sink = MapSink(HashMap::new());
+/// sink.apply(&[ConfigChange::set("a", "1"), ConfigChange::set("b", "2")]).unwrap();
+/// assert_eq!(sink.0.len(), 2);
+/// ```
+pub trait ConfigSink: Send + Sync {
+    /// Sets a key to a value.
+    fn set(&mut self, key: &ConfigKey, value: &ConfigValue) -> Result<()>;
+
+    /// Deletes a key. Deleting a key that does not exist succeeds.
+    fn delete(&mut self, key: &ConfigKey) -> Result<()>;
+
+    /// Applies a batch of changes in order.
+    ///
+    /// The default implementation applies each change individually and stops at
+    /// the first error. Sinks backed by stores with transactions override this
+    /// to apply the whole batch atomically.
+    fn apply(&mut self, changes: &[ConfigChange]) -> Result<()> {
+        for change in changes {
+            match change {
+                ConfigChange::Set { key, value } => self.set(key, value)?,
+                ConfigChange::Delete { key } => self.delete(key)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct MapSink(HashMap<String, String>);
+
+    impl ConfigSink for MapSink {
+        fn set(&mut self, key: &ConfigKey, value: &ConfigValue) -> Result<()> {
+            self.0.insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        fn delete(&mut self, key: &ConfigKey) -> Result<()> {
+            self.0.remove(key.as_str());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_config_change_key() {
+        assert_eq!(ConfigChange::set("a.b", "1").key().as_str(), "a.b");
+        assert_eq!(ConfigChange::delete("c").key().as_str(), "c");
+    }
+
+    #[test]
+    fn test_default_apply_in_order() {
+        let mut sink = MapSink(HashMap::new());
+        sink.apply(&[
+            ConfigChange::set("key", "first"),
+            ConfigChange::set("other", "value"),
+            ConfigChange::delete("other"),
+            ConfigChange::set("key", "second"),
+        ])
+        .unwrap();
+
+        assert_eq!(sink.0.len(), 1);
+        assert_eq!(sink.0.get("key").map(String::as_str), Some("second"));
+    }
+}
@@ -7,6 +7,57 @@
 //! variables, files, remote services, etc.) must implement this trait.
 
 use crate::domain::{ConfigKey, ConfigValue, Result};
+use crate::ports::ConfigSink;
+use std::time::SystemTime;
+
+/// Whether a configuration source is serving values, as reported by
+/// [`ConfigSource::health`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceStatus {
+    /// The source loaded successfully and its last reload, if any, succeeded
+    Loaded,
+    /// The source serves the values it last loaded, because reloading has
+    /// failed since the given time
+    Stale {
+        /// When reloading first failed
+        since: SystemTime,
+    },
+    /// The source has not loaded yet, because loading has failed since the
+    /// given time
+    Unavailable {
+        /// When loading first failed
+        since: SystemTime,
+    },
+}
+
+/// The health of a configuration source, for readiness probes.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::ports::{SourceHealth, SourceStatus};
+///
+/// let health = SourceHealth::loaded();
+/// assert_eq!(health.status, SourceStatus::Loaded);
+/// assert!(health.last_error.is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceHealth {
+    /// Whether the source is serving values
+    pub status: SourceStatus,
+    /// The error that made the source stale or unavailable
+    pub last_error: Option<String>,
+}
+
+impl SourceHealth {
+    /// Returns the health of a source that loaded successfully.
+    pub fn loaded() -> Self {
+        Self {
+            status: SourceStatus::Loaded,
+            last_error: None,
+        }
+    }
+}
 
 /// A trait for configuration sources.
 ///
@@ -259,6 +310,76 @@ pub trait ConfigSource: Send + Sync {
     fn get_str(&self, key: &str) -> Result<Option<ConfigValue>> {
         self.get(&ConfigKey::from(key))
     }
+
+    /// Returns this source's write interface, if it supports writes.
+    ///
+    /// Read-only sources use the default implementation, which returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::InMemoryAdapter;
+    /// use hexcfg::domain::{ConfigKey, ConfigValue};
+    /// use hexcfg::ports::ConfigSource;
+    ///
+    /// let mut source = InMemoryAdapter::new();
+    /// let sink = source.as_sink().expect("in-memory sources are writable");
+    /// sink.set(&ConfigKey::from("app.name"), &ConfigValue::from("demo")).unwrap();
+    ///
+    /// assert!(source.get_str("app.name").unwrap().is_some());
+    /// ```
+    fn as_sink(&mut self) -> Option<&mut dyn ConfigSink> {
+        None
+    }
+
+    /// Returns whether this source is serving values.
+    ///
+    /// Sources that are fully loaded when they are created, and fail their
+    /// reloads without changing their values, use the default implementation,
+    /// which reports them as loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::InMemoryAdapter;
+    /// use hexcfg::ports::{ConfigSource, SourceStatus};
+    ///
+    /// let source = InMemoryAdapter::new();
+    /// assert_eq!(source.health().status, SourceStatus::Loaded);
+    /// ```
+    fn health(&self) -> SourceHealth {
+        SourceHealth::loaded()
+    }
+}
+
+impl<S: ConfigSource + ?Sized> ConfigSource for Box<S> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn priority(&self) -> u8 {
+        (**self).priority()
+    }
+
+    fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        (**self).get(key)
+    }
+
+    fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+        (**self).all_keys()
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        (**self).reload()
+    }
+
+    fn as_sink(&mut self) -> Option<&mut dyn ConfigSink> {
+        (**self).as_sink()
+    }
+
+    fn health(&self) -> SourceHealth {
+        (**self).health()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,381 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Asynchronous configuration service.
+//!
+//! This module provides `AsyncConfigService`, which aggregates
+//! `AsyncConfigSource`s with the same precedence rules as `DefaultConfigService`
+//! but awaits source lookups and reloads instead of blocking on them.
+
+use crate::adapters::SyncSourceAdapter;
+use crate::domain::{ConfigError, ConfigKey, ConfigValue, Result};
+use crate::ports::{AsyncConfigSource, ConfigSource};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Configuration service for async applications.
+///
+/// Sources are queried in priority order, highest first, and values are cached
+/// until the next [`reload`](Self::reload). Synchronous sources can be added
+/// with [`add_sync_source`](Self::add_sync_source).
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::adapters::EnvVarAdapter;
+/// use hexcfg::service::AsyncConfigService;
+/// use std::collections::HashMap;
+///
+/// # tokio_test::block_on(async {
+/// let mut values = HashMap::new();
+/// values.insert("app.name".to_string(), "demo".to_string());
+///
+/// let mut service = AsyncConfigService::new();
+/// service.add_sync_source(Box::new(EnvVarAdapter::with_values(values)));
+///
+/// let name = service.get_str("app.name").await.unwrap();
+/// assert_eq!(name.as_str(), "demo");
+///
+/// // Reloads remote sources without blocking the runtime
+/// service.reload().await.unwrap();
+/// # });
+/// ```
+pub struct AsyncConfigService {
+    /// Configuration sources, sorted by priority (highest first)
+    sources: Vec<Box<dyn AsyncConfigSource>>,
+    /// Cache for resolved configuration values
+    cache: RwLock<HashMap<String, ConfigValue>>,
+}
+
+impl std::fmt::Debug for AsyncConfigService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sources: Vec<&str> = self.sources.iter().map(|s| s.name()).collect();
+        f.debug_struct("AsyncConfigService")
+            .field("sources", &sources)
+            .finish()
+    }
+}
+
+impl AsyncConfigService {
+    /// Creates a new async configuration service with no sources.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::service::AsyncConfigService;
+    ///
+    /// let service = AsyncConfigService::new();
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Adds an asynchronous configuration source.
+    ///
+    /// Sources are kept sorted by priority, highest first. Sources with equal
+    /// priority keep the order in which they were added.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::{EnvVarAdapter, SyncSourceAdapter};
+    /// use hexcfg::service::AsyncConfigService;
+    ///
+    /// let mut service = AsyncConfigService::new();
+    /// service.add_source(Box::new(SyncSourceAdapter::new(EnvVarAdapter::new())));
+    /// ```
+    pub fn add_source(&mut self, source: Box<dyn AsyncConfigSource>) {
+        self.sources.push(source);
+        self.sources.sort_by_key(|s| Reverse(s.priority()));
+        self.invalidate_cache();
+    }
+
+    /// Adds a synchronous configuration source.
+    ///
+    /// The source is wrapped in a [`SyncSourceAdapter`], so its operations run
+    /// inline on the calling task.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::EnvVarAdapter;
+    /// use hexcfg::service::AsyncConfigService;
+    ///
+    /// let mut service = AsyncConfigService::new();
+    /// service.add_sync_source(Box::new(EnvVarAdapter::new()));
+    /// ```
+    pub fn add_sync_source(&mut self, source: Box<dyn ConfigSource>) {
+        self.add_source(Box::new(SyncSourceAdapter::new(source)));
+    }
+
+    /// Retrieves a configuration value for the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::EnvVarAdapter;
+    /// use hexcfg::domain::ConfigKey;
+    /// use hexcfg::service::AsyncConfigService;
+    /// use std::collections::HashMap;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut values = HashMap::new();
+    /// values.insert("db.port".to_string(), "5432".to_string());
+    ///
+    /// let mut service = AsyncConfigService::new();
+    /// service.add_sync_source(Box::new(EnvVarAdapter::with_values(values)));
+    ///
+    /// let port = service.get(&ConfigKey::from("db.port")).await.unwrap();
+    /// assert_eq!(port.as_u32("db.port").unwrap(), 5432);
+    /// # });
+    /// ```
+    pub async fn get(&self, key: &ConfigKey) -> Result<ConfigValue> {
+        if let Ok(cache) = self.cache.read() {
+            if let Some(value) = cache.get(key.as_str()) {
+                return Ok(value.clone());
+            }
+        }
+
+        for source in &self.sources {
+            match source.get(key).await {
+                Ok(Some(value)) => {
+                    if let Ok(mut cache) = self.cache.write() {
+                        cache.insert(key.as_str().to_string(), value.clone());
+                    }
+                    return Ok(value);
+                }
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(
+                        "Error querying source '{}' for key '{}': {}",
+                        source.name(),
+                        key,
+                        e
+                    );
+                    continue;
+                }
+            }
+        }
+
+        Err(ConfigError::ConfigKeyNotFound {
+            key: key.as_str().to_string(),
+        })
+    }
+
+    /// Retrieves a configuration value for the given key string.
+    pub async fn get_str(&self, key: &str) -> Result<ConfigValue> {
+        self.get(&ConfigKey::from(key)).await
+    }
+
+    /// Retrieves a configuration value or returns a default value if not found.
+    pub async fn get_or_default(&self, key: &ConfigKey, default: &str) -> ConfigValue {
+        self.get(key)
+            .await
+            .unwrap_or_else(|_| ConfigValue::from(default))
+    }
+
+    /// Returns `true` if any source provides a value for the key.
+    pub async fn has(&self, key: &ConfigKey) -> bool {
+        self.get(key).await.is_ok()
+    }
+
+    /// Reloads every source and clears the cache.
+    ///
+    /// All sources are reloaded even if some fail; the first error is returned.
+    pub async fn reload(&mut self) -> Result<()> {
+        let mut first_error = None;
+
+        for source in &mut self.sources {
+            if let Err(e) = source.reload().await {
+                tracing::warn!("Failed to reload source '{}': {}", source.name(), e);
+                first_error.get_or_insert(e);
+            }
+        }
+
+        self.invalidate_cache();
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Clears all cached values.
+    fn invalidate_cache(&self) {
+        if let Ok(mut cache) = self.cache.write() {
+            cache.clear();
+        }
+    }
+}
+
+impl Default for AsyncConfigService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::BoxFuture;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct MockSource {
+        name: String,
+        priority: u8,
+        values: HashMap<String, String>,
+        reloads: Arc<AtomicUsize>,
+        fail_reload: bool,
+    }
+
+    impl MockSource {
+        fn new(name: &str, priority: u8) -> Self {
+            Self {
+                name: name.to_string(),
+                priority,
+                values: HashMap::new(),
+                reloads: Arc::new(AtomicUsize::new(0)),
+                fail_reload: false,
+            }
+        }
+
+        fn with_value(mut self, key: &str, value: &str) -> Self {
+            self.values.insert(key.to_string(), value.to_string());
+            self
+        }
+    }
+
+    impl AsyncConfigSource for MockSource {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn priority(&self) -> u8 {
+            self.priority
+        }
+
+        fn get<'a>(&'a self, key: &'a ConfigKey) -> BoxFuture<'a, Result<Option<ConfigValue>>> {
+            Box::pin(async move {
+                Ok(self
+                    .values
+                    .get(key.as_str())
+                    .map(|v| ConfigValue::from(v.as_str())))
+            })
+        }
+
+        fn all_keys(&self) -> BoxFuture<'_, Result<Vec<ConfigKey>>> {
+            Box::pin(async move {
+                Ok(self
+                    .values
+                    .keys()
+                    .map(|k| ConfigKey::from(k.as_str()))
+                    .collect())
+            })
+        }
+
+        fn reload(&mut self) -> BoxFuture<'_, Result<()>> {
+            Box::pin(async move {
+                self.reloads.fetch_add(1, Ordering::SeqCst);
+                if self.fail_reload {
+                    return Err(ConfigError::SourceError {
+                        source_name: self.name.clone(),
+                        message: "unavailable".to_string(),
+                        source: None,
+                    });
+                }
+                Ok(())
+            })
+        }
+    }
+
+    #[test]
+    fn test_async_service_precedence() {
+        let mut service = AsyncConfigService::new();
+        service.add_source(Box::new(MockSource::new("low", 1).with_value("key", "low")));
+        service.add_source(Box::new(
+            MockSource::new("high", 3).with_value("key", "high"),
+        ));
+
+        tokio_test::block_on(async {
+            assert_eq!(service.get_str("key").await.unwrap().as_str(), "high");
+        });
+    }
+
+    #[test]
+    fn test_async_service_missing_key() {
+        let service = AsyncConfigService::new();
+
+        tokio_test::block_on(async {
+            assert!(matches!(
+                service.get_str("missing").await,
+                Err(ConfigError::ConfigKeyNotFound { .. })
+            ));
+            assert!(!service.has(&ConfigKey::from("missing")).await);
+            let value = service
+                .get_or_default(&ConfigKey::from("missing"), "fallback")
+                .await;
+            assert_eq!(value.as_str(), "fallback");
+        });
+    }
+
+    #[test]
+    fn test_async_service_reload_all_sources() {
+        let failing = {
+            let mut source = MockSource::new("failing", 2);
+            source.fail_reload = true;
+            source
+        };
+        let healthy = MockSource::new("healthy", 1).with_value("key", "value");
+        let reloads = Arc::clone(&healthy.reloads);
+
+        let mut service = AsyncConfigService::new();
+        service.add_source(Box::new(failing));
+        service.add_source(Box::new(healthy));
+
+        tokio_test::block_on(async {
+            assert!(service.reload().await.is_err());
+        });
+        assert_eq!(reloads.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_async_service_with_sync_source() {
+        struct SyncSource;
+
+        impl ConfigSource for SyncSource {
+            fn name(&self) -> &str {
+                "sync"
+            }
+
+            fn priority(&self) -> u8 {
+                2
+            }
+
+            fn get(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+                Ok((key.as_str() == "key").then(|| ConfigValue::from("sync")))
+            }
+
+            fn all_keys(&self) -> Result<Vec<ConfigKey>> {
+                Ok(vec![ConfigKey::from("key")])
+            }
+
+            fn reload(&mut self) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut service = AsyncConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("async", 1).with_value("key", "async"),
+        ));
+        service.add_sync_source(Box::new(SyncSource));
+
+        tokio_test::block_on(async {
+            assert_eq!(service.get_str("key").await.unwrap().as_str(), "sync");
+        });
+    }
+}
@@ -6,10 +6,42 @@
 //! trait, which aggregates multiple configuration sources and provides a unified
 //! interface for accessing configuration values.
 
-use crate::domain::{ConfigError, ConfigKey, ConfigValue, ConfigurationService, Result};
-use crate::ports::{ConfigSource, ConfigWatcher};
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use crate::domain::{
+    ConfigError, ConfigKey, ConfigValue, ConfigurationService, KeyNormalizer, KeySegment,
+    MergeStrategy, Result,
+};
+use crate::ports::{
+    ConfigChange, ConfigSink, ConfigSource, ConfigWatcher, SourceHealth, SourceStatus,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// A deprecated configuration key found in one of the configuration sources.
+///
+/// Returned by [`DefaultConfigService::deprecated_usages`] so applications can
+/// report renamed keys that are still in use, e.g. at startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecatedKeyUsage {
+    /// The deprecated key that was found
+    pub old_key: ConfigKey,
+    /// The key that replaces the deprecated key
+    pub new_key: ConfigKey,
+    /// The name of the source that provides the deprecated key
+    pub source_name: String,
+}
+
+/// A configuration source's place in the precedence chain.
+///
+/// Returned by [`DefaultConfigService::precedence`] and
+/// [`ConfigurationServiceBuilder::precedence`], highest priority first, so
+/// applications can log or check which source overrides which.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrecedenceEntry {
+    /// The name of the source
+    pub source_name: String,
+    /// The priority of the source
+    pub priority: u8,
+}
 
 /// Default implementation of the configuration service.
 ///
@@ -41,6 +73,16 @@ pub struct DefaultConfigService {
     cache: Arc<RwLock<HashMap<String, ConfigValue>>>,
     /// List of registered watchers
     watchers: Vec<Box<dyn ConfigWatcher>>,
+    /// Deprecated key aliases, mapping each new key to the old keys it replaces
+    aliases: HashMap<String, Vec<String>>,
+    /// Deprecated keys for which a warning has already been emitted
+    warned_aliases: Mutex<HashSet<String>>,
+    /// Optional normalizer used to match keys across sources
+    normalizer: Option<Arc<dyn KeyNormalizer>>,
+    /// Keys of each source by normalized form, indexed by source position
+    key_index: RwLock<HashMap<usize, HashMap<String, ConfigKey>>>,
+    /// Merge strategies by key prefix
+    merge_rules: Vec<(ConfigKey, MergeStrategy)>,
 }
 
 impl DefaultConfigService {
@@ -58,6 +100,11 @@ impl DefaultConfigService {
             sources: Vec::new(),
             cache: Arc::new(RwLock::new(HashMap::new())),
             watchers: Vec::new(),
+            aliases: HashMap::new(),
+            warned_aliases: Mutex::new(HashSet::new()),
+            normalizer: None,
+            key_index: RwLock::new(HashMap::new()),
+            merge_rules: Vec::new(),
         }
     }
 
@@ -85,6 +132,10 @@ impl DefaultConfigService {
     /// OS-appropriate location. If the YAML file doesn't exist, only environment
     /// variables will be used.
     ///
+    /// # Errors
+    ///
+    /// Returns an error if the YAML file exists but cannot be read or parsed.
+    ///
     /// # Arguments
     ///
     /// * `app_name` - The application name
@@ -113,8 +164,11 @@ impl DefaultConfigService {
         #[cfg(feature = "yaml")]
         {
             use crate::adapters::YamlFileAdapter;
-            if let Ok(adapter) = YamlFileAdapter::from_default_location(app_name, qualifier) {
-                builder = builder.with_source(Box::new(adapter));
+            // As for an optional source, only a missing file is left out
+            match YamlFileAdapter::from_default_location(app_name, qualifier) {
+                Ok(adapter) => builder = builder.with_source(Box::new(adapter)),
+                Err(e) if is_not_found(&e) => {}
+                Err(e) => return Err(e),
             }
         }
 
@@ -123,262 +177,1307 @@ impl DefaultConfigService {
 
     /// Adds a configuration source to the service.
     ///
-    /// Sources are automatically sorted by priority after being added.
+    /// Sources are automatically sorted by priority after being added. Sources
+    /// with equal priority are queried in the order they were added.
     pub fn add_source(&mut self, source: Box<dyn ConfigSource>) {
         self.sources.push(source);
         self.sort_sources();
         self.invalidate_cache();
     }
 
-    /// Sorts sources by priority (highest first).
-    fn sort_sources(&mut self) {
-        self.sources
-            .sort_by_key(|b| std::cmp::Reverse(b.priority()));
+    /// Sets the normalizer used to match keys across sources.
+    ///
+    /// Without a normalizer, keys must match exactly. With a normalizer, a
+    /// lookup that no source satisfies exactly is matched against every key
+    /// each source provides, comparing the normalized forms. Each source's
+    /// keys are indexed by normalized form the first time they are needed
+    /// after the source is added or reloaded, or the cache is cleared. Cached
+    /// values and deprecated aliases are keyed by the normalized form as well.
+    ///
+    /// The normalizer is applied here, not by the sources, which keep the keys
+    /// they read as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::prelude::*;
+    /// use hexcfg::adapters::EnvVarAdapter;
+    /// use hexcfg::domain::StandardKeyNormalizer;
+    /// use std::collections::HashMap;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut values = HashMap::new();
+    /// values.insert("MAX_CONNECTIONS".to_string(), "100".to_string());
+    ///
+    /// let mut service = DefaultConfigService::new();
+    /// service.add_source(Box::new(EnvVarAdapter::with_values(values)));
+    /// service.set_key_normalizer(StandardKeyNormalizer::new());
+    ///
+    /// assert_eq!(service.get_str("max-connections")?.as_str(), "100");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_key_normalizer(&mut self, normalizer: impl KeyNormalizer + 'static) {
+        self.normalizer = Some(Arc::new(normalizer));
+        self.invalidate_cache();
     }
 
-    /// Invalidates the cache.
-    fn invalidate_cache(&self) {
-        if let Ok(mut cache) = self.cache.write() {
-            cache.clear();
+    /// Sets how values below `prefix` are combined across sources.
+    ///
+    /// The strategy applies to [`get`](ConfigurationService::get),
+    /// [`snapshot`](Self::snapshot) and [`get_list`](Self::get_list), so with
+    /// [`Replace`](MergeStrategy::Replace) on `servers`, `get("servers.1")`
+    /// only finds a value if the source supplying the subtree has one. Reading
+    /// a key below such a prefix reads the subtree from every source before
+    /// the value is cached. Setting a strategy for a prefix again replaces the
+    /// earlier one. Within a subtree that is replaced or merged as a list,
+    /// strategies for nested prefixes have no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::prelude::*;
+    /// use hexcfg::adapters::{EnvVarAdapter, InMemoryAdapter};
+    /// use hexcfg::domain::MergeStrategy;
+    /// use std::collections::HashMap;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut defaults = HashMap::new();
+    /// defaults.insert("servers.0".to_string(), "a.internal".to_string());
+    /// defaults.insert("servers.1".to_string(), "b.internal".to_string());
+    ///
+    /// let mut env = HashMap::new();
+    /// env.insert("servers.0".to_string(), "c.internal".to_string());
+    ///
+    /// let mut service = DefaultConfigService::new();
+    /// service.add_source(Box::new(InMemoryAdapter::with_values(defaults).with_priority(1)));
+    /// service.add_source(Box::new(EnvVarAdapter::with_values(env)));
+    ///
+    /// service.set_merge_strategy("servers", MergeStrategy::Replace);
+    /// assert_eq!(service.get_list("servers")?, vec![ConfigValue::from("c.internal")]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_merge_strategy(&mut self, prefix: impl Into<ConfigKey>, strategy: MergeStrategy) {
+        let prefix = prefix.into();
+        self.merge_rules.retain(|(p, _)| *p != prefix);
+        self.merge_rules.push((prefix, strategy));
+        self.invalidate_cache();
+    }
+
+    /// Registers a deprecated alias for a configuration key.
+    ///
+    /// Lookups for `new_key` that are not satisfied by any source fall back to
+    /// `old_key`. The first time a value is resolved through the old key, a
+    /// deprecation warning naming the source is logged. Several old keys may be
+    /// registered for the same new key; they are tried in registration order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::prelude::*;
+    /// use hexcfg::adapters::EnvVarAdapter;
+    /// use std::collections::HashMap;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut values = HashMap::new();
+    /// values.insert("db.host".to_string(), "localhost".to_string());
+    ///
+    /// let mut service = DefaultConfigService::new();
+    /// service.add_source(Box::new(EnvVarAdapter::with_values(values)));
+    /// service.add_key_alias("db.host", "database.host");
+    ///
+    /// assert_eq!(service.get_str("database.host")?.as_str(), "localhost");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_key_alias(&mut self, old_key: impl Into<ConfigKey>, new_key: impl Into<ConfigKey>) {
+        let old_key = old_key.into().into_string();
+        let new_key = new_key.into().into_string();
+        let old_keys = self.aliases.entry(new_key).or_default();
+        if !old_keys.contains(&old_key) {
+            old_keys.push(old_key);
         }
+        self.invalidate_cache();
     }
 
-    /// Queries all sources for a configuration value, respecting priority order.
-    fn query_sources(&self, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+    /// Returns every deprecated key that is currently provided by a source.
+    ///
+    /// All sources are scanned, not just the keys that have been looked up, so
+    /// this is suitable for a startup report of configuration that needs to be
+    /// migrated. Sources whose keys cannot be listed are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::prelude::*;
+    /// use hexcfg::adapters::EnvVarAdapter;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut values = HashMap::new();
+    /// values.insert("db.host".to_string(), "localhost".to_string());
+    ///
+    /// let mut service = DefaultConfigService::new();
+    /// service.add_source(Box::new(EnvVarAdapter::with_values(values)));
+    /// service.add_key_alias("db.host", "database.host");
+    ///
+    /// for usage in service.deprecated_usages() {
+    ///     println!("'{}' is deprecated, use '{}' ({})", usage.old_key, usage.new_key, usage.source_name);
+    /// }
+    /// ```
+    pub fn deprecated_usages(&self) -> Vec<DeprecatedKeyUsage> {
+        let mut usages = Vec::new();
+
         for source in &self.sources {
-            match source.get(key) {
-                Ok(Some(value)) => return Ok(Some(value)),
-                Ok(None) => continue,
+            let keys = match source.all_keys() {
+                Ok(keys) => keys,
                 Err(e) => {
-                    // Log the error but continue to next source
-                    tracing::debug!(
-                        "Error querying source '{}' for key '{}': {}",
-                        source.name(),
-                        key,
-                        e
-                    );
+                    tracing::debug!("Error listing keys of source '{}': {}", source.name(), e);
                     continue;
                 }
+            };
+
+            let mut source_usages = Vec::new();
+            for (new_key, old_keys) in &self.aliases {
+                for old_key in old_keys {
+                    let old_key_normalized = self.normalize(old_key);
+                    if keys
+                        .iter()
+                        .any(|k| self.normalize(k.as_str()) == old_key_normalized)
+                    {
+                        source_usages.push(DeprecatedKeyUsage {
+                            old_key: ConfigKey::from(old_key.as_str()),
+                            new_key: ConfigKey::from(new_key.as_str()),
+                            source_name: source.name().to_string(),
+                        });
+                    }
+                }
             }
-        }
-        Ok(None)
-    }
-}
-
-impl Default for DefaultConfigService {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl ConfigurationService for DefaultConfigService {
-    fn get(&self, key: &ConfigKey) -> Result<ConfigValue> {
-        // Check cache first
-        if let Ok(cache) = self.cache.read() {
-            if let Some(value) = cache.get(key.as_str()) {
-                return Ok(value.clone());
-            }
-        }
-
-        // Query sources
-        let value = self
-            .query_sources(key)?
-            .ok_or_else(|| ConfigError::ConfigKeyNotFound {
-                key: key.as_str().to_string(),
-            })?;
-
-        // Update cache
-        if let Ok(mut cache) = self.cache.write() {
-            cache.insert(key.as_str().to_string(), value.clone());
-        }
-
-        Ok(value)
-    }
-
-    fn get_or_default(&self, key: &ConfigKey, default: &str) -> ConfigValue {
-        self.get(key).unwrap_or_else(|_| ConfigValue::from(default))
-    }
-
-    fn has(&self, key: &ConfigKey) -> bool {
-        self.get(key).is_ok()
-    }
 
-    fn reload(&mut self) -> Result<()> {
-        // Reload all sources
-        for source in &mut self.sources {
-            if let Err(e) = source.reload() {
-                tracing::warn!("Failed to reload source '{}': {}", source.name(), e);
-            }
+            // Keep the report stable regardless of hash map iteration order
+            source_usages.sort_by(|a, b| a.old_key.as_str().cmp(b.old_key.as_str()));
+            usages.extend(source_usages);
         }
 
-        // Invalidate cache after reloading
-        self.invalidate_cache();
-
-        Ok(())
-    }
-
-    fn register_watcher(&mut self, watcher: Box<dyn ConfigWatcher>) -> Result<()> {
-        self.watchers.push(watcher);
-        Ok(())
-    }
-}
-
-/// Builder for constructing a `DefaultConfigService`.
-///
-/// This builder provides a fluent interface for configuring and creating
-/// a configuration service with multiple sources.
-///
-/// # Examples
-///
-/// ```rust
-/// use hexcfg::service::ConfigurationServiceBuilder;
-///
-/// # fn main() -> hexcfg::domain::Result<()> {
-/// let service = ConfigurationServiceBuilder::new()
-///     .with_env_vars()
-///     .build()?;
-/// # Ok(())
-/// # }
-/// ```
-pub struct ConfigurationServiceBuilder {
-    sources: Vec<Box<dyn ConfigSource>>,
-}
-
-impl ConfigurationServiceBuilder {
-    /// Creates a new builder.
-    pub fn new() -> Self {
-        Self {
-            sources: Vec::new(),
-        }
+        usages
     }
 
-    /// Adds a configuration source to the builder.
+    /// Returns the name of the source that currently supplies a key.
+    ///
+    /// This is the source whose value [`get`](ConfigurationService::get)
+    /// returns, including sources that only provide a deprecated alias of the
+    /// key. Returns `None` if no source provides the key.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use hexcfg::service::ConfigurationServiceBuilder;
-    /// use hexcfg::adapters::EnvVarAdapter;
+    /// use hexcfg::prelude::*;
+    /// use hexcfg::adapters::{EnvVarAdapter, InMemoryAdapter};
+    /// use std::collections::HashMap;
     ///
-    /// # fn main() -> hexcfg::domain::Result<()> {
-    /// let service = ConfigurationServiceBuilder::new()
-    ///     .with_source(Box::new(EnvVarAdapter::new()))
-    ///     .build()?;
-    /// # Ok(())
-    /// # }
+    /// let mut env = HashMap::new();
+    /// env.insert("db.port".to_string(), "6543".to_string());
+    ///
+    /// let mut service = DefaultConfigService::new();
+    /// service.add_source(Box::new(InMemoryAdapter::new()));
+    /// service.add_source(Box::new(EnvVarAdapter::with_values(env)));
+    ///
+    /// assert_eq!(service.source_of("db.port"), Some("env"));
+    /// assert_eq!(service.source_of("db.host"), None);
     /// ```
-    pub fn with_source(mut self, source: Box<dyn ConfigSource>) -> Self {
-        self.sources.push(source);
-        self
+    pub fn source_of(&self, key: impl Into<ConfigKey>) -> Option<&str> {
+        let key = key.into();
+        let found = match self.query_sources(&key) {
+            Ok(None) => self.query_aliases(&key),
+            found => found,
+        };
+        found.ok().flatten().map(|(_, source_name)| source_name)
     }
 
-    /// Adds environment variables as a configuration source.
+    /// Returns the sources in the order they are queried, highest priority
+    /// first.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use hexcfg::service::ConfigurationServiceBuilder;
+    /// use hexcfg::prelude::*;
+    /// use hexcfg::adapters::{EnvVarAdapter, InMemoryAdapter};
     ///
-    /// # fn main() -> hexcfg::domain::Result<()> {
-    /// let service = ConfigurationServiceBuilder::new()
-    ///     .with_env_vars()
-    ///     .build()?;
-    /// # Ok(())
-    /// # }
+    /// let mut service = DefaultConfigService::new();
+    /// service.add_source(Box::new(EnvVarAdapter::new().with_priority(5)));
+    /// service.add_source(Box::new(InMemoryAdapter::new()));
+    ///
+    /// let order: Vec<_> = service
+    ///     .precedence()
+    ///     .into_iter()
+    ///     .map(|entry| (entry.source_name, entry.priority))
+    ///     .collect();
+    /// assert_eq!(order, vec![("env".to_string(), 5), ("memory".to_string(), 4)]);
     /// ```
-    #[cfg(feature = "env")]
-    pub fn with_env_vars(self) -> Self {
-        use crate::adapters::EnvVarAdapter;
-        self.with_source(Box::new(EnvVarAdapter::new().lowercase_keys(true)))
+    pub fn precedence(&self) -> Vec<PrecedenceEntry> {
+        precedence_of(&self.sources)
     }
 
-    /// Adds environment variables with a prefix as a configuration source.
+    /// Returns the health of each source, in the order they are queried.
+    ///
+    /// See [`DeferredSource`](crate::adapters::DeferredSource) for sources
+    /// that report themselves as stale or unavailable.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use hexcfg::service::ConfigurationServiceBuilder;
+    /// use hexcfg::prelude::*;
+    /// use hexcfg::ports::SourceStatus;
     ///
-    /// # fn main() -> hexcfg::domain::Result<()> {
-    /// let service = ConfigurationServiceBuilder::new()
-    ///     .with_env_prefix("MYAPP_")
-    ///     .build()?;
+    /// # fn main() -> Result<()> {
+    /// let service = DefaultConfigService::builder().with_env_vars().build()?;
+    ///
+    /// for (name, health) in service.source_health() {
+    ///     if let SourceStatus::Stale { since } = health.status {
+    ///         println!("'{}' is stale since {:?}: {:?}", name, since, health.last_error);
+    ///     }
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "env")]
-    pub fn with_env_prefix(self, prefix: impl Into<String>) -> Self {
-        use crate::adapters::EnvVarAdapter;
-        self.with_source(Box::new(
-            EnvVarAdapter::with_prefix(prefix).lowercase_keys(true),
-        ))
+    pub fn source_health(&self) -> Vec<(String, SourceHealth)> {
+        self.sources
+            .iter()
+            .map(|source| (source.name().to_string(), source.health()))
+            .collect()
     }
 
-    /// Adds command-line arguments as a configuration source.
+    /// Returns `true` if every source serves values, for readiness probes.
+    ///
+    /// Stale sources count as ready, since they serve the values they last
+    /// loaded; sources that have not loaded yet do not.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use hexcfg::service::ConfigurationServiceBuilder;
+    /// use hexcfg::prelude::*;
     ///
-    /// # fn main() -> hexcfg::domain::Result<()> {
-    /// let args = vec!["--key", "value"];
-    /// let service = ConfigurationServiceBuilder::new()
-    ///     .with_cli_args(args)
-    ///     .build()?;
+    /// # fn main() -> Result<()> {
+    /// let service = DefaultConfigService::builder().with_env_vars().build()?;
+    /// assert!(service.is_ready());
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "cli")]
-    pub fn with_cli_args<S: AsRef<str>>(self, args: Vec<S>) -> Self {
-        use crate::adapters::CommandLineAdapter;
-        self.with_source(Box::new(CommandLineAdapter::from_args(args)))
+    pub fn is_ready(&self) -> bool {
+        self.sources
+            .iter()
+            .all(|source| !matches!(source.health().status, SourceStatus::Unavailable { .. }))
     }
 
-    /// Adds a YAML file as a configuration source.
+    /// Retrieves a list of configuration values.
+    ///
+    /// Lists are stored under indexed keys, `key.0`, `key.1` and so on, as
+    /// produced by YAML sequences and repeated command-line flags. A source
+    /// with a plain value for `key` instead provides a comma-separated list.
+    /// By default, the whole list comes from the highest-priority source that
+    /// provides either form, so lists from different sources are never mixed.
+    /// A [merge strategy](Self::set_merge_strategy) for the key or a prefix of
+    /// it changes how the list is combined across sources.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::ConfigKeyNotFound` if no source provides the list.
     ///
     /// # Examples
     ///
-    /// ```rust,no_run
-    /// use hexcfg::service::ConfigurationServiceBuilder;
+    /// ```rust
+    /// use hexcfg::prelude::*;
     ///
-    /// # fn main() -> hexcfg::domain::Result<()> {
-    /// let service = ConfigurationServiceBuilder::new()
-    ///     .with_yaml_file("/etc/myapp/config.yaml")?
+    /// # fn main() -> Result<()> {
+    /// let service = DefaultConfigService::builder()
+    ///     .with_cli_args(vec!["--tag", "web", "--tag", "eu-west", "--hosts=a.internal,b.internal"])
     ///     .build()?;
+    ///
+    /// let tags = service.get_list("tag")?;
+    /// assert_eq!(tags, vec![ConfigValue::from("web"), ConfigValue::from("eu-west")]);
+    /// assert_eq!(service.get_list("hosts")?.len(), 2);
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "yaml")]
-    pub fn with_yaml_file(self, path: impl AsRef<std::path::Path>) -> Result<Self> {
-        use crate::adapters::YamlFileAdapter;
-        let adapter = YamlFileAdapter::from_file(path)?;
-        Ok(self.with_source(Box::new(adapter)))
+    pub fn get_list(&self, key: impl Into<ConfigKey>) -> Result<Vec<ConfigValue>> {
+        let key = key.into();
+        let not_found = || ConfigError::ConfigKeyNotFound {
+            key: key.as_str().to_string(),
+        };
+
+        let normalized = ConfigKey::from(self.normalize(key.as_str()));
+        if let Some((prefix, strategy)) = self
+            .subtree_rules()
+            .into_iter()
+            .find(|(prefix, _)| prefix.is_prefix_of(&normalized))
+        {
+            let merged = merge_subtree(&prefix, strategy, &self.layers(&prefix));
+            let values: Vec<ConfigValue> = list_items(&merged, &normalized)
+                .into_iter()
+                .map_while(|mut item| item.remove(""))
+                .collect();
+            return match values.is_empty() {
+                true => Err(not_found()),
+                false => Ok(values),
+            };
+        }
+
+        let item = |index: usize| ConfigKey::from(format!("{}.{}", key, index));
+
+        for index in 0..self.sources.len() {
+            if let Ok(Some(first)) = self.query_source(index, &item(0)) {
+                let mut values = vec![first];
+                while let Ok(Some(value)) = self.query_source(index, &item(values.len())) {
+                    values.push(value);
+                }
+                return Ok(values);
+            }
+
+            if let Ok(Some(value)) = self.query_source(index, &key) {
+                return Ok(split_list(&value));
+            }
+        }
+
+        Err(not_found())
     }
 
-    /// Adds etcd as a configuration source.
+    /// Returns every configuration value, combined across sources.
     ///
-    /// # Arguments
-    ///
-    /// * `endpoints` - List of etcd endpoints (e.g., `vec!["localhost:2379"]`)
-    /// * `prefix` - Optional key prefix for namespacing
+    /// Each key is taken from the highest-priority source that provides it,
+    /// except below prefixes with a [merge strategy](Self::set_merge_strategy),
+    /// whose subtrees are combined as the strategy says. Keys are normalized if
+    /// a normalizer is set. Sources that fail to list or read their keys are
+    /// skipped, as they are by [`get`](ConfigurationService::get).
     ///
     /// # Examples
     ///
-    /// ```rust,no_run
-    /// use hexcfg::service::ConfigurationServiceBuilder;
+    /// ```rust
+    /// use hexcfg::prelude::*;
+    /// use hexcfg::domain::MergeStrategy;
     ///
-    /// # #[tokio::main]
-    /// # async fn main() -> hexcfg::domain::Result<()> {
-    /// let service = ConfigurationServiceBuilder::new()
-    ///     .with_etcd(vec!["localhost:2379"], Some("myapp/")).await?
+    /// # fn main() -> Result<()> {
+    /// let service = DefaultConfigService::builder()
+    ///     .with_cli_args(vec!["--port=8080", "--tag=web"])
+    ///     .with_merge_strategy("tag", MergeStrategy::Append)
     ///     .build()?;
+    ///
+    /// for (key, value) in service.snapshot() {
+    ///     println!("{} = {}", key, value);
+    /// }
+    /// assert_eq!(service.snapshot()["tag.0"].as_str(), "web");
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "etcd")]
-    pub async fn with_etcd<S: AsRef<str>>(
-        self,
+    pub fn snapshot(&self) -> BTreeMap<String, ConfigValue> {
+        let layers = self.layers(&ConfigKey::from(""));
+        let rules = self.subtree_rules();
+
+        let mut snapshot = BTreeMap::new();
+        for layer in &layers {
+            for (key, value) in layer {
+                let config_key = ConfigKey::from(key.as_str());
+                if rules
+                    .iter()
+                    .any(|(prefix, _)| prefix.is_prefix_of(&config_key))
+                {
+                    continue;
+                }
+                snapshot.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+        for (prefix, strategy) in &rules {
+            snapshot.extend(merge_subtree(prefix, *strategy, &layers));
+        }
+        snapshot
+    }
+
+    /// Reads every value below `prefix` of every source, highest priority
+    /// first, with keys normalized.
+    fn layers(&self, prefix: &ConfigKey) -> Vec<BTreeMap<String, ConfigValue>> {
+        let mut layers = Vec::new();
+        for source in &self.sources {
+            let keys = match source.all_keys() {
+                Ok(keys) => keys,
+                Err(e) => {
+                    tracing::debug!("Error listing keys of source '{}': {}", source.name(), e);
+                    continue;
+                }
+            };
+
+            let mut layer = BTreeMap::new();
+            for key in keys {
+                let normalized = self.normalize(key.as_str());
+                if !prefix.is_prefix_of(&ConfigKey::from(normalized.as_str())) {
+                    continue;
+                }
+                match source.get(&key) {
+                    Ok(Some(value)) => {
+                        layer.entry(normalized).or_insert(value);
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::debug!(
+                        "Error querying source '{}' for key '{}': {}",
+                        source.name(),
+                        key,
+                        e
+                    ),
+                }
+            }
+            layers.push(layer);
+        }
+        layers
+    }
+
+    /// Returns the normalized prefixes whose subtrees are combined as a whole,
+    /// leaving out prefixes nested inside another such prefix.
+    fn subtree_rules(&self) -> Vec<(ConfigKey, MergeStrategy)> {
+        let rules: Vec<(ConfigKey, MergeStrategy)> = self
+            .merge_rules
+            .iter()
+            .filter(|(_, strategy)| *strategy != MergeStrategy::DeepMerge)
+            .map(|(prefix, strategy)| (ConfigKey::from(self.normalize(prefix.as_str())), *strategy))
+            .collect();
+
+        let mut outermost: Vec<(ConfigKey, MergeStrategy)> = Vec::new();
+        for (prefix, strategy) in &rules {
+            let nested = rules
+                .iter()
+                .any(|(other, _)| other != prefix && other.is_prefix_of(prefix));
+            if !nested && !outermost.iter().any(|(p, _)| p == prefix) {
+                outermost.push((prefix.clone(), *strategy));
+            }
+        }
+        outermost
+    }
+
+    /// Writes a value to the named source.
+    ///
+    /// The target source must be writable (see [`ConfigSource::as_sink`]). The
+    /// value is written using the source's own key mapping, and cached values
+    /// are invalidated so that subsequent lookups observe the write.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SourceError` if no source has the given name or if the source
+    /// is read-only, or any error reported by the source while writing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::InMemoryAdapter;
+    /// use hexcfg::prelude::*;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut service = DefaultConfigService::new();
+    /// service.add_source(Box::new(InMemoryAdapter::new()));
+    ///
+    /// service.set("database.host", "db.internal", "memory")?;
+    /// assert_eq!(service.get_str("database.host")?.as_str(), "db.internal");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set(
+        &mut self,
+        key: impl Into<ConfigKey>,
+        value: impl Into<ConfigValue>,
+        target_source: &str,
+    ) -> Result<()> {
+        let result = self
+            .sink_for(target_source)?
+            .set(&key.into(), &value.into());
+        self.invalidate_cache();
+        result
+    }
+
+    /// Deletes a key from the named source.
+    ///
+    /// Deleting a key that does not exist succeeds. Errors are reported as for
+    /// [`set`](Self::set).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::InMemoryAdapter;
+    /// use hexcfg::prelude::*;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut service = DefaultConfigService::new();
+    /// service.add_source(Box::new(InMemoryAdapter::new()));
+    ///
+    /// service.set("feature.beta", "true", "memory")?;
+    /// service.delete("feature.beta", "memory")?;
+    /// assert!(!service.has_str("feature.beta"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete(&mut self, key: impl Into<ConfigKey>, target_source: &str) -> Result<()> {
+        let result = self.sink_for(target_source)?.delete(&key.into());
+        self.invalidate_cache();
+        result
+    }
+
+    /// Applies a batch of changes to the named source.
+    ///
+    /// Sources backed by transactional stores apply the batch atomically.
+    /// Errors are reported as for [`set`](Self::set).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::InMemoryAdapter;
+    /// use hexcfg::ports::ConfigChange;
+    /// use hexcfg::prelude::*;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut service = DefaultConfigService::new();
+    /// service.add_source(Box::new(InMemoryAdapter::new()));
+    ///
+    /// service.apply(
+    ///     &[
+    ///         ConfigChange::set("database.host", "db.internal"),
+    ///         ConfigChange::set("database.port", "5432"),
+    ///     ],
+    ///     "memory",
+    /// )?;
+    /// assert_eq!(service.get_str("database.port")?.as_str(), "5432");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn apply(&mut self, changes: &[ConfigChange], target_source: &str) -> Result<()> {
+        let result = self.sink_for(target_source)?.apply(changes);
+        self.invalidate_cache();
+        result
+    }
+
+    /// Clears cached values without reloading any source.
+    ///
+    /// Use this from a watcher callback when the watcher already keeps its
+    /// source up to date, such as one created with `EtcdAdapter::watcher`, so
+    /// the next lookup sees the new value without a full reload.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::service::DefaultConfigService;
+    ///
+    /// let service = DefaultConfigService::new();
+    /// service.clear_cache();
+    /// ```
+    pub fn clear_cache(&self) {
+        self.invalidate_cache();
+    }
+
+    /// Finds the writable source with the given name.
+    fn sink_for(&mut self, target_source: &str) -> Result<&mut dyn ConfigSink> {
+        let source = self
+            .sources
+            .iter_mut()
+            .find(|s| s.name() == target_source)
+            .ok_or_else(|| ConfigError::SourceError {
+                source_name: target_source.to_string(),
+                message: format!("No configuration source named '{}'", target_source),
+                source: None,
+            })?;
+
+        source.as_sink().ok_or_else(|| ConfigError::SourceError {
+            source_name: target_source.to_string(),
+            message: format!("Configuration source '{}' is read-only", target_source),
+            source: None,
+        })
+    }
+
+    /// Sorts sources by priority (highest first).
+    fn sort_sources(&mut self) {
+        // A stable sort, so equal priorities keep their insertion order
+        self.sources
+            .sort_by_key(|b| std::cmp::Reverse(b.priority()));
+    }
+
+    /// Invalidates the cache and the normalized key index.
+    fn invalidate_cache(&self) {
+        if let Ok(mut cache) = self.cache.write() {
+            cache.clear();
+        }
+        if let Ok(mut key_index) = self.key_index.write() {
+            key_index.clear();
+        }
+    }
+
+    /// Returns the normalized form of a key, or the key itself without a normalizer.
+    fn normalize(&self, key: &str) -> String {
+        match &self.normalizer {
+            Some(normalizer) => normalizer.normalize(key),
+            None => key.to_string(),
+        }
+    }
+
+    /// Queries the source at `index`, matching keys through the normalizer if
+    /// one is set.
+    fn query_source(&self, index: usize, key: &ConfigKey) -> Result<Option<ConfigValue>> {
+        let source = self.sources[index].as_ref();
+        if let Some(value) = source.get(key)? {
+            return Ok(Some(value));
+        }
+
+        let Some(normalizer) = &self.normalizer else {
+            return Ok(None);
+        };
+
+        let wanted = normalizer.normalize(key.as_str());
+        match self.indexed_key(index, normalizer.as_ref(), &wanted)? {
+            Some(candidate) if candidate != *key => source.get(&candidate),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the key of the source at `index` whose normalized form is
+    /// `wanted`, indexing the source's keys on first use.
+    fn indexed_key(
+        &self,
+        index: usize,
+        normalizer: &dyn KeyNormalizer,
+        wanted: &str,
+    ) -> Result<Option<ConfigKey>> {
+        if let Ok(key_index) = self.key_index.read() {
+            if let Some(keys) = key_index.get(&index) {
+                return Ok(keys.get(wanted).cloned());
+            }
+        }
+
+        let source = self.sources[index].as_ref();
+        let mut keys = HashMap::new();
+        for candidate in source.all_keys()? {
+            keys.entry(normalizer.normalize(candidate.as_str()))
+                .or_insert(candidate);
+        }
+        let found = keys.get(wanted).cloned();
+
+        // A source that may still load or recover keys is indexed again
+        if source.health().status == SourceStatus::Loaded {
+            if let Ok(mut key_index) = self.key_index.write() {
+                key_index.insert(index, keys);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Queries all sources for a configuration value, respecting priority order.
+    ///
+    /// Returns the value together with the name of the source that provided it.
+    fn query_sources(&self, key: &ConfigKey) -> Result<Option<(ConfigValue, &str)>> {
+        for (index, source) in self.sources.iter().enumerate() {
+            match self.query_source(index, key) {
+                Ok(Some(value)) => return Ok(Some((value, source.name()))),
+                Ok(None) => continue,
+                Err(e) => {
+                    // Log the error but continue to next source
+                    tracing::debug!(
+                        "Error querying source '{}' for key '{}': {}",
+                        source.name(),
+                        key,
+                        e
+                    );
+                    continue;
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Looks up a normalized key in the combined subtree of the merge strategy
+    /// covering it, or returns `None` if no strategy other than
+    /// [`DeepMerge`](MergeStrategy::DeepMerge) covers the key.
+    fn query_subtree(&self, normalized: &str) -> Option<Option<ConfigValue>> {
+        let key = ConfigKey::from(normalized);
+        let (prefix, strategy) = self
+            .subtree_rules()
+            .into_iter()
+            .find(|(prefix, _)| prefix.is_prefix_of(&key))?;
+        let mut merged = merge_subtree(&prefix, strategy, &self.layers(&prefix));
+        Some(merged.remove(normalized))
+    }
+
+    /// Queries all sources for the deprecated aliases of a key.
+    ///
+    /// Returns the value together with the name of the source that provided it.
+    fn query_aliases(&self, key: &ConfigKey) -> Result<Option<(ConfigValue, &str)>> {
+        let wanted = self.normalize(key.as_str());
+        let Some(old_keys) = self
+            .aliases
+            .iter()
+            .find(|(new_key, _)| self.normalize(new_key) == wanted)
+            .map(|(_, old_keys)| old_keys)
+        else {
+            return Ok(None);
+        };
+
+        for old_key in old_keys {
+            let old_key = ConfigKey::from(old_key.as_str());
+            if let Some((value, source_name)) = self.query_sources(&old_key)? {
+                self.warn_deprecated(&old_key, key, source_name);
+                return Ok(Some((value, source_name)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Logs a deprecation warning the first time a deprecated key is used.
+    fn warn_deprecated(&self, old_key: &ConfigKey, new_key: &ConfigKey, source_name: &str) {
+        if let Ok(mut warned) = self.warned_aliases.lock() {
+            if warned.insert(self.normalize(old_key.as_str())) {
+                tracing::warn!(
+                    "Configuration key '{}' is deprecated, use '{}' instead (found in source '{}')",
+                    old_key,
+                    new_key,
+                    source_name
+                );
+            }
+        }
+    }
+}
+
+impl Default for DefaultConfigService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigurationService for DefaultConfigService {
+    fn get(&self, key: &ConfigKey) -> Result<ConfigValue> {
+        let cache_key = self.normalize(key.as_str());
+
+        // Check cache first
+        if let Ok(cache) = self.cache.read() {
+            if let Some(value) = cache.get(&cache_key) {
+                return Ok(value.clone());
+            }
+        }
+
+        // Keys below a prefix with a merge strategy come from the combined
+        // subtree, others from the sources in order
+        let found = match self.query_subtree(&cache_key) {
+            Some(merged) => merged,
+            None => self.query_sources(key)?.map(|(value, _)| value),
+        };
+
+        // Fall back to deprecated aliases of the key
+        let value = match found {
+            Some(value) => value,
+            None => {
+                self.query_aliases(key)?
+                    .ok_or_else(|| ConfigError::ConfigKeyNotFound {
+                        key: key.as_str().to_string(),
+                    })?
+                    .0
+            }
+        };
+
+        // Update cache, unless a source may still load or recover values that
+        // would override this one
+        let settled = self
+            .sources
+            .iter()
+            .all(|source| source.health().status == SourceStatus::Loaded);
+        if settled {
+            if let Ok(mut cache) = self.cache.write() {
+                cache.insert(cache_key, value.clone());
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn get_or_default(&self, key: &ConfigKey, default: &str) -> ConfigValue {
+        self.get(key).unwrap_or_else(|_| ConfigValue::from(default))
+    }
+
+    fn has(&self, key: &ConfigKey) -> bool {
+        self.get(key).is_ok()
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        // Reload all sources
+        for source in &mut self.sources {
+            if let Err(e) = source.reload() {
+                tracing::warn!("Failed to reload source '{}': {}", source.name(), e);
+            }
+        }
+
+        // Invalidate cache after reloading
+        self.invalidate_cache();
+
+        Ok(())
+    }
+
+    fn register_watcher(&mut self, watcher: Box<dyn ConfigWatcher>) -> Result<()> {
+        self.watchers.push(watcher);
+        Ok(())
+    }
+}
+
+/// Builder for constructing a `DefaultConfigService`.
+///
+/// This builder provides a fluent interface for configuring and creating
+/// a configuration service with multiple sources.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexcfg::service::ConfigurationServiceBuilder;
+///
+/// # fn main() -> hexcfg::domain::Result<()> {
+/// let service = ConfigurationServiceBuilder::new()
+///     .with_env_vars()
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ConfigurationServiceBuilder {
+    sources: Vec<Box<dyn ConfigSource>>,
+    aliases: Vec<(ConfigKey, ConfigKey)>,
+    normalizer: Option<Arc<dyn KeyNormalizer>>,
+    merge_rules: Vec<(ConfigKey, MergeStrategy)>,
+}
+
+impl ConfigurationServiceBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+            aliases: Vec::new(),
+            normalizer: None,
+            merge_rules: Vec::new(),
+        }
+    }
+
+    /// Sets the normalizer used to match keys across sources.
+    ///
+    /// See [`DefaultConfigService::set_key_normalizer`] for the matching semantics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::domain::StandardKeyNormalizer;
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_env_vars()
+    ///     .with_key_normalizer(StandardKeyNormalizer::new())
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_key_normalizer(mut self, normalizer: impl KeyNormalizer + 'static) -> Self {
+        self.normalizer = Some(Arc::new(normalizer));
+        self
+    }
+
+    /// Registers a deprecated alias for a configuration key.
+    ///
+    /// See [`DefaultConfigService::add_key_alias`] for the lookup semantics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_env_vars()
+    ///     .with_key_alias("db.host", "database.host")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_key_alias(
+        mut self,
+        old_key: impl Into<ConfigKey>,
+        new_key: impl Into<ConfigKey>,
+    ) -> Self {
+        self.aliases.push((old_key.into(), new_key.into()));
+        self
+    }
+
+    /// Sets how values below `prefix` are combined across sources.
+    ///
+    /// See [`DefaultConfigService::set_merge_strategy`] for the semantics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::domain::MergeStrategy;
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_env_vars()
+    ///     .with_merge_strategy("servers", MergeStrategy::Replace)
+    ///     .with_merge_strategy("plugins", MergeStrategy::UniqueUnion)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_merge_strategy(
+        mut self,
+        prefix: impl Into<ConfigKey>,
+        strategy: MergeStrategy,
+    ) -> Self {
+        self.merge_rules.push((prefix.into(), strategy));
+        self
+    }
+
+    /// Adds a configuration source to the builder.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    /// use hexcfg::adapters::EnvVarAdapter;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_source(Box::new(EnvVarAdapter::new()))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_source(mut self, source: Box<dyn ConfigSource>) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// Adds a configuration source with its priority replaced by `priority`.
+    ///
+    /// This wraps the source in [`Prioritized`](crate::adapters::Prioritized),
+    /// which works for any source, including those without a `with_priority`
+    /// method of their own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    /// use hexcfg::adapters::{EnvVarAdapter, InMemoryAdapter};
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let builder = ConfigurationServiceBuilder::new()
+    ///     .with_source(Box::new(InMemoryAdapter::new()))
+    ///     .with_source_priority(Box::new(EnvVarAdapter::new()), 10);
+    /// assert_eq!(builder.precedence()[0].source_name, "env");
+    ///
+    /// let service = builder.build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_source_priority(self, source: Box<dyn ConfigSource>, priority: u8) -> Self {
+        use crate::adapters::Prioritized;
+        self.with_source(Box::new(Prioritized::new(source, priority)))
+    }
+
+    /// Returns the order in which the built service will query the sources
+    /// added so far, highest priority first.
+    ///
+    /// Sources with equal priority are queried in the order they were added.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// let builder = ConfigurationServiceBuilder::new()
+    ///     .with_env_vars()
+    ///     .with_cli_args(vec!["--port=8080"]);
+    ///
+    /// for entry in builder.precedence() {
+    ///     println!("{} (priority {})", entry.source_name, entry.priority);
+    /// }
+    /// ```
+    pub fn precedence(&self) -> Vec<PrecedenceEntry> {
+        let mut entries = precedence_of(&self.sources);
+        entries.sort_by_key(|e| std::cmp::Reverse(e.priority));
+        entries
+    }
+
+    /// Adds a source created by `factory`, applying `policy` if that fails.
+    ///
+    /// The source is wrapped in a
+    /// [`DeferredSource`](crate::adapters::DeferredSource) with the given name
+    /// and priority. An optional source that fails to load is left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns the factory's error if the policy is
+    /// [`Required`](crate::adapters::SourcePolicy::Required).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::{SourcePolicy, YamlFileAdapter};
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let policy = SourcePolicy::BestEffort {
+    ///     retry_interval: Duration::from_secs(30),
+    /// };
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_source_policy("yaml-file", 1, policy, || {
+    ///         Ok(Box::new(YamlFileAdapter::from_file("/etc/myapp/site.yaml")?))
+    ///     })?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_source_policy(
+        self,
+        name: &str,
+        priority: u8,
+        policy: crate::adapters::SourcePolicy,
+        factory: impl Fn() -> Result<Box<dyn ConfigSource>> + Send + Sync + 'static,
+    ) -> Result<Self> {
+        use crate::adapters::DeferredSource;
+        let source = DeferredSource::new(name, priority, policy, factory)?;
+        Ok(self.with_deferred_source(source, policy))
+    }
+
+    /// Adds a deferred source, leaving out optional sources that failed to load.
+    fn with_deferred_source(
+        self,
+        source: crate::adapters::DeferredSource,
+        policy: crate::adapters::SourcePolicy,
+    ) -> Self {
+        use crate::adapters::SourcePolicy;
+        if policy == SourcePolicy::Optional && !source.is_loaded() {
+            tracing::info!("Skipping optional configuration source '{}'", source.name());
+            return self;
+        }
+        self.with_source(Box::new(source))
+    }
+
+    /// Adds environment variables as a configuration source.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_env_vars()
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "env")]
+    pub fn with_env_vars(self) -> Self {
+        use crate::adapters::EnvVarAdapter;
+        self.with_source(Box::new(EnvVarAdapter::new().lowercase_keys(true)))
+    }
+
+    /// Adds environment variables with a prefix as a configuration source.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_env_prefix("MYAPP_")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "env")]
+    pub fn with_env_prefix(self, prefix: impl Into<String>) -> Self {
+        use crate::adapters::EnvVarAdapter;
+        self.with_source(Box::new(
+            EnvVarAdapter::with_prefix(prefix).lowercase_keys(true),
+        ))
+    }
+
+    /// Adds command-line arguments as a configuration source.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let args = vec!["--key", "value"];
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_cli_args(args)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "cli")]
+    pub fn with_cli_args<S: AsRef<str>>(self, args: Vec<S>) -> Self {
+        use crate::adapters::CommandLineAdapter;
+        self.with_source(Box::new(CommandLineAdapter::from_args(args)))
+    }
+
+    /// Adds arguments parsed by `clap` as a configuration source.
+    ///
+    /// Only the keys of `schema` that were given on the command line are
+    /// provided. See [`CliSchema`](crate::adapters::CliSchema) for deriving
+    /// the command from typed keys.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::CliSchema;
+    /// use hexcfg::domain::TypedKey;
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// const LOG_LEVEL: TypedKey<String> = TypedKey::new("log.level").describe("Log level");
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let schema = CliSchema::new([LOG_LEVEL.info()]);
+    /// let matches = schema
+    ///     .command("myapp")
+    ///     .get_matches_from(["myapp", "--log.level", "debug"]);
+    ///
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_cli_matches(&matches, &schema)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "cli")]
+    pub fn with_cli_matches(
+        self,
+        matches: &clap::ArgMatches,
+        schema: &crate::adapters::CliSchema,
+    ) -> Self {
+        use crate::adapters::CommandLineAdapter;
+        self.with_source(Box::new(CommandLineAdapter::from_matches(matches, schema)))
+    }
+
+    /// Adds a YAML file as a configuration source.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_yaml_file("/etc/myapp/config.yaml")?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn with_yaml_file(self, path: impl AsRef<std::path::Path>) -> Result<Self> {
+        use crate::adapters::YamlFileAdapter;
+        let adapter = YamlFileAdapter::from_file(path)?;
+        Ok(self.with_source(Box::new(adapter)))
+    }
+
+    /// Adds a YAML file as a configuration source with the given priority,
+    /// applying `policy` if it cannot be read.
+    ///
+    /// An [`Optional`](crate::adapters::SourcePolicy::Optional) file is left
+    /// out only if it does not exist; a file that exists but cannot be read or
+    /// parsed is still an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed and the policy is
+    /// [`Required`](crate::adapters::SourcePolicy::Required), or if it exists
+    /// but cannot be read or parsed and the policy is
+    /// [`Optional`](crate::adapters::SourcePolicy::Optional).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use hexcfg::adapters::SourcePolicy;
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// // A missing local override file is fine
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_yaml_file_policy("/etc/myapp/local.yaml", 1, SourcePolicy::Optional)?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn with_yaml_file_policy(
+        self,
+        path: impl AsRef<std::path::Path>,
+        priority: u8,
+        policy: crate::adapters::SourcePolicy,
+    ) -> Result<Self> {
+        use crate::adapters::{DeferredSource, SourcePolicy, YamlFileAdapter};
+        let path = path.as_ref().to_path_buf();
+        let factory = move || -> Result<Box<dyn ConfigSource>> {
+            Ok(Box::new(YamlFileAdapter::from_file(&path)?))
+        };
+
+        let first = match factory() {
+            Err(e) if policy == SourcePolicy::Optional && !is_not_found(&e) => return Err(e),
+            first => first,
+        };
+        let source = DeferredSource::with_first_attempt(
+            "yaml-file",
+            priority,
+            policy,
+            Arc::new(factory),
+            first,
+        )?;
+        Ok(self.with_deferred_source(source, policy))
+    }
+
+    /// Adds a directory with one file per key as a configuration source.
+    ///
+    /// Subdirectories are read as dotted key prefixes. See
+    /// [`KeyPerFileAdapter`](crate::adapters::KeyPerFileAdapter) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_key_per_file_dir("/etc/myapp")?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "key-per-file")]
+    pub fn with_key_per_file_dir(self, dir: impl AsRef<std::path::Path>) -> Result<Self> {
+        use crate::adapters::KeyPerFileAdapter;
+        let adapter = KeyPerFileAdapter::from_dir_nested(dir)?;
+        Ok(self.with_source(Box::new(adapter)))
+    }
+
+    /// Adds etcd as a configuration source.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - List of etcd endpoints (e.g., `vec!["localhost:2379"]`)
+    /// * `prefix` - Optional key prefix for namespacing
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_etcd(vec!["localhost:2379"], Some("myapp/")).await?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "etcd")]
+    pub async fn with_etcd<S: AsRef<str>>(
+        self,
         endpoints: Vec<S>,
         prefix: Option<&str>,
     ) -> Result<Self> {
@@ -387,6 +1486,127 @@ impl ConfigurationServiceBuilder {
         Ok(self.with_source(Box::new(adapter)))
     }
 
+    /// Adds etcd as a configuration source with the given priority, applying
+    /// `policy` if it cannot be reached.
+    ///
+    /// With [`BestEffort`](crate::adapters::SourcePolicy::BestEffort), the
+    /// service starts without etcd's values and connects in the background.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails and the policy is
+    /// [`Required`](crate::adapters::SourcePolicy::Required).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::SourcePolicy;
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let policy = SourcePolicy::BestEffort {
+    ///     retry_interval: Duration::from_secs(10),
+    /// };
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_etcd_policy(vec!["localhost:2379"], Some("myapp/"), 1, policy).await?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "etcd")]
+    pub async fn with_etcd_policy<S: AsRef<str>>(
+        self,
+        endpoints: Vec<S>,
+        prefix: Option<&str>,
+        priority: u8,
+        policy: crate::adapters::SourcePolicy,
+    ) -> Result<Self> {
+        use crate::adapters::EtcdConnectOptions;
+        self.with_etcd_options_policy(
+            endpoints,
+            prefix,
+            EtcdConnectOptions::new(),
+            priority,
+            policy,
+        )
+        .await
+    }
+
+    /// Adds etcd as a configuration source with connection options and the
+    /// given priority, applying `policy` if it cannot be reached.
+    ///
+    /// The options are used for the first connection and for every retry. See
+    /// [`with_etcd_policy`](Self::with_etcd_policy) and
+    /// [`with_etcd_options`](Self::with_etcd_options).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails and the policy is
+    /// [`Required`](crate::adapters::SourcePolicy::Required).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{EtcdConnectOptions, SourcePolicy};
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let options = EtcdConnectOptions::new()
+    ///     .with_user("myapp", "secret")
+    ///     .with_ca_cert_file("/etc/etcd/ca.pem")?;
+    /// let policy = SourcePolicy::BestEffort {
+    ///     retry_interval: Duration::from_secs(10),
+    /// };
+    ///
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_etcd_options_policy(
+    ///         vec!["etcd.internal:2379"],
+    ///         Some("myapp/"),
+    ///         options,
+    ///         1,
+    ///         policy,
+    ///     )
+    ///     .await?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "etcd")]
+    pub async fn with_etcd_options_policy<S: AsRef<str>>(
+        self,
+        endpoints: Vec<S>,
+        prefix: Option<&str>,
+        options: crate::adapters::EtcdConnectOptions,
+        priority: u8,
+        policy: crate::adapters::SourcePolicy,
+    ) -> Result<Self> {
+        use crate::adapters::runtime::block_on;
+        use crate::adapters::{DeferredSource, EtcdAdapter};
+
+        let endpoints: Vec<String> = endpoints.iter().map(|e| e.as_ref().to_string()).collect();
+        let prefix = prefix.map(str::to_string);
+        let first =
+            EtcdAdapter::with_options(endpoints.clone(), prefix.as_deref(), options.clone())
+                .await
+                .map(|adapter| Box::new(adapter) as Box<dyn ConfigSource>);
+
+        let factory = move || -> Result<Box<dyn ConfigSource>> {
+            let adapter = block_on(EtcdAdapter::with_options(
+                endpoints.clone(),
+                prefix.as_deref(),
+                options.clone(),
+            ))?;
+            Ok(Box::new(adapter))
+        };
+        let source =
+            DeferredSource::with_first_attempt("etcd", priority, policy, Arc::new(factory), first)?;
+        Ok(self.with_deferred_source(source, policy))
+    }
+
     /// Adds etcd as a configuration source with custom priority.
     ///
     /// # Arguments
@@ -420,6 +1640,46 @@ impl ConfigurationServiceBuilder {
         Ok(self.with_source(Box::new(adapter)))
     }
 
+    /// Adds etcd as a configuration source with connection options.
+    ///
+    /// Use this for clusters that require authentication, TLS or custom timeouts.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - List of etcd endpoints
+    /// * `prefix` - Optional key prefix for namespacing
+    /// * `options` - Connection options for the adapter
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::EtcdConnectOptions;
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let options = EtcdConnectOptions::new()
+    ///     .with_user("myapp", "secret")
+    ///     .with_ca_cert_file("/etc/etcd/ca.pem")?;
+    ///
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_etcd_options(vec!["etcd.internal:2379"], Some("myapp/"), options).await?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "etcd")]
+    pub async fn with_etcd_options<S: AsRef<str>>(
+        self,
+        endpoints: Vec<S>,
+        prefix: Option<&str>,
+        options: crate::adapters::EtcdConnectOptions,
+    ) -> Result<Self> {
+        use crate::adapters::EtcdAdapter;
+        let adapter = EtcdAdapter::with_options(endpoints, prefix, options).await?;
+        Ok(self.with_source(Box::new(adapter)))
+    }
+
     /// Adds Redis as a configuration source.
     ///
     /// # Arguments
@@ -482,16 +1742,220 @@ impl ConfigurationServiceBuilder {
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "redis")]
-    pub async fn with_redis_priority(
+    #[cfg(feature = "redis")]
+    pub async fn with_redis_priority(
+        self,
+        url: &str,
+        namespace: &str,
+        storage_mode: crate::adapters::RedisStorageMode,
+        priority: u8,
+    ) -> Result<Self> {
+        use crate::adapters::RedisAdapter;
+        let adapter = RedisAdapter::with_priority(url, namespace, storage_mode, priority).await?;
+        Ok(self.with_source(Box::new(adapter)))
+    }
+
+    /// Adds Redis as a configuration source with connection options.
+    ///
+    /// Use this to select a database, authenticate, use TLS, or connect through
+    /// Sentinel or to a Cluster.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Connection options for the adapter
+    /// * `namespace` - Key prefix (for StringKeys mode) or hash key name (for Hash mode)
+    /// * `storage_mode` - Whether to use string keys or hash storage
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::{RedisConnectOptions, RedisStorageMode};
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let options = RedisConnectOptions::new("rediss://redis.internal:6380")
+    ///     .with_db(2)
+    ///     .with_user("myapp", "secret");
+    ///
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_redis_options(options, "myapp:", RedisStorageMode::StringKeys).await?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "redis")]
+    pub async fn with_redis_options(
+        self,
+        options: crate::adapters::RedisConnectOptions,
+        namespace: &str,
+        storage_mode: crate::adapters::RedisStorageMode,
+    ) -> Result<Self> {
+        use crate::adapters::RedisAdapter;
+        let adapter = RedisAdapter::with_options(options, namespace, storage_mode).await?;
+        Ok(self.with_source(Box::new(adapter)))
+    }
+
+    /// Adds Consul's KV store as a configuration source.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Consul agent address (e.g., `"http://localhost:8500"`)
+    /// * `prefix` - Optional key prefix for namespacing
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_consul("http://localhost:8500", Some("myapp/")).await?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "consul")]
+    pub async fn with_consul(self, address: &str, prefix: Option<&str>) -> Result<Self> {
+        use crate::adapters::ConsulAdapter;
+        let adapter = ConsulAdapter::new(address, prefix).await?;
+        Ok(self.with_source(Box::new(adapter)))
+    }
+
+    /// Adds Consul's KV store as a configuration source with custom priority.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Consul agent address
+    /// * `prefix` - Optional key prefix for namespacing
+    /// * `priority` - Priority for this source (higher values override lower values)
+    #[cfg(feature = "consul")]
+    pub async fn with_consul_priority(
+        self,
+        address: &str,
+        prefix: Option<&str>,
+        priority: u8,
+    ) -> Result<Self> {
+        use crate::adapters::ConsulAdapter;
+        let adapter = ConsulAdapter::with_priority(address, prefix, priority).await?;
+        Ok(self.with_source(Box::new(adapter)))
+    }
+
+    /// Adds Consul's KV store as a configuration source with connection options.
+    ///
+    /// Use this for agents that require an ACL token or to read from another
+    /// datacenter.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - Consul agent address
+    /// * `prefix` - Optional key prefix for namespacing
+    /// * `options` - Connection options for the adapter
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::ConsulConnectOptions;
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let options = ConsulConnectOptions::new()
+    ///     .with_token("b1gs33cr3t")
+    ///     .with_datacenter("eu-west");
+    ///
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_consul_options("http://consul.internal:8500", Some("myapp/"), options).await?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "consul")]
+    pub async fn with_consul_options(
+        self,
+        address: &str,
+        prefix: Option<&str>,
+        options: crate::adapters::ConsulConnectOptions,
+    ) -> Result<Self> {
+        use crate::adapters::ConsulAdapter;
+        let adapter = ConsulAdapter::with_options(address, prefix, options).await?;
+        Ok(self.with_source(Box::new(adapter)))
+    }
+
+    /// Adds a configuration document served over HTTP(S) as a source.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Document URL (e.g., `"https://config.internal/myapp.yaml"`)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_http("https://config.internal/myapp.yaml").await?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "http")]
+    pub async fn with_http(self, url: &str) -> Result<Self> {
+        use crate::adapters::HttpAdapter;
+        let adapter = HttpAdapter::new(url).await?;
+        Ok(self.with_source(Box::new(adapter)))
+    }
+
+    /// Adds a configuration document served over HTTP(S) with custom priority.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Document URL
+    /// * `priority` - Priority for this source (higher values override lower values)
+    #[cfg(feature = "http")]
+    pub async fn with_http_priority(self, url: &str, priority: u8) -> Result<Self> {
+        use crate::adapters::HttpAdapter;
+        let adapter = HttpAdapter::with_priority(url, priority).await?;
+        Ok(self.with_source(Box::new(adapter)))
+    }
+
+    /// Adds a configuration document served over HTTP(S) with request options.
+    ///
+    /// Use this to send authentication or other headers, or to register
+    /// parsers for other formats.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Document URL
+    /// * `options` - Request options for the adapter
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use hexcfg::adapters::HttpOptions;
+    /// use hexcfg::service::ConfigurationServiceBuilder;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> hexcfg::domain::Result<()> {
+    /// let options = HttpOptions::new().with_bearer_token("b1gs33cr3t");
+    ///
+    /// let service = ConfigurationServiceBuilder::new()
+    ///     .with_http_options("https://config.internal/myapp.json", options).await?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "http")]
+    pub async fn with_http_options(
         self,
         url: &str,
-        namespace: &str,
-        storage_mode: crate::adapters::RedisStorageMode,
-        priority: u8,
+        options: crate::adapters::HttpOptions,
     ) -> Result<Self> {
-        use crate::adapters::RedisAdapter;
-        let adapter = RedisAdapter::with_priority(url, namespace, storage_mode, priority).await?;
+        use crate::adapters::HttpAdapter;
+        let adapter = HttpAdapter::with_options(url, options).await?;
         Ok(self.with_source(Box::new(adapter)))
     }
 
@@ -511,15 +1975,158 @@ impl ConfigurationServiceBuilder {
     /// ```
     pub fn build(self) -> Result<DefaultConfigService> {
         let mut service = DefaultConfigService::new();
+        service.normalizer = self.normalizer;
 
         for source in self.sources {
             service.add_source(source);
         }
 
+        for (old_key, new_key) in self.aliases {
+            service.add_key_alias(old_key, new_key);
+        }
+
+        for (prefix, strategy) in self.merge_rules {
+            service.set_merge_strategy(prefix, strategy);
+        }
+
         Ok(service)
     }
 }
 
+/// Splits a comma-separated list value, keeping its secret flag.
+fn split_list(value: &ConfigValue) -> Vec<ConfigValue> {
+    value
+        .as_str()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| match value.is_secret() {
+            true => ConfigValue::secret(item),
+            false => ConfigValue::from(item),
+        })
+        .collect()
+}
+
+/// Reads the list at `prefix` from flattened `entries`.
+///
+/// Each item maps the rest of its keys after `prefix.<index>` to their values,
+/// with the empty string for the item's own value. Indexed items take
+/// precedence over a comma-separated value at `prefix`.
+fn list_items(
+    entries: &BTreeMap<String, ConfigValue>,
+    prefix: &ConfigKey,
+) -> Vec<BTreeMap<String, ConfigValue>> {
+    let mut items = Vec::new();
+    loop {
+        let item_key = prefix.child(KeySegment::Index(items.len()));
+        let item: BTreeMap<String, ConfigValue> = entries
+            .iter()
+            .filter_map(|(key, value)| {
+                let rest = match key.strip_prefix(item_key.as_str())? {
+                    "" => "",
+                    rest => rest.strip_prefix('.')?,
+                };
+                Some((rest.to_string(), value.clone()))
+            })
+            .collect();
+        if item.is_empty() {
+            break;
+        }
+        items.push(item);
+    }
+
+    if items.is_empty() {
+        if let Some(value) = entries.get(prefix.as_str()) {
+            items = split_list(value)
+                .into_iter()
+                .map(|value| BTreeMap::from([(String::new(), value)]))
+                .collect();
+        }
+    }
+    items
+}
+
+/// Combines the subtrees below `prefix` of `layers`, given highest priority
+/// first, as `strategy` says.
+fn merge_subtree(
+    prefix: &ConfigKey,
+    strategy: MergeStrategy,
+    layers: &[BTreeMap<String, ConfigValue>],
+) -> BTreeMap<String, ConfigValue> {
+    let subtrees = layers.iter().map(|layer| -> BTreeMap<String, ConfigValue> {
+        layer
+            .iter()
+            .filter(|(key, _)| prefix.is_prefix_of(&ConfigKey::from(key.as_str())))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    });
+
+    match strategy {
+        MergeStrategy::Replace => subtrees
+            .into_iter()
+            .find(|t| !t.is_empty())
+            .unwrap_or_default(),
+        MergeStrategy::DeepMerge => {
+            let mut merged = BTreeMap::new();
+            for subtree in subtrees {
+                for (key, value) in subtree {
+                    merged.entry(key).or_insert(value);
+                }
+            }
+            merged
+        }
+        MergeStrategy::Append | MergeStrategy::UniqueUnion => {
+            let subtrees: Vec<_> = subtrees.collect();
+            let mut items: Vec<BTreeMap<String, ConfigValue>> = Vec::new();
+            for subtree in subtrees.iter().rev() {
+                for item in list_items(subtree, prefix) {
+                    if strategy == MergeStrategy::UniqueUnion && items.contains(&item) {
+                        continue;
+                    }
+                    items.push(item);
+                }
+            }
+
+            let mut merged = BTreeMap::new();
+            for (index, item) in items.into_iter().enumerate() {
+                let item_key = prefix.child(KeySegment::Index(index));
+                for (rest, value) in item {
+                    let key = match rest.is_empty() {
+                        true => item_key.to_string(),
+                        false => format!("{}.{}", item_key, rest),
+                    };
+                    merged.insert(key, value);
+                }
+            }
+            merged
+        }
+    }
+}
+
+/// Returns `true` if `error` was caused by a file that does not exist.
+#[cfg(feature = "yaml")]
+fn is_not_found(error: &ConfigError) -> bool {
+    let mut cause = std::error::Error::source(error);
+    while let Some(e) = cause {
+        if let Some(io) = e.downcast_ref::<std::io::Error>() {
+            return io.kind() == std::io::ErrorKind::NotFound;
+        }
+        cause = e.source();
+    }
+    false
+}
+
+/// Lists the names and priorities of `sources`, in the given order.
+fn precedence_of(sources: &[Box<dyn ConfigSource>]) -> Vec<PrecedenceEntry> {
+    sources
+        .iter()
+        .map(|source| PrecedenceEntry {
+            source_name: source.name().to_string(),
+            priority: source.priority(),
+        })
+        .collect()
+}
+
 impl Default for ConfigurationServiceBuilder {
     fn default() -> Self {
         Self::new()
@@ -529,6 +2136,7 @@ impl Default for ConfigurationServiceBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::StandardKeyNormalizer;
     use crate::ports::ConfigSource;
 
     // Mock source for testing
@@ -612,6 +2220,31 @@ mod tests {
         assert_eq!(service.sources[2].name(), "low");
     }
 
+    #[test]
+    fn test_equal_priorities_keep_insertion_order() {
+        let builder = ConfigurationServiceBuilder::new()
+            .with_source(Box::new(MockSource::new("first", 2).with_value("key", "a")))
+            .with_source(Box::new(MockSource::new("low", 1)))
+            .with_source(Box::new(
+                MockSource::new("second", 2).with_value("key", "b"),
+            ))
+            .with_source_priority(Box::new(MockSource::new("raised", 1)), 5);
+
+        let order = |entries: Vec<PrecedenceEntry>| {
+            entries
+                .into_iter()
+                .map(|e| format!("{}:{}", e.source_name, e.priority))
+                .collect::<Vec<_>>()
+        };
+        let expected = ["raised:5", "first:2", "second:2", "low:1"];
+        assert_eq!(order(builder.precedence()), expected);
+
+        // Equal priorities keep their insertion order in the built service too
+        let service = builder.build().unwrap();
+        assert_eq!(order(service.precedence()), expected);
+        assert_eq!(service.get_str("key").unwrap().as_str(), "a");
+    }
+
     #[test]
     fn test_default_service_get_from_single_source() {
         let mut service = DefaultConfigService::new();
@@ -762,4 +2395,542 @@ mod tests {
         let service = DefaultConfigService::default();
         assert_eq!(service.sources.len(), 0);
     }
+
+    #[test]
+    fn test_key_alias_falls_back_to_old_key() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("test", 1).with_value("db.host", "localhost"),
+        ));
+        service.add_key_alias("db.host", "database.host");
+
+        let value = service.get(&ConfigKey::from("database.host")).unwrap();
+        assert_eq!(value.as_str(), "localhost");
+        assert!(service.has(&ConfigKey::from("database.host")));
+    }
+
+    #[test]
+    fn test_key_alias_new_key_wins_across_sources() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("high", 3).with_value("db.host", "old_value"),
+        ));
+        service.add_source(Box::new(
+            MockSource::new("low", 1).with_value("database.host", "new_value"),
+        ));
+        service.add_key_alias("db.host", "database.host");
+
+        let value = service.get(&ConfigKey::from("database.host")).unwrap();
+        assert_eq!(value.as_str(), "new_value");
+    }
+
+    #[test]
+    fn test_source_of_reports_supplying_source() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("low", 1)
+                .with_value("app.name", "file_name")
+                .with_value("db.host", "localhost"),
+        ));
+        service.add_source(Box::new(
+            MockSource::new("high", 3).with_value("app.name", "cli_name"),
+        ));
+        service.add_key_alias("db.host", "database.host");
+
+        assert_eq!(service.source_of("app.name"), Some("high"));
+        assert_eq!(service.source_of("database.host"), Some("low"));
+        assert_eq!(service.source_of("app.port"), None);
+    }
+
+    #[test]
+    fn test_get_list_from_highest_priority_source() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("file", 1)
+                .with_value("tags.0", "a")
+                .with_value("tags.1", "b")
+                .with_value("tags.2", "c")
+                .with_value("hosts", "x.internal, y.internal,"),
+        ));
+        service.add_source(Box::new(
+            MockSource::new("cli", 3)
+                .with_value("tags.0", "d")
+                .with_value("tags.1", "e"),
+        ));
+
+        let tags: Vec<String> = service
+            .get_list("tags")
+            .unwrap()
+            .into_iter()
+            .map(|v| v.as_str().to_string())
+            .collect();
+        // The lists are not merged
+        assert_eq!(tags, vec!["d", "e"]);
+
+        let hosts = service.get_list("hosts").unwrap();
+        assert_eq!(hosts.len(), 2);
+        assert_eq!(hosts[1].as_str(), "y.internal");
+
+        assert!(matches!(
+            service.get_list("missing"),
+            Err(ConfigError::ConfigKeyNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_merge_strategies() {
+        let strings = |values: Vec<ConfigValue>| {
+            values
+                .into_iter()
+                .map(|v| v.as_str().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("file", 1)
+                .with_value("servers.0.host", "a")
+                .with_value("servers.1.host", "b")
+                .with_value("db.host", "file-db")
+                .with_value("db.port", "5432")
+                .with_value("tags.0", "web")
+                .with_value("tags.1", "eu")
+                .with_value("plugins", "auth, metrics"),
+        ));
+        service.add_source(Box::new(
+            MockSource::new("env", 2)
+                .with_value("servers.0.host", "c")
+                .with_value("db.host", "env-db")
+                .with_value("tags.0", "canary")
+                .with_value("plugins.0", "metrics")
+                .with_value("plugins.1", "tracing"),
+        ));
+
+        // Without strategies each flattened key is resolved on its own
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot["servers.0.host"].as_str(), "c");
+        assert_eq!(snapshot["servers.1.host"].as_str(), "b");
+        assert_eq!(strings(service.get_list("tags").unwrap()), ["canary"]);
+        assert_eq!(service.get_str("servers.1.host").unwrap().as_str(), "b");
+
+        service.set_merge_strategy("servers", MergeStrategy::Replace);
+        service.set_merge_strategy("db", MergeStrategy::DeepMerge);
+        service.set_merge_strategy("tags", MergeStrategy::Append);
+        service.set_merge_strategy("plugins", MergeStrategy::UniqueUnion);
+
+        let snapshot = service.snapshot();
+        assert_eq!(snapshot["servers.0.host"].as_str(), "c");
+        assert!(!snapshot.contains_key("servers.1.host"));
+        assert_eq!(snapshot["db.host"].as_str(), "env-db");
+        assert_eq!(snapshot["db.port"].as_str(), "5432");
+        assert_eq!(snapshot["tags.2"].as_str(), "canary");
+        assert!(!snapshot.contains_key("plugins"));
+        assert_eq!(snapshot["plugins.2"].as_str(), "tracing");
+
+        assert_eq!(
+            strings(service.get_list("tags").unwrap()),
+            ["web", "eu", "canary"]
+        );
+        assert_eq!(
+            strings(service.get_list("plugins").unwrap()),
+            ["auth", "metrics", "tracing"]
+        );
+        assert!(matches!(
+            service.get_list("servers.missing"),
+            Err(ConfigError::ConfigKeyNotFound { .. })
+        ));
+
+        // Single keys are read from the combined subtree as well
+        assert_eq!(service.get_str("servers.0.host").unwrap().as_str(), "c");
+        assert!(matches!(
+            service.get_str("servers.1.host"),
+            Err(ConfigError::ConfigKeyNotFound { .. })
+        ));
+        assert_eq!(service.get_str("db.port").unwrap().as_str(), "5432");
+        assert_eq!(service.get_str("tags.2").unwrap().as_str(), "canary");
+    }
+
+    #[test]
+    fn test_source_policies_and_health() {
+        use crate::adapters::SourcePolicy;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Duration;
+
+        let unreachable = || -> Result<Box<dyn ConfigSource>> {
+            Err(ConfigError::SourceError {
+                source_name: "remote".to_string(),
+                message: "Connection refused".to_string(),
+                source: None,
+            })
+        };
+        let result = ConfigurationServiceBuilder::new().with_source_policy(
+            "remote",
+            5,
+            SourcePolicy::Required,
+            unreachable,
+        );
+        assert!(result.is_err());
+
+        let available = Arc::new(AtomicBool::new(false));
+        let available_clone = Arc::clone(&available);
+        let service = ConfigurationServiceBuilder::new()
+            .with_source(Box::new(
+                MockSource::new("file", 1).with_value("key", "file"),
+            ))
+            .with_source_policy("optional", 3, SourcePolicy::Optional, unreachable)
+            .unwrap()
+            .with_source_policy(
+                "remote",
+                5,
+                SourcePolicy::BestEffort {
+                    retry_interval: Duration::from_millis(20),
+                },
+                move || {
+                    if !available_clone.load(Ordering::SeqCst) {
+                        return unreachable();
+                    }
+                    Ok(Box::new(
+                        MockSource::new("remote", 5).with_value("key", "remote"),
+                    ))
+                },
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // The optional source is left out and the best-effort one is pending
+        let health = service.source_health();
+        assert_eq!(health.len(), 2);
+        assert_eq!(health[0].0, "remote");
+        assert!(matches!(
+            health[0].1.status,
+            SourceStatus::Unavailable { .. }
+        ));
+        assert!(health[0]
+            .1
+            .last_error
+            .as_ref()
+            .unwrap()
+            .contains("Connection refused"));
+        assert_eq!(health[1].1, SourceHealth::loaded());
+        assert!(!service.is_ready());
+        assert_eq!(service.get_str("key").unwrap().as_str(), "file");
+
+        // Values read while the source was pending were not cached
+        available.store(true, Ordering::SeqCst);
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while !service.is_ready() && std::time::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(service.is_ready());
+        assert_eq!(service.get_str("key").unwrap().as_str(), "remote");
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn test_optional_yaml_file_skips_only_missing_file() {
+        use crate::adapters::SourcePolicy;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let service = ConfigurationServiceBuilder::new()
+            .with_yaml_file_policy(dir.path().join("missing.yaml"), 1, SourcePolicy::Optional)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(service.source_health().is_empty());
+
+        // A file that exists but does not parse is still an error
+        let path = dir.path().join("malformed.yaml");
+        std::fs::write(&path, "app: [unclosed\n").unwrap();
+        let result = ConfigurationServiceBuilder::new().with_yaml_file_policy(
+            &path,
+            1,
+            SourcePolicy::Optional,
+        );
+        assert!(result.is_err());
+
+        // The source takes the given priority
+        std::fs::write(&path, "app: site\n").unwrap();
+        let builder = ConfigurationServiceBuilder::new()
+            .with_source(Box::new(MockSource::new("test", 2)))
+            .with_yaml_file_policy(&path, 5, SourcePolicy::Optional)
+            .unwrap();
+        assert_eq!(builder.precedence()[0].source_name, "yaml-file");
+        assert_eq!(builder.precedence()[0].priority, 5);
+    }
+
+    #[test]
+    fn test_key_alias_multiple_old_keys() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("test", 1).with_value("database_host", "localhost"),
+        ));
+        service.add_key_alias("db.host", "database.host");
+        service.add_key_alias("database_host", "database.host");
+
+        let value = service.get(&ConfigKey::from("database.host")).unwrap();
+        assert_eq!(value.as_str(), "localhost");
+    }
+
+    #[test]
+    fn test_key_alias_missing_in_all_sources() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(MockSource::new("test", 1)));
+        service.add_key_alias("db.host", "database.host");
+
+        let result = service.get(&ConfigKey::from("database.host"));
+        assert!(matches!(
+            result.unwrap_err(),
+            ConfigError::ConfigKeyNotFound { key } if key == "database.host"
+        ));
+    }
+
+    #[test]
+    fn test_key_alias_warns_once_per_key() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("test", 1).with_value("db.host", "localhost"),
+        ));
+        service.add_key_alias("db.host", "database.host");
+
+        let key = ConfigKey::from("database.host");
+        service.get(&key).unwrap();
+        service.reload().unwrap();
+        service.get(&key).unwrap();
+
+        let warned = service.warned_aliases.lock().unwrap();
+        assert_eq!(warned.len(), 1);
+        assert!(warned.contains("db.host"));
+    }
+
+    #[test]
+    fn test_deprecated_usages() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("yaml", 1).with_value("db.host", "localhost"),
+        ));
+        service.add_source(Box::new(
+            MockSource::new("env", 2).with_value("database.port", "5432"),
+        ));
+        service.add_key_alias("db.host", "database.host");
+        service.add_key_alias("db.port", "database.port");
+
+        let usages = service.deprecated_usages();
+        assert_eq!(
+            usages,
+            vec![DeprecatedKeyUsage {
+                old_key: ConfigKey::from("db.host"),
+                new_key: ConfigKey::from("database.host"),
+                source_name: "yaml".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_builder_with_key_alias() {
+        let service = ConfigurationServiceBuilder::new()
+            .with_source(Box::new(
+                MockSource::new("test", 1).with_value("db.host", "localhost"),
+            ))
+            .with_key_alias("db.host", "database.host")
+            .build()
+            .unwrap();
+
+        let value = service.get(&ConfigKey::from("database.host")).unwrap();
+        assert_eq!(value.as_str(), "localhost");
+        assert_eq!(service.deprecated_usages().len(), 1);
+    }
+
+    #[test]
+    fn test_key_normalizer_matches_across_sources() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("yaml", 1).with_value("db.max-connections", "10"),
+        ));
+        service.add_source(Box::new(
+            MockSource::new("env", 2).with_value("DB.MAX_CONNECTIONS", "50"),
+        ));
+        service.set_key_normalizer(StandardKeyNormalizer::new());
+
+        let value = service.get(&ConfigKey::from("db.max_connections")).unwrap();
+        assert_eq!(value.as_str(), "50");
+    }
+
+    #[test]
+    fn test_key_normalizer_absent_requires_exact_match() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("env", 2).with_value("DB.MAX_CONNECTIONS", "50"),
+        ));
+
+        assert!(!service.has(&ConfigKey::from("db.max_connections")));
+    }
+
+    #[test]
+    fn test_key_normalizer_shares_cache_entry() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("test", 1).with_value("App.Name", "demo"),
+        ));
+        service.set_key_normalizer(StandardKeyNormalizer::new());
+
+        service.get(&ConfigKey::from("app.name")).unwrap();
+        service.get(&ConfigKey::from("APP.NAME")).unwrap();
+
+        let cache = service.cache.read().unwrap();
+        assert_eq!(cache.len(), 1);
+        assert!(cache.contains_key("app.name"));
+    }
+
+    #[test]
+    fn test_key_normalizer_indexes_source_keys() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("test", 1).with_value("App.Name", "demo"),
+        ));
+        service.set_key_normalizer(StandardKeyNormalizer::new());
+
+        service.get(&ConfigKey::from("app.name")).unwrap();
+        assert!(service.get(&ConfigKey::from("app.port")).is_err());
+        assert_eq!(service.key_index.read().unwrap().len(), 1);
+
+        service.reload().unwrap();
+        assert!(service.key_index.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_key_normalizer_underscores_as_dots() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("yaml", 1).with_value("db.max_connections", "10"),
+        ));
+        // MAX_CONNECTIONS as read by `EnvVarAdapter::new().lowercase_keys(true)`
+        service.add_source(Box::new(
+            MockSource::new("env", 2).with_value("db.max.connections", "50"),
+        ));
+        service.set_key_normalizer(StandardKeyNormalizer::new().underscores_as_dots(true));
+
+        let value = service.get(&ConfigKey::from("db.max_connections")).unwrap();
+        assert_eq!(value.as_str(), "50");
+    }
+
+    #[test]
+    fn test_key_normalizer_applies_to_aliases() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("env", 2).with_value("DB.HOST", "localhost"),
+        ));
+        service.set_key_normalizer(StandardKeyNormalizer::new());
+        service.add_key_alias("db.host", "database.host");
+
+        let value = service.get(&ConfigKey::from("Database.Host")).unwrap();
+        assert_eq!(value.as_str(), "localhost");
+        assert_eq!(service.deprecated_usages().len(), 1);
+    }
+
+    #[test]
+    fn test_builder_with_key_normalizer() {
+        let service = ConfigurationServiceBuilder::new()
+            .with_source(Box::new(
+                MockSource::new("test", 1).with_value("SERVER.PORT", "8080"),
+            ))
+            .with_key_normalizer(StandardKeyNormalizer::new())
+            .build()
+            .unwrap();
+
+        let value = service.get(&ConfigKey::from("server.port")).unwrap();
+        assert_eq!(value.as_str(), "8080");
+    }
+
+    #[test]
+    fn test_get_typed() {
+        use crate::domain::TypedKey;
+
+        const PORT: TypedKey<u16> = TypedKey::new("db.port").default(5432);
+        const TIMEOUT: TypedKey<u64> = TypedKey::new("db.timeout").default(30);
+        const HOST: TypedKey<String> = TypedKey::new("db.host");
+
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("test", 1)
+                .with_value("db.port", "6543")
+                .with_value("db.timeout", "soon"),
+        ));
+
+        assert_eq!(service.get_typed(&PORT).unwrap(), 6543);
+        assert!(matches!(
+            service.get_typed(&TIMEOUT),
+            Err(ConfigError::TypeConversionError { .. })
+        ));
+        assert!(matches!(
+            service.get_typed(&HOST),
+            Err(ConfigError::ConfigKeyNotFound { .. })
+        ));
+
+        let mut empty = DefaultConfigService::new();
+        empty.add_source(Box::new(MockSource::new("empty", 1)));
+        assert_eq!(empty.get_typed(&PORT).unwrap(), 5432);
+    }
+
+    #[test]
+    fn test_set_writes_to_target_source() {
+        use crate::adapters::InMemoryAdapter;
+
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(
+            MockSource::new("file", 1).with_value("db.host", "from_file"),
+        ));
+        service.add_source(Box::new(InMemoryAdapter::new()));
+
+        // Populate the cache before writing
+        assert_eq!(service.get_str("db.host").unwrap().as_str(), "from_file");
+
+        service.set("db.host", "overridden", "memory").unwrap();
+        assert_eq!(service.get_str("db.host").unwrap().as_str(), "overridden");
+
+        service.delete("db.host", "memory").unwrap();
+        assert_eq!(service.get_str("db.host").unwrap().as_str(), "from_file");
+    }
+
+    #[test]
+    fn test_apply_writes_batch() {
+        use crate::adapters::InMemoryAdapter;
+
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(InMemoryAdapter::new()));
+
+        service
+            .apply(
+                &[ConfigChange::set("a", "1"), ConfigChange::set("b", "2")],
+                "memory",
+            )
+            .unwrap();
+
+        assert!(service.has_str("a"));
+        assert!(service.has_str("b"));
+    }
+
+    #[test]
+    fn test_set_unknown_source() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(MockSource::new("file", 1)));
+
+        let result = service.set("key", "value", "missing");
+        assert!(matches!(
+            result,
+            Err(ConfigError::SourceError { source_name, .. }) if source_name == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_set_read_only_source() {
+        let mut service = DefaultConfigService::new();
+        service.add_source(Box::new(MockSource::new("file", 1)));
+
+        let result = service.set("key", "value", "file");
+        match result {
+            Err(ConfigError::SourceError { message, .. }) => assert!(message.contains("read-only")),
+            other => panic!("expected read-only error, got {:?}", other),
+        }
+    }
 }
@@ -5,7 +5,13 @@
 //! This module contains the concrete implementations of the `ConfigurationService`
 //! trait, which provides the main interface for accessing configuration values.
 
+#[cfg(feature = "async")]
+pub mod async_service;
 pub mod default_service;
 
 // Re-export commonly used types
-pub use default_service::{ConfigurationServiceBuilder, DefaultConfigService};
+#[cfg(feature = "async")]
+pub use async_service::AsyncConfigService;
+pub use default_service::{
+    ConfigurationServiceBuilder, DefaultConfigService, DeprecatedKeyUsage, PrecedenceEntry,
+};
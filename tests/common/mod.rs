@@ -1,8 +1,14 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-//! Helper utilities for Docker-based integration tests.
+//! Helper utilities for integration tests: Docker availability checks, a
+//! minimal HTTP server for mock backends, and polling for watcher changes.
 
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// Cached result of Docker availability check.
 #[allow(dead_code)]
@@ -41,3 +47,85 @@ macro_rules! require_docker {
         }
     };
 }
+
+/// An HTTP request received by a mock server.
+#[allow(dead_code)]
+pub struct Request {
+    /// The request line, e.g. `GET /v1/kv/myapp/?recurse=true HTTP/1.1`
+    pub line: String,
+    pub method: String,
+    /// The path and query
+    pub target: String,
+    /// Headers with lowercase names
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+/// Reads one request from `stream`, or `None` if the client sent none.
+#[allow(dead_code)]
+pub fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+    let mut line = String::new();
+    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+        return None;
+    }
+    let mut headers = HashMap::new();
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).unwrap_or(0) == 0 || header == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+    let length = headers
+        .get("content-length")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    let _ = reader.read_exact(&mut body);
+
+    let mut parts = line.split_whitespace();
+    Some(Request {
+        method: parts.next().unwrap_or_default().to_string(),
+        target: parts.next().unwrap_or_default().to_string(),
+        line: line.trim().to_string(),
+        headers,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+/// Accepts connections on a local port, handing each to `handle` on its own
+/// thread, and returns the base URL.
+#[allow(dead_code)]
+pub fn serve<F>(handle: F) -> String
+where
+    F: Fn(TcpStream) + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let handle = Arc::new(handle);
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let handle = Arc::clone(&handle);
+            thread::spawn(move || handle(stream));
+        }
+    });
+
+    base
+}
+
+/// Waits until `changed` holds `key`, for up to five seconds.
+#[allow(dead_code)]
+pub fn wait_for(changed: &Mutex<Vec<String>>, key: &str) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if changed.lock().unwrap().iter().any(|k| k == key) {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    false
+}
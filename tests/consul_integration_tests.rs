@@ -0,0 +1,290 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Integration tests for the Consul adapter against a mock Consul HTTP API.
+
+mod common;
+
+#[cfg(feature = "consul")]
+mod consul_tests {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use hexcfg::adapters::{ConsulAdapter, ConsulConnectOptions, ConsulWatcher};
+    use hexcfg::domain::ConfigKey;
+    use hexcfg::ports::{ConfigSource, ConfigWatcher};
+    use std::collections::BTreeMap;
+    use std::io::Write;
+    use std::net::TcpStream;
+    use std::sync::{Arc, Condvar, Mutex};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::common::{self, wait_for};
+
+    /// KV contents of the mock agent and the index of the last change.
+    #[derive(Default)]
+    struct Kv {
+        index: u64,
+        entries: BTreeMap<String, Option<String>>,
+    }
+
+    /// A minimal Consul agent serving `GET /v1/kv/<prefix>?recurse`, including
+    /// blocking queries.
+    #[derive(Default)]
+    struct MockConsul {
+        kv: Mutex<Kv>,
+        changed: Condvar,
+        /// ACL token every request must carry, if set
+        token: Option<String>,
+        /// Request lines received, e.g. `GET /v1/kv/myapp/?recurse=true HTTP/1.1`
+        requests: Mutex<Vec<String>>,
+    }
+
+    impl MockConsul {
+        /// Starts the agent on a local port and returns its address.
+        fn start(self) -> (Arc<Self>, String) {
+            let mock = Arc::new(self);
+            let server = Arc::clone(&mock);
+            let address = common::serve(move |stream| server.handle(stream));
+            (mock, address)
+        }
+
+        /// Sets a key, or a folder entry when `value` is `None`.
+        fn put(&self, key: &str, value: Option<&str>) {
+            let mut kv = self.kv.lock().unwrap();
+            kv.index += 1;
+            kv.entries
+                .insert(key.to_string(), value.map(|v| v.to_string()));
+            self.changed.notify_all();
+        }
+
+        fn delete(&self, key: &str) {
+            let mut kv = self.kv.lock().unwrap();
+            kv.index += 1;
+            kv.entries.remove(key);
+            self.changed.notify_all();
+        }
+
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        fn handle(&self, stream: TcpStream) {
+            let Some(request) = common::read_request(&stream) else {
+                return;
+            };
+            let token = request.headers.get("x-consul-token").cloned();
+            self.requests.lock().unwrap().push(request.line.clone());
+
+            let target = request.target.as_str();
+            let (path, query) = target.split_once('?').unwrap_or((target, ""));
+            let param = |name: &str| {
+                query
+                    .split('&')
+                    .find_map(|pair| pair.strip_prefix(&format!("{}=", name)))
+                    .map(|value| value.to_string())
+            };
+
+            if self.token.is_some() && token != self.token {
+                return respond(stream, "403 Forbidden", 0, "ACL not found");
+            }
+            let Some(prefix) = path.strip_prefix("/v1/kv/") else {
+                return respond(stream, "404 Not Found", 0, "");
+            };
+
+            let mut kv = self.kv.lock().unwrap();
+            if let Some(index) = param("index").and_then(|i| i.parse::<u64>().ok()) {
+                let wait = param("wait")
+                    .and_then(|w| w.trim_end_matches("ms").parse().ok())
+                    .map_or(Duration::from_secs(5), Duration::from_millis);
+                let deadline = Instant::now() + wait;
+                while kv.index <= index && Instant::now() < deadline {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    kv = self.changed.wait_timeout(kv, remaining).unwrap().0;
+                }
+            }
+
+            let entries: Vec<String> = kv
+                .entries
+                .iter()
+                .filter(|(key, _)| key.starts_with(prefix))
+                .map(|(key, value)| {
+                    let value = value.as_ref().map_or("null".to_string(), |v| {
+                        format!("\"{}\"", STANDARD.encode(v))
+                    });
+                    format!(r#"{{"Key":"{}","Value":{},"Flags":0}}"#, key, value)
+                })
+                .collect();
+            let index = kv.index;
+            drop(kv);
+
+            if entries.is_empty() {
+                respond(stream, "404 Not Found", index, "");
+            } else {
+                respond(stream, "200 OK", index, &format!("[{}]", entries.join(",")));
+            }
+        }
+    }
+
+    fn respond(mut stream: TcpStream, status: &str, index: u64, body: &str) {
+        let _ = write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nX-Consul-Index: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            index,
+            body.len(),
+            body
+        );
+    }
+
+    fn seeded() -> MockConsul {
+        let mock = MockConsul::default();
+        mock.put("myapp/", None);
+        mock.put("myapp/database/host", Some("localhost"));
+        mock.put("myapp/database/port", Some("5432"));
+        mock.put("other/database/host", Some("elsewhere"));
+        mock
+    }
+
+    #[tokio::test]
+    async fn test_consul_reads_prefix() {
+        let (mock, address) = seeded().start();
+
+        let mut adapter = ConsulAdapter::new(&address, Some("myapp/")).await.unwrap();
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "localhost"
+        );
+        assert_eq!(
+            adapter.get_str("database.port").unwrap().unwrap().as_str(),
+            "5432"
+        );
+        // Folder entries and keys outside the prefix are skipped
+        assert_eq!(adapter.all_keys().unwrap().len(), 2);
+        assert_eq!(adapter.index(), 4);
+
+        mock.put("myapp/database/host", Some("db.internal"));
+        adapter.reload().unwrap();
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "db.internal"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_consul_missing_prefix_is_empty() {
+        let (_mock, address) = seeded().start();
+
+        let adapter = ConsulAdapter::new(&address, Some("missing/"))
+            .await
+            .unwrap();
+        assert!(adapter.all_keys().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_consul_token_and_datacenter() {
+        let (mock, address) = MockConsul {
+            token: Some("s3cr3t".to_string()),
+            ..seeded()
+        }
+        .start();
+
+        let result = ConsulAdapter::new(&address, Some("myapp/")).await;
+        assert!(
+            result.is_err(),
+            "Requests without the ACL token should fail"
+        );
+
+        let options = ConsulConnectOptions::new()
+            .with_token("s3cr3t")
+            .with_datacenter("eu-west");
+        let adapter = ConsulAdapter::with_options(&address, Some("myapp/"), options)
+            .await
+            .unwrap();
+        assert!(adapter.get_str("database.host").unwrap().is_some());
+        assert!(mock.requests().last().unwrap().contains("dc=eu-west"));
+    }
+
+    #[tokio::test]
+    async fn test_consul_adapter_watcher_updates_cache() {
+        let (mock, address) = seeded().start();
+
+        let adapter = ConsulAdapter::new(&address, Some("myapp/")).await.unwrap();
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        let mut watcher = adapter.watcher();
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+
+        mock.put("myapp/database/host", Some("db.internal"));
+        assert!(wait_for(&changed, "database.host"));
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "db.internal"
+        );
+
+        mock.delete("myapp/database/port");
+        assert!(wait_for(&changed, "database.port"));
+        assert!(adapter.get_str("database.port").unwrap().is_none());
+
+        // Changes outside the prefix do not reach the callback
+        mock.put("other/database/host", Some("changed"));
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(changed.lock().unwrap().len(), 2);
+
+        watcher.stop().unwrap();
+
+        // The watcher resumed blocking queries from the adapter's index
+        assert!(mock
+            .requests()
+            .iter()
+            .any(|request| request.contains("index=4")));
+    }
+
+    #[tokio::test]
+    async fn test_consul_watcher_standalone() {
+        let (mock, address) = seeded().start();
+
+        let options = ConsulConnectOptions::new().with_wait_time(Duration::from_secs(1));
+        let mut watcher = ConsulWatcher::with_options(&address, Some("myapp/"), options)
+            .await
+            .unwrap();
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+
+        // Let the watcher load the prefix and outlast one empty wait
+        thread::sleep(Duration::from_millis(1500));
+        assert!(changed.lock().unwrap().is_empty());
+
+        mock.put("myapp/feature/enabled", Some("true"));
+        assert!(wait_for(&changed, "feature.enabled"));
+
+        watcher.stop().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_consul_with_builder() {
+        use hexcfg::domain::ConfigurationService;
+        use hexcfg::service::ConfigurationServiceBuilder;
+
+        let (_mock, address) = seeded().start();
+
+        let service = ConfigurationServiceBuilder::new()
+            .with_consul(&address, Some("myapp/"))
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(service.get_str("database.port").unwrap().as_str(), "5432");
+    }
+}
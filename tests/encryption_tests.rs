@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Integration tests for decrypting encrypted configuration values.
+
+#[cfg(all(feature = "encryption", feature = "yaml"))]
+mod encryption_tests {
+    use hexcfg::prelude::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Writes a base64 AES key file and a YAML file referencing values
+    /// encrypted with it.
+    fn write_config(dir: &TempDir) -> (std::path::PathBuf, std::path::PathBuf) {
+        let key_path = dir.path().join("config.key");
+        // base64 of 32 bytes of 0x2a
+        fs::write(&key_path, "KioqKioqKioqKioqKioqKioqKioqKioqKioqKioqKio=\n").unwrap();
+        let decryptor = AesGcmDecryptor::from_key_file(&key_path).unwrap();
+
+        let password = decryptor.encrypt("hunter2").unwrap();
+        let token = decryptor.encrypt("t0k3n").unwrap();
+        let tagged = token
+            .trim_start_matches("ENC[")
+            .trim_end_matches(']')
+            .to_string();
+
+        let yaml_path = dir.path().join("config.yaml");
+        fs::write(
+            &yaml_path,
+            format!(
+                "database:\n  host: localhost\n  password: \"{}\"\napi:\n  token: !encrypted {}\n",
+                password, tagged
+            ),
+        )
+        .unwrap();
+
+        (key_path, yaml_path)
+    }
+
+    #[test]
+    fn test_encrypted_yaml_values_are_decrypted() {
+        let dir = TempDir::new().unwrap();
+        let (key_path, yaml_path) = write_config(&dir);
+
+        let source = DecryptingSource::new(
+            Box::new(YamlFileAdapter::from_file(&yaml_path).unwrap()),
+            AesGcmDecryptor::from_key_file(&key_path).unwrap(),
+        )
+        .unwrap();
+
+        let service = DefaultConfigService::builder()
+            .with_source(Box::new(source))
+            .build()
+            .unwrap();
+
+        let password = service.get_str("database.password").unwrap();
+        assert_eq!(password.as_str(), "hunter2");
+        assert!(password.is_secret());
+        assert!(!format!("{:?}", password).contains("hunter2"));
+
+        assert_eq!(service.get_str("api.token").unwrap().as_str(), "t0k3n");
+        assert_eq!(
+            service.get_str("database.host").unwrap().as_str(),
+            "localhost"
+        );
+    }
+
+    #[test]
+    fn test_wrong_key_names_the_failing_key() {
+        let dir = TempDir::new().unwrap();
+        let (_, yaml_path) = write_config(&dir);
+
+        let err = DecryptingSource::new(
+            Box::new(YamlFileAdapter::from_file(&yaml_path).unwrap()),
+            AesGcmDecryptor::new([0u8; 32]),
+        )
+        .unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains("database.password") || message.contains("api.token"));
+        assert!(!message.contains("ENC["));
+        assert!(!message.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_reload_decrypts_new_values() {
+        let dir = TempDir::new().unwrap();
+        let (key_path, yaml_path) = write_config(&dir);
+        let decryptor = AesGcmDecryptor::from_key_file(&key_path).unwrap();
+
+        let mut source = DecryptingSource::new(
+            Box::new(YamlFileAdapter::from_file(&yaml_path).unwrap()),
+            decryptor.clone(),
+        )
+        .unwrap();
+
+        let rotated = decryptor.encrypt("correct horse").unwrap();
+        fs::write(
+            &yaml_path,
+            format!("database:\n  password: \"{}\"\n", rotated),
+        )
+        .unwrap();
+        source.reload().unwrap();
+
+        assert_eq!(
+            source
+                .get_str("database.password")
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "correct horse"
+        );
+        assert!(source.get_str("api.token").unwrap().is_none());
+    }
+}
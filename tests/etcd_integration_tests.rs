@@ -463,9 +463,9 @@ mod etcd_tests {
 
     #[tokio::test]
     async fn test_etcd_watcher_callback_triggered() {
+        use etcd_client::Client;
         use hexcfg::adapters::EtcdWatcher;
         use hexcfg::ports::ConfigWatcher;
-        use etcd_client::Client;
         use std::sync::atomic::{AtomicBool, Ordering};
         use std::sync::Arc;
 
@@ -513,9 +513,9 @@ mod etcd_tests {
 
     #[tokio::test]
     async fn test_etcd_watcher_multiple_changes() {
+        use etcd_client::Client;
         use hexcfg::adapters::EtcdWatcher;
         use hexcfg::ports::ConfigWatcher;
-        use etcd_client::Client;
         use std::sync::atomic::{AtomicUsize, Ordering};
         use std::sync::Arc;
 
@@ -569,9 +569,9 @@ mod etcd_tests {
 
     #[tokio::test]
     async fn test_etcd_watcher_prefix_filtering() {
+        use etcd_client::Client;
         use hexcfg::adapters::EtcdWatcher;
         use hexcfg::ports::ConfigWatcher;
-        use etcd_client::Client;
         use std::sync::atomic::{AtomicUsize, Ordering};
         use std::sync::Arc;
 
@@ -634,4 +634,314 @@ mod etcd_tests {
             count
         );
     }
+
+    #[tokio::test]
+    async fn test_etcd_adapter_watcher_catches_up_from_snapshot() {
+        use etcd_client::Client;
+        use hexcfg::ports::ConfigWatcher;
+        use std::sync::{Arc, Mutex};
+
+        let Some((_container, endpoint)) = setup_etcd_watcher_test().await else {
+            return;
+        };
+
+        let mut client = Client::connect([&endpoint], None).await.unwrap();
+        client
+            .put("test/snap/app/name", "before", None)
+            .await
+            .unwrap();
+        client
+            .put("test/snap/app/legacy", "old", None)
+            .await
+            .unwrap();
+
+        let adapter = EtcdAdapter::new(vec![&endpoint], Some("test/snap/"))
+            .await
+            .unwrap();
+        let revision = adapter.revision();
+        assert!(revision > 0);
+
+        // Changes made before the watcher starts must not be lost
+        client
+            .put("test/snap/app/name", "after", None)
+            .await
+            .unwrap();
+        client.delete("test/snap/app/legacy", None).await.unwrap();
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        let mut watcher = adapter.watcher();
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+
+        // Changes made while watching are applied as well
+        client
+            .put("test/snap/app/port", "8080", None)
+            .await
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        watcher.stop().unwrap();
+
+        let changed = changed.lock().unwrap();
+        assert!(changed.contains(&"app.name".to_string()));
+        assert!(changed.contains(&"app.legacy".to_string()));
+        assert!(changed.contains(&"app.port".to_string()));
+
+        // The adapter's cache was patched without a reload
+        assert_eq!(
+            adapter.get_str("app.name").unwrap().unwrap().as_str(),
+            "after"
+        );
+        assert!(adapter.get_str("app.legacy").unwrap().is_none());
+        assert_eq!(
+            adapter.get_str("app.port").unwrap().unwrap().as_str(),
+            "8080"
+        );
+        assert!(adapter.revision() > revision);
+    }
+
+    #[tokio::test]
+    async fn test_etcd_adapter_watcher_resyncs_after_compaction() {
+        use etcd_client::{Client, CompactionOptions};
+        use hexcfg::ports::ConfigWatcher;
+        use std::sync::{Arc, Mutex};
+
+        let Some((_container, endpoint)) = setup_etcd_watcher_test().await else {
+            return;
+        };
+
+        let mut client = Client::connect([&endpoint], None).await.unwrap();
+        client.put("test/compact/a", "1", None).await.unwrap();
+        client.put("test/compact/b", "1", None).await.unwrap();
+        client
+            .put("test/compact/unchanged", "1", None)
+            .await
+            .unwrap();
+
+        let adapter = EtcdAdapter::new(vec![&endpoint], Some("test/compact/"))
+            .await
+            .unwrap();
+
+        // Change keys and compact away the revisions the watcher would need
+        client.put("test/compact/a", "2", None).await.unwrap();
+        client.put("test/compact/a", "3", None).await.unwrap();
+        let response = client.delete("test/compact/b", None).await.unwrap();
+        let latest = response.header().unwrap().revision();
+        client
+            .compact(latest, Some(CompactionOptions::new().with_physical()))
+            .await
+            .unwrap();
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        let mut watcher = adapter.watcher();
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+        watcher.stop().unwrap();
+
+        let mut changed = changed.lock().unwrap().clone();
+        changed.sort();
+        assert_eq!(changed, vec!["a".to_string(), "b".to_string()]);
+
+        assert_eq!(adapter.get_str("a").unwrap().unwrap().as_str(), "3");
+        assert!(adapter.get_str("b").unwrap().is_none());
+        assert_eq!(adapter.revision(), latest);
+    }
+
+    // === Connection Options Tests ===
+
+    /// Path to a PEM fixture used by the TLS tests.
+    fn tls_fixture(name: &str) -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    #[tokio::test]
+    async fn test_etcd_options_connect_over_tls() {
+        use hexcfg::adapters::EtcdConnectOptions;
+        use std::time::Duration;
+        use tokio::io::AsyncReadExt;
+
+        // A local stand-in that records the first byte the client sends
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = listener.local_addr().unwrap().to_string();
+        let first_byte = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.ok()?;
+            let mut buf = [0u8; 1];
+            stream.read_exact(&mut buf).await.ok()?;
+            Some(buf[0])
+        });
+
+        let options = EtcdConnectOptions::new()
+            .with_ca_cert_file(tls_fixture("ca.pem"))
+            .unwrap()
+            .with_client_identity_files(tls_fixture("client.pem"), tls_fixture("client-key.pem"))
+            .unwrap()
+            .with_connect_timeout(Duration::from_secs(2))
+            .with_request_timeout(Duration::from_secs(2));
+        assert!(options.uses_tls());
+
+        // The stand-in closes the connection, so the adapter cannot load keys
+        let result = EtcdAdapter::with_options(vec![endpoint], Some("test/"), options).await;
+        assert!(result.is_err());
+
+        // 0x16 is the TLS handshake record type
+        let first_byte = tokio::time::timeout(Duration::from_secs(5), first_byte)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first_byte, Some(0x16));
+    }
+
+    #[test]
+    fn test_etcd_options_missing_pem_file() {
+        use hexcfg::adapters::EtcdConnectOptions;
+
+        let result = EtcdConnectOptions::new().with_ca_cert_file("/nonexistent/ca.pem");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_etcd_options_debug_redacts_secrets() {
+        use hexcfg::adapters::EtcdConnectOptions;
+
+        let options = EtcdConnectOptions::new()
+            .with_user("app", "hunter2")
+            .with_client_identity("cert", "private-key");
+        let debug = format!("{:?}", options);
+
+        assert!(debug.contains("app"));
+        assert!(!debug.contains("hunter2"));
+        assert!(!debug.contains("private-key"));
+    }
+
+    #[tokio::test]
+    async fn test_etcd_with_authentication() {
+        use hexcfg::adapters::EtcdConnectOptions;
+
+        let Some((_container, endpoint)) = setup_etcd_watcher_test().await else {
+            return;
+        };
+
+        // Enable RBAC with a root user
+        let mut client = etcd_client::Client::connect([&endpoint], None)
+            .await
+            .unwrap();
+        client
+            .put("secure/app/name", "authenticated", None)
+            .await
+            .unwrap();
+        client.user_add("root", "rootpw", None).await.unwrap();
+        client.user_grant_role("root", "root").await.unwrap();
+        client.auth_enable().await.unwrap();
+
+        let result = EtcdAdapter::new(vec![endpoint.clone()], Some("secure/")).await;
+        assert!(result.is_err(), "Unauthenticated access should be denied");
+
+        let options = EtcdConnectOptions::new().with_user("root", "rootpw");
+        let adapter = EtcdAdapter::with_options(vec![endpoint.clone()], Some("secure/"), options)
+            .await
+            .unwrap();
+        let value = adapter.get(&ConfigKey::from("app.name")).unwrap();
+        assert_eq!(value.unwrap().as_str(), "authenticated");
+    }
+
+    #[tokio::test]
+    async fn test_etcd_key_separator_none() {
+        use hexcfg::adapters::{EtcdConnectOptions, KeySeparator};
+        use hexcfg::domain::ConfigValue;
+
+        let Some((_container, endpoint)) = setup_etcd_watcher_test().await else {
+            return;
+        };
+
+        let mut client = etcd_client::Client::connect([&endpoint], None)
+            .await
+            .unwrap();
+        client.put("raw/db/host", "localhost", None).await.unwrap();
+
+        let options = EtcdConnectOptions::new().with_key_separator(KeySeparator::None);
+        let mut adapter = EtcdAdapter::with_options(vec![endpoint.clone()], Some("raw/"), options)
+            .await
+            .unwrap();
+        assert!(adapter.get(&ConfigKey::from("db.host")).unwrap().is_none());
+        assert!(adapter.get(&ConfigKey::from("db/host")).unwrap().is_some());
+
+        // Writes use the same mapping
+        adapter
+            .as_sink()
+            .unwrap()
+            .set(&ConfigKey::from("db.port"), &ConfigValue::from("5432"))
+            .unwrap();
+        let response = client.get("raw/db.port", None).await.unwrap();
+        assert_eq!(response.kvs()[0].value_str().unwrap(), "5432");
+    }
+
+    #[tokio::test]
+    async fn test_etcd_with_mutual_tls() {
+        use hexcfg::adapters::EtcdConnectOptions;
+        use hexcfg::domain::ConfigurationService;
+        use hexcfg::service::ConfigurationServiceBuilder;
+
+        if !docker_helpers::is_docker_available() {
+            docker_helpers::print_docker_unavailable_warning("etcd mutual TLS test");
+            return;
+        }
+
+        let etcd_image = GenericImage::new("quay.io/coreos/etcd", "v3.5.0")
+            .with_exposed_port(2379.into())
+            .with_wait_for(WaitFor::message_on_stderr("ready to serve client requests"))
+            .with_copy_to("/certs/ca.pem", tls_fixture("ca.pem"))
+            .with_copy_to("/certs/server.pem", tls_fixture("server.pem"))
+            .with_copy_to("/certs/server-key.pem", tls_fixture("server-key.pem"))
+            .with_env_var("ETCD_ADVERTISE_CLIENT_URLS", "https://0.0.0.0:2379")
+            .with_env_var("ETCD_LISTEN_CLIENT_URLS", "https://0.0.0.0:2379")
+            .with_env_var("ETCD_CERT_FILE", "/certs/server.pem")
+            .with_env_var("ETCD_KEY_FILE", "/certs/server-key.pem")
+            .with_env_var("ETCD_TRUSTED_CA_FILE", "/certs/ca.pem")
+            .with_env_var("ETCD_CLIENT_CERT_AUTH", "true");
+
+        let Ok(container) = etcd_image.start().await else {
+            return;
+        };
+        let port = container.get_host_port_ipv4(2379).await.unwrap();
+        let endpoint = format!("localhost:{}", port);
+        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+
+        let options = EtcdConnectOptions::new()
+            .with_ca_cert_file(tls_fixture("ca.pem"))
+            .unwrap()
+            .with_client_identity_files(tls_fixture("client.pem"), tls_fixture("client-key.pem"))
+            .unwrap();
+
+        // Write through the adapter, then read back through the builder
+        let mut adapter =
+            EtcdAdapter::with_options(vec![endpoint.clone()], Some("tls/"), options.clone())
+                .await
+                .unwrap();
+        adapter
+            .as_sink()
+            .unwrap()
+            .set(&ConfigKey::from("app.name"), &"secure".into())
+            .unwrap();
+
+        let service = ConfigurationServiceBuilder::new()
+            .with_etcd_options(vec![endpoint], Some("tls/"), options)
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(service.get_str("app.name").unwrap().as_str(), "secure");
+    }
 }
@@ -0,0 +1,350 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Integration tests for the HTTP adapter against a local stand-in server.
+
+mod common;
+
+#[cfg(all(feature = "http", feature = "yaml"))]
+mod http_tests {
+    use hexcfg::adapters::{HttpAdapter, HttpOptions, HttpPollingWatcher};
+    use hexcfg::domain::{ConfigKey, Result};
+    use hexcfg::ports::{ConfigParser, ConfigSource, ConfigWatcher};
+    use std::collections::HashMap;
+    use std::io::Write;
+    use std::net::TcpStream;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::common::{self, wait_for};
+
+    /// The document served by [`MockServer`].
+    struct Document {
+        body: String,
+        content_type: String,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    }
+
+    /// Serves one document on every path, answering conditional requests.
+    struct MockServer {
+        document: Mutex<Document>,
+        /// Bearer token every request must carry, if set
+        token: Option<String>,
+        /// Headers of each request received, with lowercase names
+        requests: Mutex<Vec<HashMap<String, String>>>,
+        /// Number of `200 OK` responses sent
+        downloads: Mutex<usize>,
+    }
+
+    impl MockServer {
+        fn new(body: &str, content_type: &str) -> Self {
+            Self {
+                document: Mutex::new(Document {
+                    body: body.to_string(),
+                    content_type: content_type.to_string(),
+                    etag: None,
+                    last_modified: None,
+                }),
+                token: None,
+                requests: Mutex::new(Vec::new()),
+                downloads: Mutex::new(0),
+            }
+        }
+
+        fn with_etag(self, etag: &str) -> Self {
+            self.document.lock().unwrap().etag = Some(etag.to_string());
+            self
+        }
+
+        fn with_last_modified(self, last_modified: &str) -> Self {
+            self.document.lock().unwrap().last_modified = Some(last_modified.to_string());
+            self
+        }
+
+        /// Starts serving on a local port and returns the base URL.
+        fn start(self) -> (Arc<Self>, String) {
+            let server = Arc::new(self);
+            let handler = Arc::clone(&server);
+            let base = common::serve(move |stream| handler.handle(stream));
+            (server, base)
+        }
+
+        /// Replaces the served document, with a new `ETag` if given.
+        fn update(&self, body: &str, etag: Option<&str>) {
+            let mut document = self.document.lock().unwrap();
+            document.body = body.to_string();
+            document.etag = etag.map(|e| e.to_string());
+        }
+
+        fn downloads(&self) -> usize {
+            *self.downloads.lock().unwrap()
+        }
+
+        fn last_request(&self) -> HashMap<String, String> {
+            self.requests.lock().unwrap().last().cloned().unwrap()
+        }
+
+        fn handle(&self, mut stream: TcpStream) {
+            let Some(request) = common::read_request(&stream) else {
+                return;
+            };
+            let headers = request.headers;
+            self.requests.lock().unwrap().push(headers.clone());
+
+            if let Some(token) = &self.token {
+                if headers.get("authorization") != Some(&format!("Bearer {}", token)) {
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    );
+                    return;
+                }
+            }
+
+            let document = self.document.lock().unwrap();
+            let mut validators = String::new();
+            if let Some(etag) = &document.etag {
+                validators.push_str(&format!("ETag: {}\r\n", etag));
+            }
+            if let Some(last_modified) = &document.last_modified {
+                validators.push_str(&format!("Last-Modified: {}\r\n", last_modified));
+            }
+
+            let not_modified = match (&document.etag, headers.get("if-none-match")) {
+                (Some(etag), Some(sent)) => etag == sent,
+                _ => matches!(
+                    (&document.last_modified, headers.get("if-modified-since")),
+                    (Some(modified), Some(sent)) if modified == sent
+                ),
+            };
+            if not_modified {
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 304 Not Modified\r\n{}Connection: close\r\n\r\n",
+                    validators
+                );
+                return;
+            }
+
+            *self.downloads.lock().unwrap() += 1;
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                document.content_type,
+                validators,
+                document.body.len(),
+                document.body
+            );
+        }
+    }
+
+    /// Parses `key=value` lines.
+    struct PropertiesParser;
+
+    impl ConfigParser for PropertiesParser {
+        fn parse(&self, content: &str) -> Result<HashMap<String, String>> {
+            Ok(content
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+                .collect())
+        }
+
+        fn supported_extensions(&self) -> &[&str] {
+            &["properties"]
+        }
+    }
+
+    const YAML: &str = "database:\n  host: localhost\n  port: 5432\n";
+
+    #[tokio::test]
+    async fn test_http_parser_from_content_type() {
+        let (_server, base) = MockServer::new(YAML, "application/yaml").start();
+
+        // No extension in the URL, so the content type decides
+        let adapter = HttpAdapter::new(&format!("{}/config", base)).await.unwrap();
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "localhost"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_parser_from_extension() {
+        let (_server, base) =
+            MockServer::new(r#"{"database": {"port": 5432}}"#, "text/plain").start();
+
+        let adapter = HttpAdapter::new(&format!("{}/myapp.json", base))
+            .await
+            .unwrap();
+        assert_eq!(
+            adapter.get_str("database.port").unwrap().unwrap().as_str(),
+            "5432"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_custom_parser_and_unknown_format() {
+        let (_server, base) =
+            MockServer::new("database.host = db.internal\n", "text/x-properties").start();
+        let url = format!("{}/config", base);
+
+        assert!(HttpAdapter::new(&url).await.is_err());
+
+        let options = HttpOptions::new().with_parser(PropertiesParser);
+        let adapter = HttpAdapter::with_options(&url, options).await.unwrap();
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "db.internal"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_etag_avoids_download() {
+        let (server, base) = MockServer::new(YAML, "application/yaml")
+            .with_etag("\"v1\"")
+            .start();
+
+        let mut adapter = HttpAdapter::new(&format!("{}/config", base)).await.unwrap();
+        adapter.reload().unwrap();
+
+        assert_eq!(server.downloads(), 1);
+        assert_eq!(
+            server
+                .last_request()
+                .get("if-none-match")
+                .map(String::as_str),
+            Some("\"v1\"")
+        );
+        assert!(adapter.get_str("database.host").unwrap().is_some());
+
+        server.update("database:\n  host: db.internal\n", Some("\"v2\""));
+        adapter.reload().unwrap();
+        assert_eq!(server.downloads(), 2);
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "db.internal"
+        );
+        assert!(adapter.get_str("database.port").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_http_last_modified_avoids_download() {
+        let (server, base) = MockServer::new(YAML, "application/yaml")
+            .with_last_modified("Wed, 21 Oct 2026 07:28:00 GMT")
+            .start();
+
+        let mut adapter = HttpAdapter::new(&format!("{}/config", base)).await.unwrap();
+        adapter.reload().unwrap();
+
+        assert_eq!(server.downloads(), 1);
+        assert!(adapter.get_str("database.port").unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_http_headers_and_bearer_token() {
+        let (server, base) = MockServer {
+            token: Some("s3cr3t".to_string()),
+            ..MockServer::new(YAML, "application/yaml")
+        }
+        .start();
+        let url = format!("{}/config", base);
+
+        assert!(HttpAdapter::new(&url).await.is_err());
+
+        let options = HttpOptions::new()
+            .with_bearer_token("s3cr3t")
+            .with_header("X-Environment", "production")
+            .unwrap();
+        let adapter = HttpAdapter::with_options(&url, options).await.unwrap();
+        assert!(adapter.get_str("database.host").unwrap().is_some());
+        assert_eq!(
+            server
+                .last_request()
+                .get("x-environment")
+                .map(String::as_str),
+            Some("production")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_watcher_fires_only_on_change() {
+        // No validators, so every poll downloads the document again
+        let (server, base) = MockServer::new(YAML, "application/yaml").start();
+
+        let mut watcher =
+            HttpPollingWatcher::new(&format!("{}/config", base), Duration::from_millis(100))
+                .await
+                .unwrap();
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+
+        thread::sleep(Duration::from_millis(400));
+        assert!(server.downloads() > 1);
+        assert!(changed.lock().unwrap().is_empty());
+
+        server.update("database:\n  host: localhost\n  port: 6543\n", None);
+        assert!(wait_for(&changed, "database.port"));
+        watcher.stop().unwrap();
+
+        assert_eq!(*changed.lock().unwrap(), vec!["database.port".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_http_adapter_watcher_updates_values() {
+        let (server, base) = MockServer::new(YAML, "application/yaml")
+            .with_etag("\"v1\"")
+            .start();
+
+        let adapter = HttpAdapter::new(&format!("{}/config", base)).await.unwrap();
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        let mut watcher = adapter.watcher(Duration::from_millis(100));
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+
+        // Polls of the unchanged document are answered with 304
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(server.downloads(), 1);
+
+        server.update(
+            "database:\n  host: db.internal\n  port: 5432\n",
+            Some("\"v2\""),
+        );
+        assert!(wait_for(&changed, "database.host"));
+        watcher.stop().unwrap();
+
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "db.internal"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_with_builder() {
+        use hexcfg::domain::ConfigurationService;
+        use hexcfg::service::ConfigurationServiceBuilder;
+
+        let (_server, base) = MockServer::new(YAML, "application/yaml").start();
+
+        let service = ConfigurationServiceBuilder::new()
+            .with_http(&format!("{}/config", base))
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(service.get_str("database.port").unwrap().as_str(), "5432");
+    }
+}
@@ -6,7 +6,7 @@
 //! with various sources and handles common use cases.
 
 use hexcfg::adapters::{CommandLineAdapter, EnvVarAdapter, YamlFileAdapter};
-use hexcfg::domain::{ConfigKey, ConfigurationService};
+use hexcfg::domain::{ConfigKey, ConfigurationService, StandardKeyNormalizer};
 use hexcfg::service::{ConfigurationServiceBuilder, DefaultConfigService};
 use std::collections::HashMap;
 use std::io::Write;
@@ -67,7 +67,7 @@ fn test_type_conversions() {
     env_vars.insert("bool_true".to_string(), "true".to_string());
     env_vars.insert("bool_false".to_string(), "false".to_string());
     env_vars.insert("int_value".to_string(), "42".to_string());
-    env_vars.insert("float_value".to_string(), "3.14".to_string());
+    env_vars.insert("float_value".to_string(), "2.75".to_string());
     env_vars.insert("string_value".to_string(), "hello".to_string());
 
     let adapter = EnvVarAdapter::with_values(env_vars);
@@ -76,10 +76,10 @@ fn test_type_conversions() {
 
     // Test boolean conversions
     let bool_true = service.get(&ConfigKey::from("bool_true")).unwrap();
-    assert_eq!(bool_true.as_bool("").unwrap(), true);
+    assert!(bool_true.as_bool("").unwrap());
 
     let bool_false = service.get(&ConfigKey::from("bool_false")).unwrap();
-    assert_eq!(bool_false.as_bool("").unwrap(), false);
+    assert!(!bool_false.as_bool("").unwrap());
 
     // Test integer conversions
     let int_val = service.get(&ConfigKey::from("int_value")).unwrap();
@@ -90,7 +90,7 @@ fn test_type_conversions() {
 
     // Test float conversion
     let float_val = service.get(&ConfigKey::from("float_value")).unwrap();
-    assert!((float_val.as_f64("").unwrap() - 3.14).abs() < 0.001);
+    assert!((float_val.as_f64("").unwrap() - 2.75).abs() < 0.001);
 
     // Test string conversion
     let string_val = service.get(&ConfigKey::from("string_value")).unwrap();
@@ -282,3 +282,34 @@ fn test_whitespace_handling() {
     let val = service.get(&ConfigKey::from("whitespace.key")).unwrap();
     assert_eq!(val.as_str(), "  value  ");
 }
+
+#[test]
+fn test_normalized_keys_across_yaml_and_env() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    writeln!(temp_file, "db:").unwrap();
+    writeln!(temp_file, "  max-connections: 10").unwrap();
+    writeln!(temp_file, "  host: localhost").unwrap();
+    temp_file.flush().unwrap();
+
+    std::env::set_var("NORMTEST__DB__MAX_CONNECTIONS", "50");
+
+    let yaml_adapter = YamlFileAdapter::from_file(temp_file.path()).unwrap();
+    let env_adapter = EnvVarAdapter::with_prefix("NORMTEST__").nesting_separator("__");
+
+    let service = ConfigurationServiceBuilder::new()
+        .with_source(Box::new(yaml_adapter))
+        .with_source(Box::new(env_adapter))
+        .with_key_normalizer(StandardKeyNormalizer::new())
+        .build()
+        .unwrap();
+
+    // The env var overrides the YAML key despite different case and separators
+    let val = service.get(&ConfigKey::from("db.max_connections")).unwrap();
+    assert_eq!(val.as_str(), "50");
+
+    // Keys only present in YAML are still found
+    let val = service.get(&ConfigKey::from("DB.HOST")).unwrap();
+    assert_eq!(val.as_str(), "localhost");
+
+    std::env::remove_var("NORMTEST__DB__MAX_CONNECTIONS");
+}
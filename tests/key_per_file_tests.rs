@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Integration tests for key-per-file directories such as mounted Kubernetes
+//! ConfigMaps, Docker secrets and systemd credentials.
+
+mod common;
+
+#[cfg(all(feature = "key-per-file", unix))]
+mod key_per_file_tests {
+    use hexcfg::adapters::KeyPerFileAdapter;
+    use hexcfg::prelude::*;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[cfg(feature = "reload")]
+    use hexcfg::adapters::KeyPerFileWatcher;
+    #[cfg(feature = "reload")]
+    use std::sync::{Arc, Mutex};
+    #[cfg(feature = "reload")]
+    use std::thread;
+    #[cfg(feature = "reload")]
+    use std::time::Duration;
+
+    #[cfg(feature = "reload")]
+    use crate::common::wait_for;
+
+    /// Publishes a new version of a Kubernetes volume the way the kubelet
+    /// does: a timestamped directory, a `..data_tmp` symlink renamed over
+    /// `..data`, and one symlink per key pointing through `..data`.
+    fn publish(dir: &Path, version: &str, files: &HashMap<&str, &str>) {
+        let data_dir = format!("..{}", version);
+        fs::create_dir(dir.join(&data_dir)).unwrap();
+        for (key, value) in files {
+            fs::write(dir.join(&data_dir).join(key), value).unwrap();
+        }
+
+        symlink(&data_dir, dir.join("..data_tmp")).unwrap();
+        fs::rename(dir.join("..data_tmp"), dir.join("..data")).unwrap();
+
+        for key in files.keys() {
+            let link = dir.join(key);
+            if fs::symlink_metadata(&link).is_err() {
+                symlink(Path::new("..data").join(key), link).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_per_file_reads_kubernetes_volume() {
+        let dir = TempDir::new().unwrap();
+        publish(
+            dir.path(),
+            "2026_10_18_12_00_00.1",
+            &HashMap::from([("tls.crt", "certificate\n"), ("log_level", "info")]),
+        );
+
+        let adapter = KeyPerFileAdapter::from_dir(dir.path()).unwrap();
+        // The dot in a file name is part of the key segment
+        assert_eq!(
+            adapter.get_str(r"tls\.crt").unwrap().unwrap().as_str(),
+            "certificate"
+        );
+        assert_eq!(
+            adapter.get_str("log_level").unwrap().unwrap().as_str(),
+            "info"
+        );
+        // The timestamped directory and `..data` are not keys
+        assert_eq!(adapter.all_keys().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_key_per_file_reload_follows_symlink_swap() {
+        let dir = TempDir::new().unwrap();
+        publish(dir.path(), "v1", &HashMap::from([("log_level", "info")]));
+
+        let mut adapter = KeyPerFileAdapter::from_dir(dir.path()).unwrap();
+        publish(dir.path(), "v2", &HashMap::from([("log_level", "debug")]));
+        adapter.reload().unwrap();
+
+        assert_eq!(
+            adapter.get_str("log_level").unwrap().unwrap().as_str(),
+            "debug"
+        );
+    }
+
+    #[test]
+    fn test_key_per_file_with_builder() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("database")).unwrap();
+        fs::write(dir.path().join("database/password"), "s3cr3t\n").unwrap();
+        fs::write(dir.path().join("name"), "myapp").unwrap();
+
+        let service = DefaultConfigService::builder()
+            .with_key_per_file_dir(dir.path())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            service.get_str("database.password").unwrap().as_str(),
+            "s3cr3t"
+        );
+        assert_eq!(service.get_str("name").unwrap().as_str(), "myapp");
+    }
+
+    #[test]
+    #[cfg(feature = "reload")]
+    fn test_key_per_file_watcher_detects_symlink_swap() {
+        let dir = TempDir::new().unwrap();
+        publish(
+            dir.path(),
+            "v1",
+            &HashMap::from([("log_level", "info"), ("name", "myapp")]),
+        );
+
+        let adapter = KeyPerFileAdapter::from_dir(dir.path()).unwrap();
+        let mut watcher = adapter.watcher(Some(Duration::from_millis(100)));
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+        thread::sleep(Duration::from_millis(100));
+
+        publish(
+            dir.path(),
+            "v2",
+            &HashMap::from([("log_level", "debug"), ("name", "myapp")]),
+        );
+        assert!(wait_for(&changed, "log_level"));
+        watcher.stop().unwrap();
+
+        // Only the key whose value changed is reported
+        assert_eq!(*changed.lock().unwrap(), vec!["log_level".to_string()]);
+        assert_eq!(
+            adapter.get_str("log_level").unwrap().unwrap().as_str(),
+            "debug"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "reload")]
+    fn test_key_per_file_watcher_plain_files() {
+        // Docker secrets and systemd credentials are plain files
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("api_token"), "old\n").unwrap();
+
+        let mut watcher =
+            KeyPerFileWatcher::new(dir.path(), Some(Duration::from_millis(100))).unwrap();
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+        thread::sleep(Duration::from_millis(100));
+
+        fs::write(dir.path().join("api_token"), "new\n").unwrap();
+        assert!(wait_for(&changed, "api_token"));
+
+        fs::remove_file(dir.path().join("api_token")).unwrap();
+        thread::sleep(Duration::from_millis(400));
+        watcher.stop().unwrap();
+
+        assert_eq!(changed.lock().unwrap().len(), 2);
+    }
+}
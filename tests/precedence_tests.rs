@@ -334,3 +334,133 @@ fn test_cache_behavior() {
     // Values should be equal
     assert_eq!(value1.as_str(), value2.as_str());
 }
+
+#[test]
+#[cfg(all(feature = "env", feature = "yaml"))]
+fn test_priority_override_yaml_over_env() {
+    let mut env_guard = EnvGuard::new();
+
+    let mut yaml_file = NamedTempFile::new().unwrap();
+    writeln!(yaml_file, "site.key: yaml_value").unwrap();
+    env_guard.set("SITE_KEY", "env_value");
+
+    // A site file raised above environment variables
+    let yaml = YamlFileAdapter::from_file(yaml_file.path())
+        .unwrap()
+        .with_priority(3);
+    let service = DefaultConfigService::builder()
+        .with_env_vars()
+        .with_source(Box::new(yaml))
+        .build()
+        .unwrap();
+
+    assert_eq!(service.get_str("site.key").unwrap().as_str(), "yaml_value");
+    assert_eq!(service.source_of("site.key"), Some("yaml-file"));
+}
+
+#[test]
+#[cfg(all(feature = "cli", feature = "env"))]
+fn test_priority_override_precedence_chain() {
+    let mut env_guard = EnvGuard::new();
+    env_guard.set("CHAIN_KEY", "env_value");
+
+    let builder = DefaultConfigService::builder()
+        .with_cli_args(vec!["--chain.key=cli_value"])
+        .with_source_priority(Box::new(EnvVarAdapter::new().lowercase_keys(true)), 4);
+
+    let chain: Vec<_> = builder
+        .precedence()
+        .into_iter()
+        .map(|entry| (entry.source_name, entry.priority))
+        .collect();
+    assert_eq!(chain, vec![("env".to_string(), 4), ("cli".to_string(), 3)]);
+
+    let service = builder.build().unwrap();
+    assert_eq!(service.get_str("chain.key").unwrap().as_str(), "env_value");
+}
+
+#[test]
+#[cfg(feature = "yaml")]
+fn test_merge_strategy_yaml_over_yaml() {
+    use hexcfg::domain::MergeStrategy;
+
+    let mut base = NamedTempFile::new().unwrap();
+    writeln!(
+        base,
+        "servers:\n  - a.internal\n  - b.internal\nplugins:\n  - auth\n  - metrics"
+    )
+    .unwrap();
+    let mut site = NamedTempFile::new().unwrap();
+    writeln!(
+        site,
+        "servers:\n  - c.internal\nplugins:\n  - metrics\n  - tracing"
+    )
+    .unwrap();
+
+    let site = YamlFileAdapter::from_file(site.path())
+        .unwrap()
+        .with_priority(2);
+    let service = DefaultConfigService::builder()
+        .with_yaml_file(base.path())
+        .unwrap()
+        .with_source(Box::new(site))
+        .with_merge_strategy("servers", MergeStrategy::Replace)
+        .with_merge_strategy("plugins", MergeStrategy::UniqueUnion)
+        .build()
+        .unwrap();
+
+    // The site list replaces the base list instead of overlaying it
+    let servers = service.get_list("servers").unwrap();
+    assert_eq!(servers, vec![ConfigValue::from("c.internal")]);
+    assert!(!service.snapshot().contains_key("servers.1"));
+
+    let plugins = service.get_list("plugins").unwrap();
+    assert_eq!(
+        plugins,
+        vec![
+            ConfigValue::from("auth"),
+            ConfigValue::from("metrics"),
+            ConfigValue::from("tracing")
+        ]
+    );
+}
+
+#[test]
+#[cfg(all(feature = "env", feature = "yaml"))]
+fn test_merge_strategy_env_over_yaml() {
+    use hexcfg::domain::MergeStrategy;
+
+    let mut env_guard = EnvGuard::new();
+
+    let mut yaml_file = NamedTempFile::new().unwrap();
+    writeln!(
+        yaml_file,
+        "mergetest:\n  hosts:\n    - a.internal\n    - b.internal\n  tags:\n    - web"
+    )
+    .unwrap();
+    env_guard.set("MERGETEST_HOSTS_0", "c.internal");
+    env_guard.set("MERGETEST_TAGS", "canary, web");
+
+    let service = DefaultConfigService::builder()
+        .with_yaml_file(yaml_file.path())
+        .unwrap()
+        .with_env_vars()
+        .with_merge_strategy("mergetest.hosts", MergeStrategy::Replace)
+        .with_merge_strategy("mergetest.tags", MergeStrategy::Append)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        service.get_list("mergetest.hosts").unwrap(),
+        vec![ConfigValue::from("c.internal")]
+    );
+
+    let snapshot = service.snapshot();
+    assert_eq!(snapshot["mergetest.hosts.0"].as_str(), "c.internal");
+    assert!(!snapshot.contains_key("mergetest.hosts.1"));
+    // The comma-separated env list is appended to the YAML list
+    assert_eq!(snapshot["mergetest.tags.0"].as_str(), "web");
+    assert_eq!(snapshot["mergetest.tags.1"].as_str(), "canary");
+    assert_eq!(snapshot["mergetest.tags.2"].as_str(), "web");
+    assert!(!snapshot.contains_key("mergetest.tags"));
+}
@@ -87,6 +87,52 @@ mod redis_tests {
                     .await
                     .unwrap()
             }
+            RedisStorageMode::Document => {
+                // For document mode, store the configuration as a YAML string
+                let client = redis::Client::open(url.as_str()).unwrap();
+                let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+
+                let _: () = redis::cmd("SET")
+                    .arg("test_doc")
+                    .arg("test:\n  key: test_value\ndatabase:\n  host: localhost\n  port: 5432\n")
+                    .query_async(&mut conn)
+                    .await
+                    .unwrap();
+
+                RedisAdapter::new(&url, "test_doc", storage_mode)
+                    .await
+                    .unwrap()
+            }
+            RedisStorageMode::NestedHash => {
+                // For nested hash mode, store each section in its own hash
+                let client = redis::Client::open(url.as_str()).unwrap();
+                let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+
+                let _: () = redis::cmd("HSET")
+                    .arg("test:test")
+                    .arg("key")
+                    .arg("test_value")
+                    .query_async(&mut conn)
+                    .await
+                    .unwrap();
+
+                let _: () = redis::cmd("HSET")
+                    .arg("test:database")
+                    .arg("host")
+                    .arg("localhost")
+                    .arg("port")
+                    .arg("5432")
+                    .query_async(&mut conn)
+                    .await
+                    .unwrap();
+
+                RedisAdapter::new(&url, "test:", storage_mode)
+                    .await
+                    .unwrap()
+            }
+            RedisStorageMode::JsonDocument => {
+                unimplemented!("needs the RedisJSON module, see test_redis_json_document_mode")
+            }
         };
 
         Some((container, adapter))
@@ -621,4 +667,426 @@ mod redis_tests {
             count
         );
     }
+
+    #[tokio::test]
+    async fn test_redis_adapter_watcher_patches_string_keys() {
+        use hexcfg::ports::ConfigWatcher;
+        use redis::Commands;
+        use std::sync::{Arc, Mutex};
+
+        let Some((_container, url)) = setup_redis_watcher_test().await else {
+            return;
+        };
+
+        let client = redis::Client::open(url.as_str()).unwrap();
+        let mut conn = client.get_connection().unwrap();
+        let _: () = conn.set("patch:app.name", "before").unwrap();
+        let _: () = conn.set("patch:app.legacy", "old").unwrap();
+
+        let adapter = RedisAdapter::new(&url, "patch:", RedisStorageMode::StringKeys)
+            .await
+            .unwrap();
+
+        // Changed before the watcher subscribes; picked up by its initial resync
+        let _: () = conn.set("patch:app.name", "after").unwrap();
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        let mut watcher = adapter.watcher();
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        let _: () = conn.set("patch:app.port", "8080").unwrap();
+        let _: () = conn.del("patch:app.legacy").unwrap();
+        let _: () = conn.set_ex("patch:app.temporary", "soon gone", 1).unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+        watcher.stop().unwrap();
+
+        let changed = changed.lock().unwrap();
+        for key in ["app.name", "app.port", "app.legacy", "app.temporary"] {
+            assert!(changed.contains(&key.to_string()), "missing {}", key);
+        }
+
+        // The adapter's cache was patched without a reload
+        assert_eq!(
+            adapter.get_str("app.name").unwrap().unwrap().as_str(),
+            "after"
+        );
+        assert_eq!(
+            adapter.get_str("app.port").unwrap().unwrap().as_str(),
+            "8080"
+        );
+        assert!(adapter.get_str("app.legacy").unwrap().is_none());
+        assert!(adapter.get_str("app.temporary").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_redis_adapter_watcher_hash_change_channel() {
+        use hexcfg::ports::{ConfigChange, ConfigWatcher};
+        use redis::Commands;
+        use std::sync::{Arc, Mutex};
+
+        let Some((_container, url)) = setup_redis_watcher_test().await else {
+            return;
+        };
+
+        let client = redis::Client::open(url.as_str()).unwrap();
+        let mut conn = client.get_connection().unwrap();
+        let _: () = conn.hset("hash:config", "app.name", "before").unwrap();
+
+        let mut adapter = RedisAdapter::new(&url, "hash:config", RedisStorageMode::Hash)
+            .await
+            .unwrap()
+            .with_hash_change_channel("hash:config:changes");
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        let mut watcher = adapter.watcher();
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        // Another writer following the convention
+        let _: () = conn.hset("hash:config", "app.name", "after").unwrap();
+        let _: () = conn.publish("hash:config:changes", "app.name").unwrap();
+
+        // Writes through the adapter publish their fields as well
+        adapter
+            .as_sink()
+            .unwrap()
+            .apply(&[ConfigChange::set("app.port", "8080")])
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        watcher.stop().unwrap();
+
+        let changed = changed.lock().unwrap();
+        assert!(changed.contains(&"app.name".to_string()));
+        assert!(changed.contains(&"app.port".to_string()));
+        assert_eq!(
+            adapter.get_str("app.name").unwrap().unwrap().as_str(),
+            "after"
+        );
+    }
+
+    // === Structured Storage Tests ===
+
+    #[tokio::test]
+    async fn test_redis_document_mode_get() {
+        let Some((_container, mut adapter)) = setup_redis_test(RedisStorageMode::Document).await
+        else {
+            return;
+        };
+
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "localhost"
+        );
+        assert_eq!(
+            adapter.get_str("test.key").unwrap().unwrap().as_str(),
+            "test_value"
+        );
+        assert_eq!(adapter.all_keys().unwrap().len(), 3);
+
+        // Documents are read-only
+        assert!(adapter.as_sink().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_redis_nested_hash_mode() {
+        use hexcfg::ports::ConfigChange;
+
+        let Some((_container, mut adapter)) = setup_redis_test(RedisStorageMode::NestedHash).await
+        else {
+            return;
+        };
+
+        assert_eq!(
+            adapter.get_str("database.port").unwrap().unwrap().as_str(),
+            "5432"
+        );
+        assert_eq!(
+            adapter.get_str("test.key").unwrap().unwrap().as_str(),
+            "test_value"
+        );
+        assert_eq!(adapter.all_keys().unwrap().len(), 3);
+
+        // Writes go to the hash for the key's section
+        adapter
+            .as_sink()
+            .unwrap()
+            .apply(&[
+                ConfigChange::set("database.replica.host", "replica"),
+                ConfigChange::delete("database.port"),
+            ])
+            .unwrap();
+        adapter.reload().unwrap();
+
+        assert_eq!(
+            adapter
+                .get_str("database.replica.host")
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "replica"
+        );
+        assert!(adapter.get_str("database.port").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_redis_nested_hash_watcher_reloads_section() {
+        use hexcfg::ports::ConfigWatcher;
+        use redis::Commands;
+        use std::sync::{Arc, Mutex};
+
+        let Some((_container, url)) = setup_redis_watcher_test().await else {
+            return;
+        };
+
+        let client = redis::Client::open(url.as_str()).unwrap();
+        let mut conn = client.get_connection().unwrap();
+        let _: () = conn
+            .hset_multiple("nested:database", &[("host", "db"), ("port", "5432")])
+            .unwrap();
+
+        let adapter = RedisAdapter::new(&url, "nested:", RedisStorageMode::NestedHash)
+            .await
+            .unwrap();
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        let mut watcher = adapter.watcher();
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        let _: () = conn.hset("nested:database", "host", "db2").unwrap();
+        let _: () = conn.hdel("nested:database", "port").unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        watcher.stop().unwrap();
+
+        let changed = changed.lock().unwrap();
+        assert!(changed.contains(&"database.host".to_string()));
+        assert!(changed.contains(&"database.port".to_string()));
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "db2"
+        );
+        assert!(adapter.get_str("database.port").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_redis_json_document_mode() {
+        if !docker_helpers::is_docker_available() {
+            docker_helpers::print_docker_unavailable_warning("RedisJSON integration test");
+            return;
+        }
+
+        let image = GenericImage::new("redis/redis-stack-server", "7.2.0-v10")
+            .with_exposed_port(6379.into())
+            .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"));
+        let Ok(container) = image.start().await else {
+            return;
+        };
+        let port = container.get_host_port_ipv4(6379).await.unwrap();
+        let url = format!("redis://127.0.0.1:{}", port);
+
+        let client = redis::Client::open(url.as_str()).unwrap();
+        let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+        let _: () = redis::cmd("JSON.SET")
+            .arg("json:config")
+            .arg("$")
+            .arg(r#"{"database": {"host": "localhost", "port": 5432}, "features": ["a", "b"]}"#)
+            .query_async(&mut conn)
+            .await
+            .unwrap();
+
+        let adapter = RedisAdapter::new(&url, "json:config", RedisStorageMode::JsonDocument)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            adapter.get_str("database.host").unwrap().unwrap().as_str(),
+            "localhost"
+        );
+        assert_eq!(
+            adapter.get_str("database.port").unwrap().unwrap().as_str(),
+            "5432"
+        );
+        assert_eq!(
+            adapter.get_str("features.1").unwrap().unwrap().as_str(),
+            "b"
+        );
+    }
+
+    // === Connection Options Tests ===
+
+    #[tokio::test]
+    async fn test_redis_options_non_zero_db() {
+        use hexcfg::adapters::{RedisConnectOptions, RedisWatcher};
+        use hexcfg::ports::ConfigWatcher;
+        use redis::Commands;
+        use std::sync::{Arc, Mutex};
+
+        let Some((_container, url)) = setup_redis_watcher_test().await else {
+            return;
+        };
+
+        let client = redis::Client::open(format!("{}/3", url)).unwrap();
+        let mut conn = client.get_connection().unwrap();
+        let _: () = conn.set("db3:app.name", "from_db3").unwrap();
+
+        let options = RedisConnectOptions::new(url.as_str()).with_db(3);
+        let adapter =
+            RedisAdapter::with_options(options.clone(), "db3:", RedisStorageMode::StringKeys)
+                .await
+                .unwrap();
+        assert_eq!(
+            adapter
+                .get(&ConfigKey::from("app.name"))
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "from_db3"
+        );
+
+        // The watcher subscribes to the same database
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        let mut watcher = RedisWatcher::with_options(options, "db3:").unwrap();
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        let _: () = conn.set("db3:app.port", "8080").unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        watcher.stop().unwrap();
+
+        assert!(changed.lock().unwrap().contains(&"app.port".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_redis_key_separator_translation() {
+        use hexcfg::adapters::{KeySeparator, RedisConnectOptions};
+        use hexcfg::domain::ConfigValue;
+        use hexcfg::ports::ConfigWatcher;
+        use redis::Commands;
+        use std::sync::{Arc, Mutex};
+
+        let Some((_container, url)) = setup_redis_watcher_test().await else {
+            return;
+        };
+
+        let client = redis::Client::open(url.as_str()).unwrap();
+        let mut conn = client.get_connection().unwrap();
+        let _: () = conn.set("sep:db:host", "localhost").unwrap();
+
+        let options =
+            RedisConnectOptions::new(url.as_str()).with_key_separator(KeySeparator::translate(":"));
+        let mut adapter = RedisAdapter::with_options(options, "sep:", RedisStorageMode::StringKeys)
+            .await
+            .unwrap();
+        assert_eq!(
+            adapter
+                .get(&ConfigKey::from("db.host"))
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "localhost"
+        );
+
+        // Writes map back to the stored separator
+        adapter
+            .as_sink()
+            .unwrap()
+            .set(&ConfigKey::from("db.port"), &ConfigValue::from("5432"))
+            .unwrap();
+        let stored: String = conn.get("sep:db:port").unwrap();
+        assert_eq!(stored, "5432");
+
+        // Keyspace events report translated keys
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        let mut watcher = adapter.watcher();
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        let _: () = conn.set("sep:db:user", "admin").unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        watcher.stop().unwrap();
+
+        assert!(changed.lock().unwrap().contains(&"db.user".to_string()));
+        assert_eq!(
+            adapter
+                .get(&ConfigKey::from("db.user"))
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "admin"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redis_options_acl_user() {
+        use hexcfg::adapters::RedisConnectOptions;
+
+        let Some((_container, url)) = setup_redis_watcher_test().await else {
+            return;
+        };
+
+        let client = redis::Client::open(url.as_str()).unwrap();
+        let mut conn = client.get_connection().unwrap();
+        redis::cmd("ACL")
+            .arg("SETUSER")
+            .arg("config-reader")
+            .arg("on")
+            .arg(">reader-pw")
+            .arg("~acl:*")
+            .arg("+@read")
+            .arg("+scan")
+            .query::<()>(&mut conn)
+            .unwrap();
+        redis::cmd("SET")
+            .arg("acl:app.name")
+            .arg("restricted")
+            .query::<()>(&mut conn)
+            .unwrap();
+
+        let wrong = RedisConnectOptions::new(url.as_str()).with_user("config-reader", "wrong");
+        assert!(
+            RedisAdapter::with_options(wrong, "acl:", RedisStorageMode::StringKeys)
+                .await
+                .is_err()
+        );
+
+        let options =
+            RedisConnectOptions::new(url.as_str()).with_user("config-reader", "reader-pw");
+        let adapter = RedisAdapter::with_options(options, "acl:", RedisStorageMode::StringKeys)
+            .await
+            .unwrap();
+        assert_eq!(
+            adapter
+                .get(&ConfigKey::from("app.name"))
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "restricted"
+        );
+    }
 }
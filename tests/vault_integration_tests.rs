@@ -0,0 +1,306 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Integration tests for resolving secrets from Vault against a local
+//! stand-in server.
+
+mod common;
+
+#[cfg(feature = "vault")]
+mod vault_tests {
+    use hexcfg::adapters::{InMemoryAdapter, ResolvingSource, VaultOptions, VaultResolver};
+    use hexcfg::domain::ConfigKey;
+    use hexcfg::ports::{ConfigSource, ConfigWatcher, SecretReference, SecretResolver};
+    use std::collections::{HashMap, HashSet};
+    use std::io::Write;
+    use std::net::TcpStream;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::common::{self, wait_for};
+
+    const ROLE_ID: &str = "my-role";
+    const SECRET_ID: &str = "my-secret-id";
+    const ROOT_TOKEN: &str = "root-token";
+
+    /// Answers the subset of Vault's API the resolver uses: AppRole login,
+    /// token lookup and renewal, and KV v2 reads.
+    struct MockVault {
+        /// KV secrets by path below the `kv` mount, as JSON objects
+        secrets: Mutex<HashMap<String, String>>,
+        /// Tokens currently accepted
+        tokens: Mutex<HashSet<String>>,
+        /// Lease duration of AppRole tokens, in seconds
+        token_ttl: u64,
+        /// Number of AppRole logins
+        logins: Mutex<usize>,
+        /// Number of token renewals
+        renewals: Mutex<usize>,
+        /// Headers of each request received, with lowercase names
+        requests: Mutex<Vec<HashMap<String, String>>>,
+    }
+
+    impl MockVault {
+        fn new(token_ttl: u64) -> Self {
+            Self {
+                secrets: Mutex::new(HashMap::from([(
+                    "myapp/db".to_string(),
+                    r#"{"password":"hunter2","port":5432}"#.to_string(),
+                )])),
+                tokens: Mutex::new(HashSet::from([ROOT_TOKEN.to_string()])),
+                token_ttl,
+                logins: Mutex::new(0),
+                renewals: Mutex::new(0),
+                requests: Mutex::new(Vec::new()),
+            }
+        }
+
+        /// Starts serving on a local port and returns the address.
+        fn start(self) -> (Arc<Self>, String) {
+            let server = Arc::new(self);
+            let handler = Arc::clone(&server);
+            let address = common::serve(move |stream| handler.handle(stream));
+            (server, address)
+        }
+
+        fn set_secret(&self, path: &str, json: &str) {
+            self.secrets
+                .lock()
+                .unwrap()
+                .insert(path.to_string(), json.to_string());
+        }
+
+        /// Revokes every token except the root token.
+        fn revoke_tokens(&self) {
+            self.tokens.lock().unwrap().retain(|t| t == ROOT_TOKEN);
+        }
+
+        fn logins(&self) -> usize {
+            *self.logins.lock().unwrap()
+        }
+
+        fn renewals(&self) -> usize {
+            *self.renewals.lock().unwrap()
+        }
+
+        fn last_request(&self) -> HashMap<String, String> {
+            self.requests.lock().unwrap().last().cloned().unwrap()
+        }
+
+        fn handle(&self, mut stream: TcpStream) {
+            let Some(request) = common::read_request(&stream) else {
+                return;
+            };
+            let headers = request.headers;
+            let body = request.body;
+            self.requests.lock().unwrap().push(headers.clone());
+
+            let method = request.method.as_str();
+            let path = request.target.as_str();
+            let token = headers.get("x-vault-token").cloned().unwrap_or_default();
+            let authorized = self.tokens.lock().unwrap().contains(&token);
+
+            let (status, response) = match (method, path) {
+                ("POST", "/v1/auth/approle/login") => {
+                    if body.contains(ROLE_ID) && body.contains(SECRET_ID) {
+                        let mut logins = self.logins.lock().unwrap();
+                        *logins += 1;
+                        let token = format!("approle-token-{}", logins);
+                        self.tokens.lock().unwrap().insert(token.clone());
+                        (200, self.auth_response(&token))
+                    } else {
+                        (
+                            400,
+                            r#"{"errors":["invalid role or secret ID"]}"#.to_string(),
+                        )
+                    }
+                }
+                ("POST", "/v1/auth/token/renew-self") if authorized => {
+                    *self.renewals.lock().unwrap() += 1;
+                    (200, self.auth_response(&token))
+                }
+                ("GET", "/v1/auth/token/lookup-self") if authorized => {
+                    (200, r#"{"data":{"ttl":0,"renewable":false}}"#.to_string())
+                }
+                ("GET", path) if path.starts_with("/v1/kv/data/") && authorized => {
+                    let secret_path = &path["/v1/kv/data/".len()..];
+                    match self.secrets.lock().unwrap().get(secret_path) {
+                        Some(data) => (
+                            200,
+                            format!(
+                                r#"{{"data":{{"data":{},"metadata":{{"version":1}}}},"lease_duration":0,"renewable":false}}"#,
+                                data
+                            ),
+                        ),
+                        None => (404, r#"{"errors":[]}"#.to_string()),
+                    }
+                }
+                _ => (403, r#"{"errors":["permission denied"]}"#.to_string()),
+            };
+
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            );
+        }
+
+        fn auth_response(&self, token: &str) -> String {
+            format!(
+                r#"{{"auth":{{"client_token":"{}","lease_duration":{},"renewable":true}}}}"#,
+                token, self.token_ttl
+            )
+        }
+    }
+
+    fn inner(values: &[(&str, &str)]) -> Box<dyn ConfigSource> {
+        Box::new(InMemoryAdapter::with_values(
+            values
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        ))
+    }
+
+    fn approle() -> VaultOptions {
+        VaultOptions::new().with_approle(ROLE_ID, SECRET_ID)
+    }
+
+    #[test]
+    fn test_vault_token_auth_resolves_references() {
+        let (server, address) = MockVault::new(3600).start();
+        let resolver = VaultResolver::new(
+            &address,
+            VaultOptions::new()
+                .with_token(ROOT_TOKEN)
+                .with_namespace("team-a"),
+        )
+        .unwrap();
+
+        let source = ResolvingSource::new(
+            inner(&[
+                ("db.password", "secret://vault/kv/myapp/db#password"),
+                ("db.port", "secret://vault/kv/myapp/db#port"),
+                (
+                    "db.url",
+                    "postgres://app:${secret:kv/myapp/db#password}@db/app",
+                ),
+            ]),
+            resolver,
+        )
+        .unwrap();
+
+        let password = source.get_str("db.password").unwrap().unwrap();
+        assert_eq!(password.as_str(), "hunter2");
+        assert!(password.is_secret());
+        assert_eq!(source.get_str("db.port").unwrap().unwrap().as_str(), "5432");
+        assert_eq!(
+            source.get_str("db.url").unwrap().unwrap().as_str(),
+            "postgres://app:hunter2@db/app"
+        );
+
+        let request = server.last_request();
+        assert_eq!(request.get("x-vault-token").unwrap(), ROOT_TOKEN);
+        assert_eq!(request.get("x-vault-namespace").unwrap(), "team-a");
+        assert_eq!(server.logins(), 0);
+    }
+
+    #[test]
+    fn test_vault_approle_login_and_token_renewal() {
+        let (server, address) = MockVault::new(1).start();
+        let resolver = VaultResolver::new(&address, approle()).unwrap();
+        let reference = SecretReference::parse("secret://vault/kv/myapp/db#password").unwrap();
+
+        assert_eq!(resolver.resolve(&reference).unwrap().value, "hunter2");
+        assert_eq!(server.logins(), 1);
+        assert_eq!(server.renewals(), 0);
+
+        // Past two thirds of the one second lease, the token is renewed
+        thread::sleep(Duration::from_millis(750));
+        assert_eq!(resolver.resolve(&reference).unwrap().value, "hunter2");
+        assert_eq!(server.renewals(), 1);
+        assert_eq!(server.logins(), 1);
+    }
+
+    #[test]
+    fn test_vault_approle_logs_in_again_after_revocation() {
+        let (server, address) = MockVault::new(3600).start();
+        let resolver = VaultResolver::new(&address, approle()).unwrap();
+        let reference = SecretReference::parse("secret://vault/kv/myapp/db#password").unwrap();
+
+        resolver.resolve(&reference).unwrap();
+        server.revoke_tokens();
+
+        assert_eq!(resolver.resolve(&reference).unwrap().value, "hunter2");
+        assert_eq!(server.logins(), 2);
+    }
+
+    #[test]
+    fn test_vault_errors_do_not_leak_secrets() {
+        let (_server, address) = MockVault::new(3600).start();
+        let resolver = VaultResolver::new(&address, approle()).unwrap();
+
+        let missing = SecretReference::parse("secret://vault/kv/myapp/cache#password").unwrap();
+        let err = resolver.resolve(&missing).unwrap_err().to_string();
+        assert!(err.contains("Secret not found"));
+
+        // Two fields, so one has to be named
+        let ambiguous = SecretReference::parse("secret://vault/kv/myapp/db").unwrap();
+        let err = resolver.resolve(&ambiguous).unwrap_err().to_string();
+        assert!(err.contains("name one with #<field>"));
+        assert!(!err.contains("hunter2"));
+
+        let bad_credentials =
+            VaultResolver::new(&address, VaultOptions::new().with_approle(ROLE_ID, "wrong"))
+                .unwrap();
+        let err = ResolvingSource::new(
+            inner(&[("db.password", "secret://vault/kv/myapp/db#password")]),
+            bad_credentials,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("db.password"));
+        assert!(!err.contains("wrong"));
+    }
+
+    #[test]
+    fn test_lease_watcher_reports_rotated_secret() {
+        let (server, address) = MockVault::new(3600).start();
+        let resolver = VaultResolver::new(
+            &address,
+            approle().with_default_ttl(Duration::from_millis(200)),
+        )
+        .unwrap();
+
+        let source = ResolvingSource::new(
+            inner(&[
+                ("db.password", "secret://vault/kv/myapp/db#password"),
+                ("db.port", "secret://vault/kv/myapp/db#port"),
+            ]),
+            resolver,
+        )
+        .unwrap();
+        let mut watcher = source.watcher(Some(Duration::from_millis(50)));
+
+        let changed = Arc::new(Mutex::new(Vec::new()));
+        let changed_clone = Arc::clone(&changed);
+        watcher
+            .watch(Arc::new(move |key: ConfigKey| {
+                changed_clone.lock().unwrap().push(key.to_string());
+            }))
+            .unwrap();
+
+        server.set_secret("myapp/db", r#"{"password":"correct horse","port":5432}"#);
+        assert!(wait_for(&changed, "db.password"));
+        watcher.stop().unwrap();
+
+        // Only the key whose value changed is reported
+        assert!(changed.lock().unwrap().iter().all(|k| k == "db.password"));
+        assert_eq!(
+            source.get_str("db.password").unwrap().unwrap().as_str(),
+            "correct horse"
+        );
+    }
+}